@@ -2,15 +2,22 @@
 //!
 //! Handles applying authentication to requests and managing token refresh.
 
-use super::types::{AuthConfig, CachedToken, Location};
+use super::session::SessionState;
+use super::types::{
+    AuthConfig, CachedToken, CsrfConfig, CsrfSource, Location, DEFAULT_REFRESH_MARGIN,
+};
 use crate::error::{Error, Result};
+use crate::secrets::redact;
 use chrono::Utc;
 use jsonwebtoken::{encode, EncodingKey, Header};
+use reqwest::header::{HeaderMap, COOKIE};
 use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 /// Authenticator handles applying authentication to HTTP requests
@@ -19,33 +26,71 @@ pub struct Authenticator {
     config: AuthConfig,
     /// Cached token for OAuth2/Session/JWT auth
     cached_token: Arc<RwLock<Option<CachedToken>>>,
+    /// Logged-in cookie session
+    session: Arc<RwLock<Option<SessionState>>>,
+    /// Incremented whenever credentials are fetched or invalidated
+    generation: AtomicU64,
+    /// Refresh tokens this long before they expire
+    refresh_margin: Duration,
     /// HTTP client for token requests
     http_client: Client,
 }
 
 impl Authenticator {
     /// Create a new authenticator with the given config
+    ///
+    /// The config's secrets are registered with the process-wide redactor.
     pub fn new(config: AuthConfig) -> Self {
+        redact::register_auth(&config);
         Self {
             config,
             cached_token: Arc::new(RwLock::new(None)),
+            session: Arc::new(RwLock::new(None)),
+            generation: AtomicU64::new(0),
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
             http_client: Client::new(),
         }
     }
 
     /// Create an authenticator with a custom HTTP client
     pub fn with_client(config: AuthConfig, http_client: Client) -> Self {
+        redact::register_auth(&config);
         Self {
             config,
             cached_token: Arc::new(RwLock::new(None)),
+            session: Arc::new(RwLock::new(None)),
+            generation: AtomicU64::new(0),
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
             http_client,
         }
     }
 
+    /// Set how long before expiry tokens are refreshed
+    ///
+    /// Long syncs use a larger margin so a token never expires mid-request.
+    #[must_use]
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Credential generation, incremented on every token fetch, login and
+    /// invalidation
+    ///
+    /// `apply` returns the generation of the credentials it applied; pass it
+    /// to `invalidate` when the request is rejected, so concurrent requests
+    /// rejected with the same credentials trigger a single refresh.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
     /// Apply authentication to a request builder
-    pub async fn apply(&self, req: RequestBuilder) -> Result<RequestBuilder> {
-        match &self.config {
-            AuthConfig::None => Ok(req),
+    ///
+    /// Returns the request with the generation of the applied credentials,
+    /// read under the same lock so a concurrent refresh cannot come between.
+    pub async fn apply(&self, req: RequestBuilder) -> Result<(RequestBuilder, u64)> {
+        let req = match &self.config {
+            AuthConfig::None => req,
 
             AuthConfig::ApiKey {
                 location,
@@ -58,27 +103,46 @@ impl Authenticator {
                 match location {
                     Location::Header => {
                         let header = header_name.as_deref().unwrap_or("Authorization");
-                        Ok(req.header(header, val))
+                        req.header(header, val)
                     }
                     Location::Query => {
                         let param = query_param.as_deref().unwrap_or("api_key");
-                        Ok(req.query(&[(param, val)]))
+                        req.query(&[(param, val)])
                     }
                 }
             }
 
-            AuthConfig::Basic { username, password } => {
-                Ok(req.basic_auth(username, Some(password)))
-            }
+            AuthConfig::Basic { username, password } => req.basic_auth(username, Some(password)),
 
-            AuthConfig::Bearer { token } => Ok(req.bearer_auth(token)),
+            AuthConfig::Bearer { token } => req.bearer_auth(token),
+
+            AuthConfig::Session {
+                token_header,
+                token_prefix,
+                ..
+            } => {
+                let (token, generation) = self.get_or_refresh_token().await?;
+                let value = format!("{}{}", token_prefix.as_deref().unwrap_or(""), token);
+                return Ok((req.header(token_header.as_str(), value), generation));
+            }
 
             AuthConfig::Oauth2ClientCredentials { .. }
             | AuthConfig::Oauth2Refresh { .. }
-            | AuthConfig::Session { .. }
             | AuthConfig::Jwt { .. } => {
-                let token = self.get_or_refresh_token().await?;
-                Ok(req.bearer_auth(token))
+                let (token, generation) = self.get_or_refresh_token().await?;
+                return Ok((req.bearer_auth(token), generation));
+            }
+
+            AuthConfig::CookieSession { csrf, .. } => {
+                let (session, generation) = self.get_or_login().await?;
+                let mut req = req;
+                if !session.cookies.is_empty() {
+                    req = req.header(COOKIE, session.cookies.header_value());
+                }
+                if let (Some(csrf), Some(token)) = (csrf, &session.csrf_token) {
+                    req = req.header(csrf.header_name.as_str(), token.as_str());
+                }
+                return Ok((req, generation));
             }
 
             AuthConfig::CustomHeaders { headers } => {
@@ -86,19 +150,24 @@ impl Authenticator {
                 for (key, value) in headers {
                     req = req.header(key.as_str(), value.as_str());
                 }
-                Ok(req)
+                req
             }
-        }
+        };
+        // Static credentials never change generation
+        Ok((req, self.generation()))
     }
 
-    /// Get a valid token, refreshing if necessary
-    async fn get_or_refresh_token(&self) -> Result<String> {
+    /// Get a valid token and its generation, refreshing if necessary
+    ///
+    /// The generation only changes under the token lock, so it is read while
+    /// the lock is held.
+    async fn get_or_refresh_token(&self) -> Result<(String, u64)> {
         // Check if we have a valid cached token
         {
             let cached = self.cached_token.read().await;
             if let Some(token) = cached.as_ref() {
-                if !token.is_expired() {
-                    return Ok(token.token.clone());
+                if !token.expires_within(self.refresh_margin) {
+                    return Ok((token.token.clone(), self.generation()));
                 }
             }
         }
@@ -108,17 +177,152 @@ impl Authenticator {
 
         // Double-check after acquiring write lock (another task might have refreshed)
         if let Some(token) = cached.as_ref() {
-            if !token.is_expired() {
-                return Ok(token.token.clone());
+            if !token.expires_within(self.refresh_margin) {
+                return Ok((token.token.clone(), self.generation()));
             }
         }
 
         // Refresh the token
         let new_token = self.fetch_new_token().await?;
         let token_str = new_token.token.clone();
+        redact::register_secret(&token_str);
         *cached = Some(new_token);
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
 
-        Ok(token_str)
+        Ok((token_str, generation))
+    }
+
+    /// Get the current cookie session and its generation, logging in if necessary
+    async fn get_or_login(&self) -> Result<(SessionState, u64)> {
+        if let Some(session) = self.session.read().await.as_ref() {
+            return Ok((session.clone(), self.generation()));
+        }
+
+        let mut session = self.session.write().await;
+
+        // Double-check after acquiring write lock (another task might have logged in)
+        if let Some(existing) = session.as_ref() {
+            return Ok((existing.clone(), self.generation()));
+        }
+
+        let new_session = self.login().await?;
+        *session = Some(new_session.clone());
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok((new_session, generation))
+    }
+
+    /// Log in and capture session cookies and the CSRF token
+    async fn login(&self) -> Result<SessionState> {
+        let AuthConfig::CookieSession {
+            login_url,
+            login_method,
+            login_body,
+            login_form,
+            csrf,
+            ..
+        } = &self.config
+        else {
+            return Err(Error::auth("Login is only supported for cookie sessions"));
+        };
+
+        let req = self.http_client.request(login_method.clone(), login_url);
+        let req = if *login_form {
+            req.form(login_body)
+        } else {
+            req.json(login_body)
+        };
+        let response = req.send().await.map_err(Error::Http)?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(Error::Auth {
+                message: format!(
+                    "Login request failed with status {}: {}",
+                    status.as_u16(),
+                    redact::redact_str(&body)
+                ),
+            });
+        }
+
+        let mut session = SessionState::default();
+        session.cookies.store_headers(&headers);
+        if session.cookies.is_empty() {
+            return Err(Error::auth("Login response did not set any cookies"));
+        }
+
+        if let Some(csrf) = csrf {
+            let token = match &csrf.source {
+                CsrfSource::Body(path) => serde_json::from_str::<Value>(&body)
+                    .ok()
+                    .and_then(|json| extract_jsonpath(&json, path)),
+                _ => csrf_from_response(csrf, &headers, &session),
+            };
+            session.csrf_token = Some(token.ok_or_else(|| Error::Auth {
+                message: format!("Could not extract CSRF token from {:?}", csrf.source),
+            })?);
+        }
+
+        register_session(&session);
+        Ok(session)
+    }
+
+    /// Update session cookies and CSRF token from an API response
+    ///
+    /// Keeps rolling session cookies and rotated CSRF tokens current for the
+    /// rest of the sync. Does nothing for other auth types.
+    pub async fn observe_response(&self, headers: &HeaderMap) {
+        let AuthConfig::CookieSession { csrf, .. } = &self.config else {
+            return;
+        };
+        let mut guard = self.session.write().await;
+        let Some(session) = guard.as_mut() else {
+            return;
+        };
+
+        session.cookies.store_headers(headers);
+        if let Some(token) = csrf
+            .as_ref()
+            .and_then(|csrf| csrf_from_response(csrf, headers, session))
+        {
+            session.csrf_token = Some(token);
+        }
+        register_session(session);
+    }
+
+    /// Check if a response status means the credentials must be re-established
+    ///
+    /// A 401 invalidates fetched tokens; cookie sessions use their configured
+    /// re-login statuses. Static credentials are never retried.
+    pub fn should_reauthenticate(&self, status: u16) -> bool {
+        match &self.config {
+            AuthConfig::CookieSession {
+                relogin_statuses, ..
+            } => relogin_statuses.contains(&status),
+            AuthConfig::Oauth2ClientCredentials { .. }
+            | AuthConfig::Oauth2Refresh { .. }
+            | AuthConfig::Session { .. }
+            | AuthConfig::Jwt { .. } => status == 401,
+            _ => false,
+        }
+    }
+
+    /// Invalidate the credentials of a generation
+    ///
+    /// Does nothing if the credentials were already replaced since
+    /// `generation` was read, so only the first of several concurrently
+    /// rejected requests clears the cache. Returns whether it was cleared.
+    pub async fn invalidate(&self, generation: u64) -> bool {
+        let mut cached = self.cached_token.write().await;
+        let mut session = self.session.write().await;
+        if self.generation() != generation {
+            return false;
+        }
+        *cached = None;
+        *session = None;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        true
     }
 
     /// Fetch a new token based on auth type
@@ -393,10 +597,11 @@ impl Authenticator {
         }
     }
 
-    /// Clear the cached token (useful for testing or forced refresh)
+    /// Clear the cached token and cookie session (forces refresh or re-login)
     pub async fn clear_cache(&self) {
-        let mut cached = self.cached_token.write().await;
-        *cached = None;
+        *self.cached_token.write().await = None;
+        *self.session.write().await = None;
+        self.generation.fetch_add(1, Ordering::SeqCst);
     }
 
     /// Get the current auth config
@@ -405,6 +610,32 @@ impl Authenticator {
     }
 }
 
+/// Read a CSRF token from response headers or session cookies
+fn csrf_from_response(
+    csrf: &CsrfConfig,
+    headers: &HeaderMap,
+    session: &SessionState,
+) -> Option<String> {
+    match &csrf.source {
+        CsrfSource::Header(name) => headers
+            .get(name.as_str())
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+        CsrfSource::Cookie(name) => session.cookies.get(name).map(str::to_string),
+        CsrfSource::Body(_) => None,
+    }
+}
+
+/// Register session cookies and CSRF token with the redactor
+fn register_session(session: &SessionState) {
+    for value in session.cookies.values() {
+        redact::register_secret(value);
+    }
+    if let Some(token) = &session.csrf_token {
+        redact::register_secret(token);
+    }
+}
+
 /// OAuth2 token response
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
@@ -438,24 +669,11 @@ struct JwtClaims {
     extra: HashMap<String, String>,
 }
 
-/// Extract a value from JSON using a simple JSONPath expression
-/// Supports basic paths like "$.data.token" or "data.token"
+/// Extract a scalar from JSON as a string using a JSONPath expression
+/// (e.g., "$.data.token" or "data.token")
 pub fn extract_jsonpath(value: &Value, path: &str) -> Option<String> {
-    let path = path.strip_prefix("$.").unwrap_or(path);
-    let parts: Vec<&str> = path.split('.').collect();
-
-    let mut current = value;
-    for part in parts {
-        match current {
-            Value::Object(map) => {
-                current = map.get(part)?;
-            }
-            _ => return None,
-        }
-    }
-
-    match current {
-        Value::String(s) => Some(s.clone()),
+    match crate::jsonpath::select_first(value, path)? {
+        Value::String(s) => Some(s),
         Value::Number(n) => Some(n.to_string()),
         Value::Bool(b) => Some(b.to_string()),
         _ => None,
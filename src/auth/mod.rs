@@ -1,15 +1,20 @@
 //! Authentication module
 //!
-//! Supports: API Key, Basic, Bearer, OAuth2, Session, JWT, Custom Headers
+//! Supports: API Key, Basic, Bearer, OAuth2, Session, Cookie Session, JWT, Custom Headers
 //!
 //! The `Authenticator` handles all auth types and manages token caching
-//! for auth types that require token refresh.
+//! for auth types that require token refresh. `AuthProfiles` keeps one
+//! `Authenticator` per named auth profile of a connector.
 
 mod authenticator;
+mod profiles;
+mod session;
 mod types;
 
 pub use authenticator::{extract_jsonpath, Authenticator};
-pub use types::{AuthConfig, CachedToken, Location};
+pub use profiles::AuthProfiles;
+pub use session::{CookieJar, SessionState};
+pub use types::{AuthConfig, CachedToken, CsrfConfig, CsrfSource, Location};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,104 @@
+//! Named auth profiles
+//!
+//! A connector can declare several auth schemes (e.g., an admin token and a
+//! storefront token). Each profile gets its own `Authenticator`, created on
+//! first use and shared by every stream that references the profile, so
+//! tokens are fetched and cached once per profile for the whole sync run.
+
+use super::authenticator::Authenticator;
+use super::types::AuthConfig;
+use crate::error::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Cache of authenticators keyed by profile name
+///
+/// The connector-level (default) auth is stored under `None`.
+#[derive(Default)]
+pub struct AuthProfiles {
+    /// Authenticators by profile name
+    authenticators: HashMap<Option<String>, Arc<Authenticator>>,
+    /// Token refresh margin for created authenticators
+    refresh_margin: Option<Duration>,
+}
+
+impl AuthProfiles {
+    /// Create an empty profile cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refresh tokens of created authenticators this long before they expire
+    #[must_use]
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = Some(margin);
+        self
+    }
+
+    /// Get the authenticator for a profile, if one was created
+    pub fn get(&self, profile: Option<&str>) -> Option<Arc<Authenticator>> {
+        self.authenticators
+            .get(&profile.map(str::to_string))
+            .cloned()
+    }
+
+    /// Register an authenticator for a profile, replacing any existing one
+    pub fn insert(&mut self, profile: Option<&str>, authenticator: Arc<Authenticator>) {
+        self.authenticators
+            .insert(profile.map(str::to_string), authenticator);
+    }
+
+    /// Get the authenticator for a profile, creating it from `build` on first use
+    pub fn get_or_create<F>(
+        &mut self,
+        profile: Option<&str>,
+        build: F,
+    ) -> Result<Arc<Authenticator>>
+    where
+        F: FnOnce() -> Result<AuthConfig>,
+    {
+        if let Some(authenticator) = self.get(profile) {
+            return Ok(authenticator);
+        }
+        let mut authenticator = Authenticator::new(build()?);
+        if let Some(margin) = self.refresh_margin {
+            authenticator = authenticator.with_refresh_margin(margin);
+        }
+        let authenticator = Arc::new(authenticator);
+        self.insert(profile, authenticator.clone());
+        Ok(authenticator)
+    }
+
+    /// Number of profiles with an authenticator
+    pub fn len(&self) -> usize {
+        self.authenticators.len()
+    }
+
+    /// Check if no authenticator was created yet
+    pub fn is_empty(&self) -> bool {
+        self.authenticators.is_empty()
+    }
+
+    /// Clear cached tokens for every profile
+    pub async fn clear_cache(&self) {
+        for authenticator in self.authenticators.values() {
+            authenticator.clear_cache().await;
+        }
+    }
+}
+
+impl std::fmt::Debug for AuthProfiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self
+            .authenticators
+            .keys()
+            .map(|k| k.as_deref().unwrap_or("default"))
+            .collect();
+        names.sort_unstable();
+        f.debug_struct("AuthProfiles")
+            .field("profiles", &names)
+            .field("refresh_margin", &self.refresh_margin)
+            .finish()
+    }
+}
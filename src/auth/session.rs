@@ -0,0 +1,96 @@
+//! Cookie session state
+//!
+//! Holds the cookies and CSRF token of a logged-in cookie session.
+
+use reqwest::header::{HeaderMap, SET_COOKIE};
+use std::collections::BTreeMap;
+
+/// Cookies received from `Set-Cookie` headers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CookieJar {
+    /// Cookie values by name
+    cookies: BTreeMap<String, String>,
+}
+
+impl CookieJar {
+    /// Create an empty cookie jar
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a single `Set-Cookie` header value
+    ///
+    /// Cookies with an empty value or `Max-Age=0` (or negative) are removed,
+    /// which is how servers delete cookies on logout or rotation.
+    pub fn store(&mut self, set_cookie: &str) {
+        let mut parts = set_cookie.split(';');
+        let Some((name, value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+            return;
+        };
+        let name = name.trim();
+        let value = value.trim().trim_matches('"');
+        if name.is_empty() {
+            return;
+        }
+
+        let expired = parts.any(|attr| {
+            attr.split_once('=').is_some_and(|(k, v)| {
+                k.trim().eq_ignore_ascii_case("max-age")
+                    && v.trim().parse::<i64>().is_ok_and(|age| age <= 0)
+            })
+        });
+
+        if expired || value.is_empty() {
+            self.cookies.remove(name);
+        } else {
+            self.cookies.insert(name.to_string(), value.to_string());
+        }
+    }
+
+    /// Store every `Set-Cookie` header of a response
+    pub fn store_headers(&mut self, headers: &HeaderMap) {
+        for value in headers.get_all(SET_COOKIE) {
+            if let Ok(value) = value.to_str() {
+                self.store(value);
+            }
+        }
+    }
+
+    /// Get a cookie value
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name).map(String::as_str)
+    }
+
+    /// Cookie values (for registering with the redactor)
+    pub fn values(&self) -> impl Iterator<Item = &str> {
+        self.cookies.values().map(String::as_str)
+    }
+
+    /// Number of cookies
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Check if the jar is empty
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    /// Value for a `Cookie` request header
+    pub fn header_value(&self) -> String {
+        self.cookies
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// A logged-in cookie session
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    /// Session cookies
+    pub cookies: CookieJar,
+    /// Current CSRF token
+    pub csrf_token: Option<String>,
+}
@@ -28,7 +28,7 @@ async fn test_api_key_header() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     // Build the request to inspect headers
     let built = req.build().unwrap();
@@ -47,7 +47,7 @@ async fn test_api_key_header_with_prefix() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(
@@ -68,7 +68,7 @@ async fn test_api_key_query() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert!(built.url().query().unwrap().contains("apikey=secret123"));
@@ -83,7 +83,7 @@ async fn test_basic_auth() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     let auth_header = built
@@ -110,7 +110,7 @@ async fn test_bearer_auth() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(
@@ -129,7 +129,7 @@ async fn test_custom_headers() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(built.headers().get("X-Custom-1").unwrap(), "value1");
@@ -163,7 +163,7 @@ async fn test_oauth2_client_credentials() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(
@@ -234,7 +234,7 @@ async fn test_oauth2_refresh_token() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(
@@ -274,7 +274,7 @@ async fn test_session_auth() {
 
     let client = reqwest::Client::new();
     let req = client.get("https://example.com/api");
-    let req = auth.apply(req).await.unwrap();
+    let (req, _) = auth.apply(req).await.unwrap();
 
     let built = req.build().unwrap();
     assert_eq!(
@@ -283,6 +283,158 @@ async fn test_session_auth() {
     );
 }
 
+#[test]
+fn test_cookie_jar() {
+    let mut jar = CookieJar::new();
+    jar.store("session=abc123; Path=/; HttpOnly");
+    jar.store("XSRF-TOKEN=tok; Path=/");
+    assert_eq!(jar.get("session"), Some("abc123"));
+    assert_eq!(jar.header_value(), "XSRF-TOKEN=tok; session=abc123");
+
+    // Rotation and deletion
+    jar.store("session=def456; Path=/");
+    jar.store("XSRF-TOKEN=deleted; Max-Age=0");
+    assert_eq!(jar.header_value(), "session=def456");
+    assert_eq!(jar.len(), 1);
+}
+
+#[tokio::test]
+async fn test_cookie_session_csrf_header() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .and(body_string_contains("\"username\":\"admin\""))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .append_header("Set-Cookie", "sessionid=s3ss10n; Path=/; HttpOnly")
+                .append_header("Set-Cookie", "lang=en; Path=/")
+                .append_header("X-CSRF-Token", "csrf-1"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut login_body = HashMap::new();
+    login_body.insert("username".to_string(), "admin".to_string());
+    login_body.insert("password".to_string(), "hunter22".to_string());
+
+    let auth = Authenticator::new(AuthConfig::CookieSession {
+        login_url: format!("{}/login", mock_server.uri()),
+        login_method: reqwest::Method::POST,
+        login_body,
+        login_form: false,
+        csrf: Some(CsrfConfig::new(
+            CsrfSource::Header("X-CSRF-Token".to_string()),
+            "X-CSRF-Token",
+        )),
+        relogin_statuses: vec![401, 419],
+    });
+
+    let client = reqwest::Client::new();
+    let built = auth
+        .apply(client.get("https://example.com/api"))
+        .await
+        .unwrap()
+        .0
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.headers().get("Cookie").unwrap(),
+        "lang=en; sessionid=s3ss10n"
+    );
+    assert_eq!(built.headers().get("X-CSRF-Token").unwrap(), "csrf-1");
+
+    // Rotated CSRF token and rolling session cookie from an API response
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("X-CSRF-Token", "csrf-2".parse().unwrap());
+    headers.insert("Set-Cookie", "sessionid=rolled; Path=/".parse().unwrap());
+    auth.observe_response(&headers).await;
+
+    let built = auth
+        .apply(client.get("https://example.com/api"))
+        .await
+        .unwrap()
+        .0
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.headers().get("Cookie").unwrap(),
+        "lang=en; sessionid=rolled"
+    );
+    assert_eq!(built.headers().get("X-CSRF-Token").unwrap(), "csrf-2");
+
+    assert!(auth.should_reauthenticate(419));
+    assert!(!auth.should_reauthenticate(403));
+}
+
+#[tokio::test]
+async fn test_cookie_session_form_login_csrf_from_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .and(body_string_contains("username=admin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .append_header("Set-Cookie", "PHPSESSID=php1; Path=/")
+                .set_body_json(serde_json::json!({"meta": {"csrf": "body-csrf"}})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut login_body = HashMap::new();
+    login_body.insert("username".to_string(), "admin".to_string());
+
+    let auth = Authenticator::new(AuthConfig::CookieSession {
+        login_url: format!("{}/login", mock_server.uri()),
+        login_method: reqwest::Method::POST,
+        login_body,
+        login_form: true,
+        csrf: Some(CsrfConfig::new(
+            CsrfSource::Body("$.meta.csrf".to_string()),
+            "X-CSRF-Token",
+        )),
+        relogin_statuses: vec![401],
+    });
+
+    let built = auth
+        .apply(reqwest::Client::new().get("https://example.com/api"))
+        .await
+        .unwrap()
+        .0
+        .build()
+        .unwrap();
+    assert_eq!(built.headers().get("Cookie").unwrap(), "PHPSESSID=php1");
+    assert_eq!(built.headers().get("X-CSRF-Token").unwrap(), "body-csrf");
+}
+
+#[tokio::test]
+async fn test_cookie_session_login_without_cookies_fails() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let auth = Authenticator::new(AuthConfig::CookieSession {
+        login_url: format!("{}/login", mock_server.uri()),
+        login_method: reqwest::Method::POST,
+        login_body: HashMap::new(),
+        login_form: false,
+        csrf: None,
+        relogin_statuses: vec![401],
+    });
+
+    let err = auth
+        .apply(reqwest::Client::new().get("https://example.com/api"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("did not set any cookies"));
+}
+
 #[tokio::test]
 async fn test_clear_cache() {
     let mock_server = MockServer::start().await;
@@ -319,6 +471,189 @@ async fn test_clear_cache() {
     let _ = auth.apply(req2).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_auth_profiles_cache_per_profile() {
+    let mock_server = MockServer::start().await;
+
+    for (token_path, token) in [
+        ("/admin/token", "admin-token"),
+        ("/store/token", "store-token"),
+    ] {
+        Mock::given(method("POST"))
+            .and(path(token_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": token,
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let oauth = |token_path: &str| AuthConfig::Oauth2ClientCredentials {
+        token_url: format!("{}{}", mock_server.uri(), token_path),
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        scopes: vec![],
+        token_body: HashMap::new(),
+    };
+
+    let mut profiles = AuthProfiles::new();
+    let client = reqwest::Client::new();
+
+    for profile in [None, Some("storefront"), None, Some("storefront")] {
+        let token_path = if profile.is_some() {
+            "/store/token"
+        } else {
+            "/admin/token"
+        };
+        let auth = profiles
+            .get_or_create(profile, || Ok(oauth(token_path)))
+            .unwrap();
+        let built = auth
+            .apply(client.get("https://example.com/api"))
+            .await
+            .unwrap()
+            .0
+            .build()
+            .unwrap();
+
+        let expected = if profile.is_some() {
+            "Bearer store-token"
+        } else {
+            "Bearer admin-token"
+        };
+        assert_eq!(built.headers().get("Authorization").unwrap(), expected);
+    }
+
+    assert_eq!(profiles.len(), 2);
+    assert!(profiles.get(Some("unknown")).is_none());
+}
+
+#[tokio::test]
+async fn test_proactive_refresh_margin() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "short-lived",
+            "expires_in": 120
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let config = AuthConfig::Oauth2ClientCredentials {
+        token_url: format!("{}/oauth/token", mock_server.uri()),
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        scopes: vec![],
+        token_body: HashMap::new(),
+    };
+    let client = reqwest::Client::new();
+
+    // Default margin: a token valid for two minutes is reused
+    let auth = Authenticator::new(config.clone());
+    for url in ["https://example.com/a", "https://example.com/b"] {
+        let request = auth
+            .apply(client.get(url))
+            .await
+            .unwrap()
+            .0
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer short-lived");
+    }
+
+    // A margin longer than the token's lifetime is capped, so it is still reused
+    let auth = Authenticator::new(config).with_refresh_margin(std::time::Duration::from_secs(300));
+    for url in ["https://example.com/a", "https://example.com/b"] {
+        let request = auth
+            .apply(client.get(url))
+            .await
+            .unwrap()
+            .0
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer short-lived");
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_refresh_is_coalesced() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"access_token": "shared", "expires_in": 3600}))
+                .set_delay(std::time::Duration::from_millis(50)),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let auth = Authenticator::new(AuthConfig::Oauth2ClientCredentials {
+        token_url: format!("{}/oauth/token", mock_server.uri()),
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        scopes: vec![],
+        token_body: HashMap::new(),
+    });
+    let client = reqwest::Client::new();
+
+    let apply_all = || {
+        futures::future::join_all(
+            (0..8).map(|i| auth.apply(client.get(format!("https://example.com/{i}")))),
+        )
+    };
+
+    // Every request carries the generation of the token it was sent with
+    let mut generations = Vec::new();
+    for result in apply_all().await {
+        let (request, generation) = result.unwrap();
+        let request = request.build().unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer shared");
+        generations.push(generation);
+    }
+    assert!(generations.iter().all(|g| *g == generations[0]));
+
+    // All workers rejected with the same token invalidate it only once
+    let cleared: Vec<bool> = futures::future::join_all(
+        generations
+            .iter()
+            .map(|generation| auth.invalidate(*generation)),
+    )
+    .await;
+    assert_eq!(cleared.iter().filter(|c| **c).count(), 1);
+
+    for result in apply_all().await {
+        let (request, generation) = result.unwrap();
+        let request = request.build().unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer shared");
+        assert!(generation > generations[0]);
+    }
+}
+
+#[test]
+fn test_should_reauthenticate() {
+    let oauth = Authenticator::new(AuthConfig::Oauth2Refresh {
+        token_url: "https://example.com/token".to_string(),
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        refresh_token: "refresh".to_string(),
+    });
+    assert!(oauth.should_reauthenticate(401));
+    assert!(!oauth.should_reauthenticate(403));
+
+    let bearer = Authenticator::new(AuthConfig::Bearer {
+        token: "static".to_string(),
+    });
+    assert!(!bearer.should_reauthenticate(401));
+}
+
 #[tokio::test]
 async fn test_oauth2_error_handling() {
     let mock_server = MockServer::start().await;
@@ -98,6 +98,25 @@ pub enum AuthConfig {
         expires_in_path: Option<String>,
     },
 
+    /// Cookie session authentication (login sets session cookies)
+    ///
+    /// Cookies from the login and later responses are kept in a jar for the
+    /// whole sync; a CSRF token can be echoed back in a header.
+    CookieSession {
+        /// Login endpoint URL
+        login_url: String,
+        /// HTTP method for login (POST by default)
+        login_method: reqwest::Method,
+        /// Login request body
+        login_body: HashMap<String, String>,
+        /// Send the login body form-encoded instead of as JSON
+        login_form: bool,
+        /// CSRF token handling
+        csrf: Option<CsrfConfig>,
+        /// Response statuses that trigger a re-login (e.g., 401, 419)
+        relogin_statuses: Vec<u16>,
+    },
+
     /// JWT authentication (service account style)
     Jwt {
         /// Token issuer (iss claim)
@@ -125,11 +144,46 @@ pub enum AuthConfig {
     },
 }
 
+/// Where a CSRF token is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsrfSource {
+    /// Response header with the given name
+    Header(String),
+    /// Cookie with the given name
+    Cookie(String),
+    /// JSONPath into the login response body
+    Body(String),
+}
+
+/// CSRF token handling for cookie sessions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfConfig {
+    /// Where to read the token from
+    pub source: CsrfSource,
+    /// Request header the token is sent in
+    pub header_name: String,
+}
+
+impl CsrfConfig {
+    /// Create a CSRF config
+    pub fn new(source: CsrfSource, header_name: impl Into<String>) -> Self {
+        Self {
+            source,
+            header_name: header_name.into(),
+        }
+    }
+}
+
+/// Default margin before expiry at which tokens are refreshed
+pub const DEFAULT_REFRESH_MARGIN: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Cached token with expiration
 #[derive(Debug, Clone)]
 pub struct CachedToken {
     /// The access token
     pub token: String,
+    /// When the token was fetched
+    issued_at: DateTime<Utc>,
     /// When the token expires
     pub expires_at: Option<DateTime<Utc>>,
 }
@@ -137,24 +191,35 @@ pub struct CachedToken {
 impl CachedToken {
     /// Create a new cached token
     pub fn new(token: String, expires_at: Option<DateTime<Utc>>) -> Self {
-        Self { token, expires_at }
+        Self {
+            token,
+            issued_at: Utc::now(),
+            expires_at,
+        }
     }
 
     /// Create a token that expires in N seconds from now
     pub fn expires_in(token: String, seconds: i64) -> Self {
         let expires_at = Utc::now() + chrono::Duration::seconds(seconds);
-        Self {
-            token,
-            expires_at: Some(expires_at),
-        }
+        Self::new(token, Some(expires_at))
     }
 
-    /// Check if the token is expired (with 30 second buffer)
+    /// Check if the token is expired (with the default refresh margin)
     pub fn is_expired(&self) -> bool {
+        self.expires_within(DEFAULT_REFRESH_MARGIN)
+    }
+
+    /// Check if the token expires within `margin` from now
+    ///
+    /// The margin is capped at half the token's lifetime, so short-lived
+    /// tokens are still reused instead of being refetched on every request.
+    pub fn expires_within(&self, margin: std::time::Duration) -> bool {
         match self.expires_at {
             Some(expires_at) => {
-                let buffer = chrono::Duration::seconds(30);
-                Utc::now() + buffer >= expires_at
+                let margin = chrono::Duration::from_std(margin)
+                    .unwrap_or(chrono::Duration::MAX)
+                    .min((expires_at - self.issued_at) / 2);
+                Utc::now() + margin >= expires_at
             }
             None => false, // No expiration = never expires
         }
@@ -177,6 +242,19 @@ mod type_tests {
         assert!(token.is_expired());
     }
 
+    #[test]
+    fn test_cached_token_refresh_margin() {
+        let mut token = CachedToken::expires_in("test".to_string(), 120);
+        assert!(!token.expires_within(std::time::Duration::from_secs(30)));
+
+        // The margin is capped at half of a two minute lifetime
+        assert!(!token.expires_within(std::time::Duration::from_secs(300)));
+
+        // An hour-old token with two minutes left is within a five minute margin
+        token.issued_at -= chrono::Duration::hours(1);
+        assert!(token.expires_within(std::time::Duration::from_secs(300)));
+    }
+
     #[test]
     fn test_cached_token_no_expiration() {
         let token = CachedToken::new("test".to_string(), None);
@@ -32,6 +32,10 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Require secret config fields to use `{{ secret.<provider>.<key> }}` references
+    #[arg(long, global = true)]
+    pub strict_secrets: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -79,6 +83,18 @@ pub enum Commands {
         /// Emit state after each page
         #[arg(long)]
         state_per_page: bool,
+
+        /// Retry a failed partition this many times (exponential backoff from 1s)
+        #[arg(long, default_value = "0")]
+        partition_retries: u32,
+
+        /// Re-run only the partitions that failed in the previous sync (requires --state)
+        #[arg(long)]
+        retry_failed: bool,
+
+        /// Fail the sync once more records than this are quarantined (0 = unlimited)
+        #[arg(long, default_value = "0")]
+        max_rejected: usize,
     },
 
     /// Show connector specification
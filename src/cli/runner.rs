@@ -1,27 +1,38 @@
 //! CLI runner - executes commands
 
-use crate::auth::{AuthConfig, Authenticator};
+use crate::auth::{AuthConfig, AuthProfiles, Authenticator};
 use crate::cli::commands::{Cli, Commands, OutputFormat};
-use crate::connectors::is_database_connector;
+use crate::connectors::{builtin_secret_fields, is_database_connector};
 use crate::database::DbEngine;
-use crate::decode::{CsvDecoder, JsonDecoder, JsonlDecoder, RecordDecoder};
-use crate::engine::{Message, SyncConfig, SyncEngine};
+use crate::decode::{
+    ArchiveDecoder, ArchiveFormat, AvroDecoder, CsvCoercion, CsvDecoder, DecodeErrorPolicy,
+    GraphqlDecoder, JsonlDecoder, ParquetDecoder, RecordDecoder, StreamDecoder, StreamFormat,
+    XmlDecoder,
+};
+use crate::engine::{Message, SyncConfig, SyncEngine, SyncStats};
 use crate::error::{Error, Result};
 use crate::http::{HttpClient, HttpClientConfig, RateLimiterConfig, RequestConfig};
 use crate::loader::{
-    load_connector, AuthDefinition, ConnectorDefinition, DatabaseConnectionDef,
-    DatabaseEngine as DbType, DatabaseStreamDefinition, DecoderDefinition, PaginationDefinition,
-    PartitionDefinition, StopConditionDefinition,
+    load_connector, AuthDefinition, ConnectorDefinition, CsrfDefinition, DatabaseConnectionDef,
+    DatabaseEngine as DbType, DatabaseStreamDefinition, DecoderDefinition, GraphqlDefinition,
+    PaginationDefinition, ParentFilterDefinition, PartitionDefinition, StopConditionDefinition,
+    StreamDefinition,
 };
 use crate::output::{
     arrow_to_json, build_partitioned_dir, build_partitioned_path, ParquetWriter,
     ParquetWriterConfig,
 };
 use crate::pagination::{
-    CursorPaginator, LinkHeaderPaginator, NextUrlPaginator, NoPaginator, OffsetPaginator,
-    PageNumberPaginator, Paginator, StopCondition,
+    CursorPaginator, CursorSource, KeyOrder, KeysetPaginator, LinkHeaderPaginator,
+    NextUrlPaginator, NoPaginator, OffsetPaginator, PageNumberPaginator, Paginator, RelayPaginator,
+    RequestLocation, StopCondition,
+};
+use crate::partition::{
+    AdaptiveWindows, AsyncJobConfig, CartesianRouter, CompareOp, DatetimeRouter, ListRouter,
+    ParentFilter, ParentRouter, PartitionRouter,
 };
-use crate::partition::{ListRouter, PartitionRouter};
+use crate::schema::JsonSchema;
+use crate::secrets::{redact, require_references, SecretResolver};
 use crate::state::StateManager;
 use crate::template::{self, TemplateContext};
 use base64::Engine as _;
@@ -31,16 +42,27 @@ use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Streamed response messages buffered ahead of the output writer
+const STREAM_OUTPUT_BUFFER: usize = 8;
+
+/// Static auth headers and the optional authenticator of an auth profile
+type ProfileAuth = (HashMap<String, String>, Option<Arc<Authenticator>>);
 
 /// CLI runner
 pub struct Runner {
     cli: Cli,
+    secrets: SecretResolver,
 }
 
 impl Runner {
     /// Create a new runner
     pub fn new(cli: Cli) -> Self {
-        Self { cli }
+        Self {
+            cli,
+            secrets: SecretResolver::from_env(),
+        }
     }
 
     /// Run the CLI command
@@ -57,6 +79,9 @@ impl Runner {
                 output,
                 max_records,
                 state_per_page,
+                partition_retries,
+                retry_failed,
+                max_rejected,
             } => {
                 self.read(
                     streams.as_deref(),
@@ -64,12 +89,15 @@ impl Runner {
                     output.as_deref(),
                     *max_records,
                     *state_per_page,
+                    *partition_retries,
+                    *retry_failed,
+                    *max_rejected,
                 )
                 .await
             }
             Commands::Spec => self.spec(),
             Commands::Validate => self.validate(),
-            Commands::Streams { config_json } => self.streams(config_json.as_deref()),
+            Commands::Streams { config_json } => self.streams(config_json.as_deref()).await,
             Commands::List => self.list_connectors(),
             Commands::Serve {
                 port,
@@ -77,6 +105,7 @@ impl Runner {
             } => {
                 let config = crate::cli::ServerConfig {
                     connectors_dir: connectors_dir.clone(),
+                    strict_secrets: self.cli.strict_secrets,
                 };
                 crate::cli::serve(config, *port).await
             }
@@ -164,8 +193,56 @@ impl Runner {
         }
     }
 
-    /// Load configuration
-    fn load_config(&self, inline: Option<&str>) -> Result<Value> {
+    /// Load configuration and resolve secret references
+    ///
+    /// In strict mode, secret fields must be references rather than raw values.
+    async fn load_config(&self, inline: Option<&str>) -> Result<Value> {
+        let config = self.read_config(inline)?;
+        let secret_fields = self.secret_fields();
+        if self.cli.strict_secrets {
+            require_references(&config, &secret_fields)?;
+        }
+        let config = self.secrets.resolve_value(&config).await?;
+        redact::register_config(&config, &secret_fields);
+        Ok(config)
+    }
+
+    /// Config fields that hold secrets for the selected connector
+    fn secret_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = builtin_secret_fields(&self.get_connector_name())
+            .into_iter()
+            .map(String::from)
+            .collect();
+        if !self.is_database_connector() {
+            if let Ok(connector) = self.load_connector() {
+                for field in connector.secret_config_fields() {
+                    if !fields.contains(&field) {
+                        fields.push(field);
+                    }
+                }
+            }
+        }
+        fields
+    }
+
+    /// Build a template context with config and the connector's secret references
+    async fn build_context(
+        &self,
+        connector: &ConnectorDefinition,
+        config: Value,
+    ) -> Result<TemplateContext> {
+        let definition = serde_json::to_string(connector)?;
+        let mut context = TemplateContext::new();
+        context.set_config(config);
+        context.set_secrets(self.secrets.prefetch(&definition).await?);
+        for auth in connector.auth_definitions() {
+            redact::register_auth_definition(auth, &context);
+        }
+        Ok(context)
+    }
+
+    /// Read raw configuration JSON
+    fn read_config(&self, inline: Option<&str>) -> Result<Value> {
         // Inline config takes precedence
         if let Some(json_str) = inline {
             return serde_json::from_str(json_str)
@@ -200,15 +277,14 @@ impl Runner {
     async fn check(&self, config_json: Option<&str>) -> Result<()> {
         // Handle database connectors
         if self.is_database_connector() {
-            return self.check_database(config_json);
+            return self.check_database(config_json).await;
         }
 
         let connector = self.load_connector()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
 
         // Build template context
-        let mut context = TemplateContext::new();
-        context.set_config(config);
+        let context = self.build_context(&connector, config).await?;
 
         // Render base URL template
         let base_url = template::render(&connector.base_url, &context)?;
@@ -221,15 +297,22 @@ impl Runner {
             }
         }));
 
-        // Build auth headers (use async version for OAuth2)
-        let authenticator = Self::create_authenticator(&connector.auth, &context)?;
-        let auth_headers =
-            Self::build_auth_headers_async(&connector.auth, &context, authenticator.as_ref())
-                .await?;
+        // Build auth headers (the stream's profile when checking via the first stream)
+        let profile = if connector.check.is_none() {
+            connector.streams.first().and_then(|s| s.auth.as_deref())
+        } else {
+            None
+        };
+        let mut auth_profiles = AuthProfiles::new().with_refresh_margin(Duration::from_secs(
+            connector.http.token_refresh_margin_secs,
+        ));
+        let (auth_headers, authenticator) =
+            Self::profile_auth(&connector, profile, &context, &mut auth_profiles)?;
 
         // Build HTTP client with rendered base URL
         let http_config = Self::build_http_config_with_url(&connector, &base_url);
-        let client = HttpClient::with_config(http_config);
+        let mut client = HttpClient::with_config(http_config);
+        client.set_shared_authenticator(authenticator);
 
         // Build check URL - use check.path if defined, otherwise try first stream
         let test_url = if let Some(check_def) = &connector.check {
@@ -293,11 +376,10 @@ impl Runner {
     /// Discover streams
     async fn discover(&self, config_json: Option<&str>, sample_count: usize) -> Result<()> {
         let connector = self.load_connector()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
 
         // Build template context for sampling
-        let mut context = TemplateContext::new();
-        context.set_config(config.clone());
+        let context = self.build_context(&connector, config.clone()).await?;
 
         // Optionally sample data for schema inference
         let inferred_schemas = if sample_count > 0 {
@@ -362,15 +444,14 @@ impl Runner {
         // Render base URL
         let base_url = template::render(&connector.base_url, context)?;
 
-        // Build auth headers (use async version for OAuth2)
-        let authenticator = Self::create_authenticator(&connector.auth, context)?;
-        let auth_headers =
-            Self::build_auth_headers_async(&connector.auth, context, authenticator.as_ref())
-                .await?;
+        // Authenticators per auth profile, shared across streams
+        let mut auth_profiles = AuthProfiles::new().with_refresh_margin(Duration::from_secs(
+            connector.http.token_refresh_margin_secs,
+        ));
 
         // Build HTTP client
         let http_config = Self::build_http_config_with_url(connector, &base_url);
-        let client = HttpClient::with_config(http_config);
+        let mut client = HttpClient::with_config(http_config);
 
         for stream_def in &connector.streams {
             // Skip streams with partitions for now (require parent data)
@@ -414,8 +495,14 @@ impl Runner {
                 params.insert(key.clone(), rendered);
             }
 
-            // Merge headers
-            let mut headers = auth_headers.clone();
+            // Merge headers: stream auth headers + connector headers + stream headers
+            let (mut headers, authenticator) = Self::profile_auth(
+                connector,
+                stream_def.auth.as_deref(),
+                context,
+                &mut auth_profiles,
+            )?;
+            client.set_shared_authenticator(authenticator);
             headers.extend(connector.headers.clone());
             headers.extend(stream_def.headers.clone());
 
@@ -476,34 +563,12 @@ impl Runner {
     /// Extract records from JSON response using decoder definition
     fn extract_records(&self, value: &Value, decoder: &DecoderDefinition) -> Vec<Value> {
         match decoder {
-            DecoderDefinition::Json { records_path } => {
-                if let Some(path) = records_path {
-                    // Navigate to records path
-                    let mut current = value;
-                    for part in path.split('.') {
-                        if let Some(obj) = current.as_object() {
-                            if let Some(next) = obj.get(part) {
-                                current = next;
-                            } else {
-                                return vec![];
-                            }
-                        } else {
-                            return vec![];
-                        }
-                    }
-                    // Extract array
-                    if let Some(arr) = current.as_array() {
-                        arr.clone()
-                    } else {
-                        vec![current.clone()]
-                    }
-                } else if let Some(arr) = value.as_array() {
-                    arr.clone()
-                } else {
-                    vec![value.clone()]
-                }
-            }
-            DecoderDefinition::Jsonl => {
+            DecoderDefinition::Json { .. } => decoder
+                .json_decoder()
+                .unwrap_or_default()
+                .extract_records(value)
+                .unwrap_or_default(),
+            DecoderDefinition::Jsonl { .. } | DecoderDefinition::Stream { .. } => {
                 // JSONL would be line-by-line, but we have full response
                 if let Some(arr) = value.as_array() {
                     arr.clone()
@@ -519,6 +584,7 @@ impl Runner {
     }
 
     /// Read data
+    #[allow(clippy::too_many_arguments)]
     async fn read(
         &self,
         streams: Option<&str>,
@@ -526,6 +592,9 @@ impl Runner {
         output: Option<&str>,
         max_records: Option<usize>,
         state_per_page: bool,
+        partition_retries: u32,
+        retry_failed: bool,
+        max_rejected: usize,
     ) -> Result<()> {
         // Handle database connectors
         if self.is_database_connector() {
@@ -538,24 +607,22 @@ impl Runner {
 
         let sync_start = Instant::now();
         let connector = self.load_connector()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
         let state = self.load_state()?;
 
         // Parse output destination (local or cloud)
         let destination = output.map(CloudDestination::parse).transpose()?;
 
         // Build template context
-        let mut context = TemplateContext::new();
-        context.set_config(config.clone());
+        let context = self.build_context(&connector, config.clone()).await?;
 
         // Render base URL template
         let base_url = template::render(&connector.base_url, &context)?;
 
-        // Build auth headers (use async version for OAuth2)
-        let authenticator = Self::create_authenticator(&connector.auth, &context)?;
-        let auth_headers =
-            Self::build_auth_headers_async(&connector.auth, &context, authenticator.as_ref())
-                .await?;
+        // Authenticators per auth profile, shared across streams
+        let mut auth_profiles = AuthProfiles::new().with_refresh_margin(Duration::from_secs(
+            connector.http.token_refresh_margin_secs,
+        ));
 
         // Parse streams filter
         let stream_filter: Option<Vec<&str>> = streams.map(|s| s.split(',').collect());
@@ -572,6 +639,13 @@ impl Runner {
         if state_per_page {
             sync_config = sync_config.with_state_per_page(true);
         }
+        if retry_failed && self.cli.state.is_none() {
+            return Err(Error::config("--retry-failed requires --state"));
+        }
+        sync_config = sync_config
+            .with_partition_retries(partition_retries, Duration::from_secs(1))
+            .with_failed_partitions_only(retry_failed)
+            .with_max_rejected(max_rejected);
 
         let mut engine = SyncEngine::new(client, state).with_config(sync_config);
 
@@ -588,8 +662,21 @@ impl Runner {
                 }
             }
 
+            // Only streams with failed partitions are re-run in retry-failed mode
+            if retry_failed
+                && engine
+                    .state()
+                    .failed_partitions(&stream_def.name)
+                    .await
+                    .is_empty()
+            {
+                continue;
+            }
+
             let stream_start = Instant::now();
             let records_before = engine.stats().records_synced;
+            let pagination_events_before = engine.stats().pagination_events();
+            let rejected_before = engine.stats().records_rejected;
 
             self.output_message(&json!({
                 "type": "LOG",
@@ -600,54 +687,126 @@ impl Runner {
             }));
 
             // Build decoder
-            let decoder: Box<dyn RecordDecoder> = Self::build_decoder(&stream_def.decoder);
+            let decoder: Box<dyn RecordDecoder> =
+                Self::build_decoder(&stream_def.decoder, stream_def.schema.as_ref());
 
             // Build paginator
             let paginator: Box<dyn Paginator> =
                 Self::build_paginator(stream_def.pagination.as_ref());
 
-            // Merge headers: auth headers + connector headers + stream headers
-            let mut headers = auth_headers.clone();
+            // Merge headers: stream auth headers + connector headers + stream headers
+            let (mut headers, authenticator) = Self::profile_auth(
+                &connector,
+                stream_def.auth.as_deref(),
+                &context,
+                &mut auth_profiles,
+            )?;
+            engine.set_authenticator(authenticator);
+            Self::configure_request(&mut engine, stream_def)?;
             headers.extend(connector.headers.clone());
             headers.extend(stream_def.headers.clone());
 
-            // Sync stream
-            let sync_result = if let Some(partition_def) = &stream_def.partition {
-                // Build partition router
-                let router: Box<dyn PartitionRouter> = Self::build_router(partition_def);
-
-                engine
-                    .sync_partitioned_stream(
-                        &stream_def.name,
-                        &base_url,
-                        &stream_def.request.path,
-                        &stream_def.request.params,
-                        &headers,
-                        decoder.as_ref(),
-                        paginator.as_ref(),
-                        router.as_ref(),
-                        &context,
-                    )
-                    .await
-            } else {
-                engine
-                    .sync_stream(
-                        &stream_def.name,
-                        &base_url,
-                        &stream_def.request.path,
-                        &stream_def.request.params,
-                        &headers,
-                        decoder.as_ref(),
-                        paginator.as_ref(),
-                        &context,
-                        stream_def.cursor_field.as_deref(),
-                    )
-                    .await
+            // Sync stream; streamed responses are written while the sync runs
+            let (output, streamed) = mpsc::channel(STREAM_OUTPUT_BUFFER);
+            engine.set_output(Some(output));
+            let checkpoints = engine.state().share();
+            let sync = async {
+                let result = if let Some(job) = stream_def
+                    .partition
+                    .as_ref()
+                    .and_then(Self::build_async_job)
+                {
+                    engine
+                        .sync_async_job_stream(
+                            &stream_def.name,
+                            &base_url,
+                            &headers,
+                            decoder.as_ref(),
+                            &job,
+                            &context,
+                            stream_def.cursor_field.as_deref(),
+                        )
+                        .await
+                } else if let Some(partition_def) = &stream_def.partition {
+                    // Adaptive date ranges size their own windows; other partitions use a router
+                    match Self::build_adaptive_windows(partition_def, &context) {
+                        Ok(Some(windows)) => {
+                            engine
+                                .sync_adaptive_stream(
+                                    &stream_def.name,
+                                    &base_url,
+                                    &stream_def.request.path,
+                                    &stream_def.request.params,
+                                    &headers,
+                                    decoder.as_ref(),
+                                    paginator.as_ref(),
+                                    windows,
+                                    &context,
+                                )
+                                .await
+                        }
+                        Ok(None) => match Self::build_router(
+                            partition_def,
+                            &mut engine,
+                            stream_def,
+                            &connector,
+                            &mut auth_profiles,
+                            &base_url,
+                            &headers,
+                            &context,
+                        )
+                        .await
+                        {
+                            Ok(router) => {
+                                engine
+                                    .sync_partitioned_stream(
+                                        &stream_def.name,
+                                        &base_url,
+                                        &stream_def.request.path,
+                                        &stream_def.request.params,
+                                        &headers,
+                                        decoder.as_ref(),
+                                        paginator.as_ref(),
+                                        router.as_ref(),
+                                        &context,
+                                        stream_def.cursor_field.as_deref(),
+                                    )
+                                    .await
+                            }
+                            Err(e) => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    engine
+                        .sync_stream(
+                            &stream_def.name,
+                            &base_url,
+                            &stream_def.request.path,
+                            &stream_def.request.params,
+                            &headers,
+                            decoder.as_ref(),
+                            paginator.as_ref(),
+                            &context,
+                            stream_def.cursor_field.as_deref(),
+                        )
+                        .await
+                };
+                engine.set_output(None);
+                result
             };
+            let (sync_result, written) = tokio::join!(
+                sync,
+                self.write_streamed(streamed, destination.as_ref(), &checkpoints)
+            );
+            written?;
 
             let stream_duration_ms = stream_start.elapsed().as_millis() as u64;
             let records_after = engine.stats().records_synced;
             let stream_records = records_after - records_before;
+            let pagination_events = engine.stats().pagination_events() - pagination_events_before;
+            let stream_rejected = engine.stats().records_rejected - rejected_before;
+            let failed_partitions = Self::failed_partitions(engine.state(), &stream_def.name).await;
 
             match sync_result {
                 Ok(messages) => {
@@ -664,6 +823,9 @@ impl Runner {
                         "stream": stream_def.name,
                         "status": "SUCCESS",
                         "records_synced": stream_records,
+                        "records_rejected": stream_rejected,
+                        "pagination_events": pagination_events,
+                        "failed_partitions": failed_partitions,
                         "duration_ms": stream_duration_ms
                     });
 
@@ -693,6 +855,9 @@ impl Runner {
                         "status": "FAILED",
                         "error": e.to_string(),
                         "records_synced": stream_records,
+                        "records_rejected": stream_rejected,
+                        "pagination_events": pagination_events,
+                        "failed_partitions": failed_partitions,
                         "duration_ms": stream_duration_ms
                     }));
                 }
@@ -734,9 +899,12 @@ impl Runner {
                 "status": if failed_streams == 0 { "SUCCEEDED" } else if successful_streams == 0 { "FAILED" } else { "PARTIAL" },
                 "connector": connector.name,
                 "total_records": total_records,
+                "total_rejected": engine.stats().records_rejected,
                 "total_streams": stream_results.len(),
                 "successful_streams": successful_streams,
                 "failed_streams": failed_streams,
+                "pagination": Self::pagination_summary(engine.stats()),
+                "partitions": Self::partition_summary(engine.stats()),
                 "duration_ms": total_duration_ms,
                 "output": {
                     "format": match self.cli.format {
@@ -795,9 +963,9 @@ impl Runner {
     }
 
     /// Check database connection
-    fn check_database(&self, config_json: Option<&str>) -> Result<()> {
+    async fn check_database(&self, config_json: Option<&str>) -> Result<()> {
         let db_type = self.get_database_type()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
         let connection = self.build_database_connection(&config);
 
         let connector_name = self.get_connector_name();
@@ -848,9 +1016,9 @@ impl Runner {
     }
 
     /// List database tables as streams
-    fn streams_database(&self, config_json: Option<&str>) -> Result<()> {
+    async fn streams_database(&self, config_json: Option<&str>) -> Result<()> {
         let db_type = self.get_database_type()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
         let connection = self.build_database_connection(&config);
         let connector_name = self.get_connector_name();
 
@@ -878,7 +1046,7 @@ impl Runner {
     ) -> Result<()> {
         let sync_start = Instant::now();
         let db_type = self.get_database_type()?;
-        let config = self.load_config(config_json)?;
+        let config = self.load_config(config_json).await?;
         let connection = self.build_database_connection(&config);
         let connector_name = self.get_connector_name();
 
@@ -1089,10 +1257,10 @@ impl Runner {
     }
 
     /// List available streams (lightweight, no schemas)
-    fn streams(&self, config_json: Option<&str>) -> Result<()> {
+    async fn streams(&self, config_json: Option<&str>) -> Result<()> {
         // Handle database connectors - requires config for connection
         if self.is_database_connector() {
-            return self.streams_database(config_json);
+            return self.streams_database(config_json).await;
         }
 
         let connector = self.load_connector()?;
@@ -1173,24 +1341,72 @@ impl Runner {
     }
 
     /// Build decoder from definition
-    fn build_decoder(def: &DecoderDefinition) -> Box<dyn RecordDecoder> {
+    fn build_decoder(
+        def: &DecoderDefinition,
+        schema: Option<&JsonSchema>,
+    ) -> Box<dyn RecordDecoder> {
         match def {
-            DecoderDefinition::Json { records_path } => {
-                if let Some(path) = records_path {
-                    Box::new(JsonDecoder::with_path(path))
-                } else {
-                    Box::new(JsonDecoder::new())
-                }
-            }
-            DecoderDefinition::Jsonl => Box::new(JsonlDecoder::new()),
+            DecoderDefinition::Json { .. } => Box::new(def.json_decoder().unwrap_or_default()),
+            DecoderDefinition::Jsonl { on_error } => Box::new(
+                JsonlDecoder::new()
+                    .with_error_policy(DecodeErrorPolicy::parse(on_error).unwrap_or_default()),
+            ),
             DecoderDefinition::Csv {
                 delimiter,
                 has_header,
-            } => Box::new(CsvDecoder::with_options(*delimiter, *has_header)),
-            DecoderDefinition::Xml { records_path: _ } => {
-                // Fall back to JSON decoder for now
-                Box::new(JsonDecoder::new())
+                quote,
+                escape,
+                coerce,
+                on_error,
+            } => {
+                let mut decoder = CsvDecoder::with_options(*delimiter, *has_header)
+                    .with_quote(*quote)
+                    .with_escape(*escape)
+                    .with_coercion(CsvCoercion::parse(coerce).unwrap_or_default())
+                    .with_error_policy(DecodeErrorPolicy::parse(on_error).unwrap_or_default());
+                if let Some(schema) = schema {
+                    decoder = decoder.with_schema(schema);
+                }
+                Box::new(decoder)
             }
+            DecoderDefinition::Xml {
+                records_path,
+                keep_prefixes,
+                coerce,
+            } => {
+                let mut decoder = XmlDecoder::with_element(records_path)
+                    .with_prefixes(*keep_prefixes)
+                    .with_coercion(CsvCoercion::parse(coerce).unwrap_or_default());
+                if let Some(schema) = schema {
+                    decoder = decoder.with_schema(schema);
+                }
+                Box::new(decoder)
+            }
+            DecoderDefinition::Graphql { connection_path } => {
+                Box::new(GraphqlDecoder::new(connection_path))
+            }
+            DecoderDefinition::Parquet => Box::new(ParquetDecoder::new()),
+            DecoderDefinition::Avro => Box::new(AvroDecoder::new()),
+            DecoderDefinition::Archive {
+                format,
+                files,
+                max_unpacked_mb,
+                inner,
+            } => {
+                let decoder = ArchiveDecoder::new(
+                    ArchiveFormat::parse(format).unwrap_or_default(),
+                    Self::build_decoder(inner, schema),
+                )
+                .with_max_size(max_unpacked_mb.saturating_mul(1024 * 1024));
+                match files {
+                    Some(pattern) => Box::new(decoder.with_files(pattern)),
+                    None => Box::new(decoder),
+                }
+            }
+            DecoderDefinition::Stream { format, events } => Box::new(
+                StreamDecoder::new(StreamFormat::parse(format).unwrap_or_default())
+                    .with_events(events),
+            ),
         }
     }
 
@@ -1230,21 +1446,74 @@ impl Runner {
             Some(PaginationDefinition::Cursor {
                 cursor_param,
                 cursor_path,
-                location: _,
-            }) => Box::new(CursorPaginator::new(
-                cursor_param,
-                cursor_path,
-                StopCondition::EmptyPage,
-            )),
+                cursor_header,
+                location,
+                stop,
+            }) => {
+                let stop_condition = Self::build_stop_condition(stop);
+                let mut pag = CursorPaginator::new(
+                    cursor_param,
+                    cursor_path.as_deref().unwrap_or_default(),
+                    stop_condition,
+                )
+                .with_location(RequestLocation::parse(location).unwrap_or_default());
+                if let Some(header) = cursor_header {
+                    pag = pag.with_source(CursorSource::Header(header.clone()));
+                }
+                Box::new(pag)
+            }
+            Some(PaginationDefinition::Keyset {
+                key_param,
+                key_field,
+                location,
+                order,
+                stop,
+            }) => Box::new(
+                KeysetPaginator::new(key_param, key_field, Self::build_stop_condition(stop))
+                    .with_location(RequestLocation::parse(location).unwrap_or_default())
+                    .with_order(KeyOrder::parse(order).unwrap_or_default()),
+            ),
             Some(PaginationDefinition::LinkHeader { rel }) => {
                 Box::new(LinkHeaderPaginator::new(rel))
             }
             Some(PaginationDefinition::NextUrl { next_url_path }) => {
                 Box::new(NextUrlPaginator::new(next_url_path))
             }
+            Some(PaginationDefinition::Relay {
+                page_info_path,
+                cursor_variable,
+            }) => Box::new(RelayPaginator::new(page_info_path, cursor_variable)),
         }
     }
 
+    /// Summarize pagination safeguard events for the sync summary
+    fn pagination_summary(stats: &SyncStats) -> Value {
+        json!({
+            "max_pages_reached": stats.max_pages_reached,
+            "repeated_cursors": stats.repeated_cursors,
+            "repeated_pages": stats.repeated_pages
+        })
+    }
+
+    /// Partition counters for the sync summary
+    fn partition_summary(stats: &SyncStats) -> Value {
+        json!({
+            "synced": stats.partitions_synced,
+            "failed": stats.partitions_failed,
+            "retries": stats.partition_retries
+        })
+    }
+
+    /// Failed partitions of a stream with their last error
+    async fn failed_partitions(state: &StateManager, stream: &str) -> Value {
+        state
+            .failed_partitions(stream)
+            .await
+            .into_iter()
+            .map(|(partition, error)| json!({ "partition": partition, "error": error }))
+            .collect()
+    }
+
     /// Build stop condition from definition
     fn build_stop_condition(def: &StopConditionDefinition) -> StopCondition {
         match def {
@@ -1257,34 +1526,268 @@ impl Runner {
         }
     }
 
+    /// Apply a stream's request method, body, GraphQL query, pagination limits and transformations
+    fn configure_request(engine: &mut SyncEngine, stream_def: &StreamDefinition) -> Result<()> {
+        engine.set_request(
+            stream_def.request.http_method()?,
+            stream_def.request.body.clone(),
+        );
+        engine.set_graphql(
+            stream_def
+                .request
+                .graphql
+                .as_ref()
+                .map(GraphqlDefinition::to_request),
+        );
+        engine.set_pagination_limits(stream_def.pagination_limits.to_limits());
+        engine.set_transformations(stream_def.transform_pipeline()?);
+        Ok(())
+    }
+
+    /// Build a parent filter, rendering its value
+    fn build_parent_filter(
+        def: &ParentFilterDefinition,
+        context: &TemplateContext,
+    ) -> Result<ParentFilter> {
+        let op = CompareOp::parse(&def.op)
+            .ok_or_else(|| Error::config(format!("Invalid parent filter op '{}'", def.op)))?;
+        Ok(match &def.value {
+            Some(value) => {
+                ParentFilter::new(&def.field, op, template::render_value(value, context)?)
+            }
+            None => ParentFilter::child_cursor(&def.field, op),
+        })
+    }
+
+    /// Build adaptive date windows when a date range partition is adaptive
+    fn build_adaptive_windows(
+        def: &PartitionDefinition,
+        context: &TemplateContext,
+    ) -> Result<Option<AdaptiveWindows>> {
+        let PartitionDefinition::DateRange {
+            start,
+            end,
+            step,
+            start_field,
+            end_field,
+            format,
+            adaptive: Some(adaptive),
+        } = def
+        else {
+            return Ok(None);
+        };
+
+        let mut windows = AdaptiveWindows::from_strings(
+            &template::render_optional(start, context),
+            &template::render_optional(end, context),
+            step,
+            adaptive.max_records,
+        )?
+        .with_step_bounds(&adaptive.min_step, &adaptive.max_step)?
+        .with_format(format)
+        .with_params(start_field, end_field);
+        if let Some(path) = &adaptive.total_path {
+            windows = windows.with_total_path(path);
+        }
+        Ok(Some(windows))
+    }
+
+    /// Build the async job config for an `async_job` partition
+    fn build_async_job(def: &PartitionDefinition) -> Option<AsyncJobConfig> {
+        let PartitionDefinition::AsyncJob {
+            create,
+            poll,
+            download,
+        } = def
+        else {
+            return None;
+        };
+
+        Some(
+            AsyncJobConfig::new()
+                .with_create(&create.method, &create.path, create.body.as_deref())
+                .with_job_id_path(&create.job_id_path)
+                .with_poll(&poll.path, poll.interval_secs, poll.max_attempts)
+                .with_status(
+                    &poll.status_path,
+                    &poll.completed_value,
+                    poll.failed_values.iter().map(String::as_str).collect(),
+                )
+                .with_download(&download.path, download.records_path.as_deref()),
+        )
+    }
+
+    /// Build a parent router from the parent stream's records
+    async fn build_parent_router(
+        def: &PartitionDefinition,
+        engine: &mut SyncEngine,
+        child: &StreamDefinition,
+        connector: &ConnectorDefinition,
+        auth_profiles: &mut AuthProfiles,
+        base_url: &str,
+        context: &TemplateContext,
+    ) -> Result<Box<dyn PartitionRouter>> {
+        let PartitionDefinition::Parent {
+            stream,
+            parent_field,
+            partition_field,
+            fields,
+            filters,
+        } = def
+        else {
+            return Err(Error::config("Expected a parent partition"));
+        };
+        let parent = connector
+            .streams
+            .iter()
+            .find(|s| &s.name == stream)
+            .ok_or_else(|| Error::config(format!("Unknown parent stream '{stream}'")))?;
+        let filters = filters
+            .iter()
+            .map(|filter| Self::build_parent_filter(filter, context))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Read the parent stream with its own request settings and auth profile
+        let decoder = Self::build_decoder(&parent.decoder, parent.schema.as_ref());
+        let paginator = Self::build_paginator(parent.pagination.as_ref());
+        let (mut parent_headers, parent_auth) =
+            Self::profile_auth(connector, parent.auth.as_deref(), context, auth_profiles)?;
+        parent_headers.extend(connector.headers.clone());
+        parent_headers.extend(parent.headers.clone());
+        let child_auth = engine.authenticator();
+        engine.set_authenticator(parent_auth);
+        Self::configure_request(engine, parent)?;
+        let records = engine
+            .collect_records(
+                &parent.name,
+                base_url,
+                &parent.request.path,
+                &parent.request.params,
+                &parent_headers,
+                decoder.as_ref(),
+                paginator.as_ref(),
+                context,
+            )
+            .await;
+        engine.set_authenticator(child_auth);
+        Self::configure_request(engine, child)?;
+
+        Ok(Box::new(
+            ParentRouter::new(records?, parent_field, partition_field)
+                .with_fields(fields.clone())
+                .with_filters(filters)
+                .with_cursors(engine.state().partition_cursors(&child.name).await),
+        ))
+    }
+
     /// Build partition router from definition
-    fn build_router(def: &PartitionDefinition) -> Box<dyn PartitionRouter> {
-        match def {
-            PartitionDefinition::List { field, values } => {
-                Box::new(ListRouter::new(values.clone(), field))
+    ///
+    /// List values may come from config (`values_from`) or a lookup request;
+    /// parent partitions read the parent stream's records first; cartesian
+    /// partitions build each component router in turn.
+    #[allow(clippy::too_many_arguments)]
+    async fn build_router(
+        def: &PartitionDefinition,
+        engine: &mut SyncEngine,
+        child: &StreamDefinition,
+        connector: &ConnectorDefinition,
+        auth_profiles: &mut AuthProfiles,
+        base_url: &str,
+        headers: &HashMap<String, String>,
+        context: &TemplateContext,
+    ) -> Result<Box<dyn PartitionRouter>> {
+        Ok(match def {
+            PartitionDefinition::List {
+                field,
+                fields,
+                values,
+                values_from,
+                lookup,
+            } => {
+                let values = if let Some(lookup) = lookup {
+                    let decoder = Self::build_decoder(&lookup.decoder, None);
+                    engine
+                        .fetch_records(
+                            &child.name,
+                            base_url,
+                            lookup.request.http_method()?,
+                            &lookup.request.path,
+                            &lookup.request.params,
+                            lookup.request.body.as_deref(),
+                            headers,
+                            decoder.as_ref(),
+                            context,
+                        )
+                        .await?
+                } else if let Some(template) = values_from {
+                    ListRouter::parse_values(&template::render(template, context)?)
+                } else {
+                    values.clone()
+                };
+                Box::new(ListRouter::from_values(values, field).with_fields(fields.clone()))
             }
             PartitionDefinition::Parent { .. } => {
-                // Parent router requires parent stream records
-                // For now, use empty list
-                Box::new(ListRouter::new(vec![], "parent_id"))
+                Self::build_parent_router(
+                    def,
+                    engine,
+                    child,
+                    connector,
+                    auth_profiles,
+                    base_url,
+                    context,
+                )
+                .await?
             }
-            PartitionDefinition::DateRange { .. } => {
-                // Date range router would need to be implemented
-                Box::new(ListRouter::new(vec![], "date"))
+            PartitionDefinition::DateRange {
+                start,
+                end,
+                step,
+                start_field,
+                end_field,
+                format,
+                ..
+            } => Box::new(DatetimeRouter::from_strings(
+                &template::render_optional(start, context),
+                &template::render_optional(end, context),
+                step,
+                format,
+                start_field,
+                end_field,
+            )?),
+            PartitionDefinition::Cartesian { routers } => {
+                let mut components = Vec::with_capacity(routers.len());
+                for router in routers {
+                    components.push(
+                        Box::pin(Self::build_router(
+                            router,
+                            engine,
+                            child,
+                            connector,
+                            auth_profiles,
+                            base_url,
+                            headers,
+                            context,
+                        ))
+                        .await?,
+                    );
+                }
+                Box::new(CartesianRouter::new(components))
             }
             PartitionDefinition::AsyncJob { .. } => {
                 // Async job router is handled separately in sync_async_job_stream
                 // Return empty list as placeholder
                 Box::new(ListRouter::new(vec![], "job_id"))
             }
-        }
+        })
     }
 
-    /// Build auth headers from auth definition (handles all auth types including OAuth2)
-    async fn build_auth_headers_async(
-        auth: &Option<AuthDefinition>,
+    /// Build auth headers for static auth types (API key, Bearer, Basic)
+    ///
+    /// OAuth2 and session auth types add no headers here; their
+    /// `Authenticator` is attached to the HTTP client and applied per request.
+    fn build_auth_headers(
+        auth: Option<&AuthDefinition>,
         context: &TemplateContext,
-        authenticator: Option<&Arc<Authenticator>>,
     ) -> Result<HashMap<String, String>> {
         let mut headers = HashMap::new();
 
@@ -1293,7 +1796,6 @@ impl Runner {
         };
 
         match auth_def {
-            // Simple auth types - handle synchronously
             AuthDefinition::ApiKey {
                 key,
                 value,
@@ -1318,31 +1820,11 @@ impl Runner {
                     .encode(format!("{rendered_username}:{rendered_password}"));
                 headers.insert("Authorization".to_string(), format!("Basic {credentials}"));
             }
-            AuthDefinition::None => {}
-
-            // OAuth2 types - use authenticator for token management
-            AuthDefinition::OAuth2ClientCredentials { .. }
+            AuthDefinition::None
+            | AuthDefinition::OAuth2ClientCredentials { .. }
             | AuthDefinition::OAuth2RefreshToken { .. }
-            | AuthDefinition::SessionToken { .. } => {
-                if let Some(auth) = authenticator {
-                    // Get token from authenticator (handles caching and refresh)
-                    let req = reqwest::Client::new().get("http://dummy");
-                    let req = auth.apply(req).await?;
-                    // Extract Authorization header from the built request
-                    if let Some(auth_header) = req
-                        .build()
-                        .ok()
-                        .and_then(|r| r.headers().get("Authorization").cloned())
-                    {
-                        headers.insert(
-                            "Authorization".to_string(),
-                            auth_header.to_str().unwrap_or_default().to_string(),
-                        );
-                    }
-                } else {
-                    return Err(Error::auth("OAuth2/Session auth requires authenticator"));
-                }
-            }
+            | AuthDefinition::SessionToken { .. }
+            | AuthDefinition::CookieSession { .. } => {}
         }
 
         Ok(headers)
@@ -1350,7 +1832,7 @@ impl Runner {
 
     /// Convert AuthDefinition (from YAML) to AuthConfig (runtime)
     fn build_auth_config(
-        auth: &Option<AuthDefinition>,
+        auth: Option<&AuthDefinition>,
         context: &TemplateContext,
     ) -> Result<AuthConfig> {
         let Some(auth_def) = auth else {
@@ -1457,35 +1939,91 @@ impl Runner {
                     expires_in_path: None,
                 })
             }
+
+            AuthDefinition::CookieSession { .. } => {
+                Self::build_cookie_session_config(auth_def, context)
+            }
         }
     }
 
-    /// Check if auth type requires async token fetch
-    fn is_oauth2_auth(auth: &Option<AuthDefinition>) -> bool {
+    /// Convert a `cookie_session` AuthDefinition to AuthConfig
+    fn build_cookie_session_config(
+        auth: &AuthDefinition,
+        context: &TemplateContext,
+    ) -> Result<AuthConfig> {
+        let AuthDefinition::CookieSession {
+            login_url,
+            login_method,
+            body,
+            content_type,
+            csrf,
+            relogin_statuses,
+        } = auth
+        else {
+            return Err(Error::config("Expected cookie_session auth"));
+        };
+
+        let rendered_body = template::render(body, context)?;
+        let login_body: HashMap<String, String> =
+            serde_json::from_str(&rendered_body).unwrap_or_default();
+        Ok(AuthConfig::CookieSession {
+            login_url: template::render(login_url, context)?,
+            login_method: login_method
+                .to_uppercase()
+                .parse()
+                .map_err(|_| Error::config(format!("Invalid login method: {login_method}")))?,
+            login_body,
+            login_form: content_type == "form",
+            csrf: csrf.as_ref().map(CsrfDefinition::to_config).transpose()?,
+            relogin_statuses: relogin_statuses.clone(),
+        })
+    }
+
+    /// Check if auth type needs an `Authenticator` (token or session management)
+    fn uses_authenticator(auth: Option<&AuthDefinition>) -> bool {
         matches!(
             auth,
             Some(
                 AuthDefinition::OAuth2ClientCredentials { .. }
                     | AuthDefinition::OAuth2RefreshToken { .. }
                     | AuthDefinition::SessionToken { .. }
+                    | AuthDefinition::CookieSession { .. }
             )
         )
     }
 
-    /// Create an authenticator for OAuth2 auth types
-    fn create_authenticator(
-        auth: &Option<AuthDefinition>,
+    /// Resolve auth for a profile (`None` is the connector-level auth)
+    ///
+    /// Returns the static auth headers and, for OAuth2/session profiles, the
+    /// profile's `Authenticator`. Authenticators are cached in `profiles`, so
+    /// each profile fetches its token or logs in once per run.
+    fn profile_auth(
+        connector: &ConnectorDefinition,
+        profile: Option<&str>,
         context: &TemplateContext,
-    ) -> Result<Option<Arc<Authenticator>>> {
-        if !Self::is_oauth2_auth(auth) {
-            return Ok(None);
-        }
-        let auth_config = Self::build_auth_config(auth, context)?;
-        Ok(Some(Arc::new(Authenticator::new(auth_config))))
+        profiles: &mut AuthProfiles,
+    ) -> Result<ProfileAuth> {
+        let auth = connector.auth_profile(profile)?;
+        let authenticator = if Self::uses_authenticator(auth) {
+            Some(profiles.get_or_create(profile, || Self::build_auth_config(auth, context))?)
+        } else {
+            None
+        };
+        Ok((Self::build_auth_headers(auth, context)?, authenticator))
     }
 
-    /// Output a message
+    /// Output a message (secrets are redacted from everything but records and state)
     fn output_message(&self, msg: &Value) {
+        let redacted;
+        let msg = if matches!(
+            msg.get("type").and_then(Value::as_str),
+            Some("RECORD" | "STATE")
+        ) {
+            msg
+        } else {
+            redacted = redact::redact_value(msg);
+            &redacted
+        };
         match self.cli.format {
             OutputFormat::Json | OutputFormat::Parquet => {
                 println!("{}", serde_json::to_string(msg).unwrap_or_default());
@@ -1574,6 +2112,27 @@ impl Runner {
         Ok(())
     }
 
+    /// Write streamed response messages as they arrive
+    ///
+    /// Event ID checkpoints are committed only after the records sent before
+    /// them are written, so an interrupted stream resumes without gaps.
+    async fn write_streamed(
+        &self,
+        mut streamed: mpsc::Receiver<Message>,
+        destination: Option<&crate::output::CloudDestination>,
+        state: &StateManager,
+    ) -> Result<()> {
+        while let Some(msg) = streamed.recv().await {
+            self.output_engine_message_async(&msg, destination).await?;
+            if let Some((stream, partition_id, event_id)) = msg.event_checkpoint() {
+                state
+                    .set_event_id(stream, partition_id, event_id.to_string())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     /// Convert Arrow batch to Parquet bytes in memory
     fn batch_to_parquet_bytes(batch: &arrow::record_batch::RecordBatch) -> Result<bytes::Bytes> {
         use parquet::arrow::ArrowWriter;
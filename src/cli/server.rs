@@ -1,8 +1,9 @@
 //! HTTP server mode for REST API access to connector operations
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Request, State},
     http::StatusCode,
+    middleware::{self, Next},
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
@@ -13,28 +14,39 @@ use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
-use crate::auth::{AuthConfig, Authenticator};
-use crate::connectors::{self, is_database_connector, list_builtin_info};
+use crate::auth::{AuthConfig, AuthProfiles, Authenticator};
+use crate::connectors::{self, builtin_secret_fields, is_database_connector, list_builtin_info};
 use crate::database::DbEngine;
-use crate::decode::{CsvDecoder, JsonDecoder, JsonlDecoder, RecordDecoder};
-use crate::engine::{Message, SyncConfig, SyncEngine};
+use crate::decode::{
+    ArchiveDecoder, ArchiveFormat, AvroDecoder, CsvCoercion, CsvDecoder, DecodeErrorPolicy,
+    GraphqlDecoder, JsonlDecoder, ParquetDecoder, RecordDecoder, StreamDecoder, StreamFormat,
+    XmlDecoder,
+};
+use crate::engine::{Message, SyncConfig, SyncEngine, SyncStats};
 use crate::error::{Error, Result};
 use crate::http::{HttpClient, HttpClientConfig, RateLimiterConfig, RequestConfig};
 use crate::loader::{
-    load_connector, AuthDefinition, ConnectorDefinition, DatabaseConnectionDef,
-    DatabaseEngine as DbType, DatabaseStreamDefinition, DecoderDefinition, PaginationDefinition,
-    PartitionDefinition, StopConditionDefinition,
+    load_connector, AuthDefinition, ConnectorDefinition, CsrfDefinition, DatabaseConnectionDef,
+    DatabaseEngine as DbType, DatabaseStreamDefinition, DecoderDefinition, GraphqlDefinition,
+    PaginationDefinition, ParentFilterDefinition, PartitionDefinition, StopConditionDefinition,
+    StreamDefinition,
 };
 use crate::output::{build_partitioned_dir, build_partitioned_path, CloudDestination};
 use crate::pagination::{
-    CursorPaginator, LinkHeaderPaginator, NextUrlPaginator, NoPaginator, OffsetPaginator,
-    PageNumberPaginator, Paginator, StopCondition,
+    CursorPaginator, CursorSource, KeyOrder, KeysetPaginator, LinkHeaderPaginator,
+    NextUrlPaginator, NoPaginator, OffsetPaginator, PageNumberPaginator, Paginator, RelayPaginator,
+    RequestLocation, StopCondition,
+};
+use crate::partition::{
+    AdaptiveWindows, AsyncJobConfig, CartesianRouter, CompareOp, DatetimeRouter, ListRouter,
+    ParentFilter, ParentRouter, PartitionRouter,
 };
-use crate::partition::{ListRouter, PartitionRouter};
-use crate::schema::SchemaInferrer;
+use crate::schema::{JsonSchema, SchemaInferrer};
+use crate::secrets::{redact, require_references, SecretResolver};
 use crate::state::StateManager;
 use crate::template::{self, TemplateContext};
 use base64::Engine as _;
@@ -44,12 +56,24 @@ use base64::Engine as _;
 pub struct ServerConfig {
     /// Directory containing connector YAML files
     pub connectors_dir: PathBuf,
+    /// Require secret config fields to be secret references
+    pub strict_secrets: bool,
 }
 
+/// How long the server reuses a resolved secret before fetching it again
+const SECRET_CACHE_TTL: std::time::Duration = std::time::Duration::from_mins(5);
+
+/// Streamed response messages buffered ahead of the response writer
+const STREAM_OUTPUT_BUFFER: usize = 8;
+
 /// App state shared across handlers
 #[derive(Clone)]
 struct AppState {
     config: ServerConfig,
+    /// Secret resolver for connector definitions (shared cache across requests)
+    secrets: Arc<SecretResolver>,
+    /// Secret resolver for request config (remote providers only)
+    request_secrets: Arc<SecretResolver>,
 }
 
 /// Request body for check/discover endpoints
@@ -95,6 +119,15 @@ struct SyncRequest {
     /// Maximum records to sync per stream
     #[serde(default)]
     max_records: Option<usize>,
+    /// Times a failed partition is retried (exponential backoff from 1s)
+    #[serde(default)]
+    partition_retries: u32,
+    /// Re-run only the partitions that failed in the sync that produced `state`
+    #[serde(default)]
+    retry_failed: bool,
+    /// Quarantined records allowed before the sync fails (0 = unlimited)
+    #[serde(default)]
+    max_rejected: usize,
     /// Cursor field configuration for incremental database sync
     /// Maps stream/table name to cursor field name: {"public.users": "updated_at"}
     #[serde(default)]
@@ -128,14 +161,18 @@ impl<T: Serialize> ApiResponse<T> {
         ApiResponse {
             success: false,
             data: None,
-            error: Some(msg.into()),
+            error: Some(redact::redact_str(&msg.into())),
         }
     }
 }
 
 /// Start the HTTP server
 pub async fn serve(config: ServerConfig, port: u16) -> Result<()> {
-    let state = AppState { config };
+    let state = AppState {
+        config,
+        secrets: Arc::new(SecretResolver::from_env().with_ttl(SECRET_CACHE_TTL)),
+        request_secrets: Arc::new(SecretResolver::remote_from_env().with_ttl(SECRET_CACHE_TTL)),
+    };
 
     // Build CORS layer - allow all origins for development
     let cors = CorsLayer::new()
@@ -152,6 +189,7 @@ pub async fn serve(config: ServerConfig, port: u16) -> Result<()> {
         .route("/streams", post(get_streams_post))
         .route("/discover", post(discover))
         .route("/sync", post(sync_data))
+        .layer(middleware::from_fn(redact_scope))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(Arc::new(state));
@@ -170,6 +208,14 @@ pub async fn serve(config: ServerConfig, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// Give each request its own secret redactor
+///
+/// Secrets registered while serving a request are dropped with it and never
+/// scrub another request's output.
+async fn redact_scope(request: Request, next: Next) -> axum::response::Response {
+    redact::scope(next.run(request)).await
+}
+
 /// Health check endpoint
 async fn health() -> impl IntoResponse {
     Json(json!({ "status": "ok" }))
@@ -267,8 +313,13 @@ async fn get_streams(
 /// Get streams for a connector (POST - same as GET but accepts body)
 async fn get_streams_post(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<ConnectorRequest>,
+    Json(mut req): Json<ConnectorRequest>,
 ) -> impl IntoResponse {
+    req.config = match resolve_request_config(&state, &req.connector, &req.config).await {
+        Ok(config) => config,
+        Err(e) => return secret_error_response(&e),
+    };
+
     // Check if this is a database connector
     if is_database_connector(&req.connector) {
         return get_database_streams(&req);
@@ -311,11 +362,73 @@ fn resolve_connector(name: &str, connectors_dir: &std::path::Path) -> Result<Con
     load_connector(&path)
 }
 
+/// Resolve secret references in request config
+///
+/// In strict mode, the connector's secret fields must be references. Config
+/// comes from HTTP clients, so only remote providers (`vault`, `aws`) are
+/// available: `env` and `file` references would read the server's own
+/// environment and filesystem.
+async fn resolve_request_config(
+    state: &AppState,
+    connector: &str,
+    config: &Value,
+) -> Result<Value> {
+    let mut fields: Vec<String> = builtin_secret_fields(connector)
+        .into_iter()
+        .map(String::from)
+        .collect();
+    if !is_database_connector(connector) {
+        if let Ok(def) = resolve_connector(connector, &state.config.connectors_dir) {
+            for field in def.secret_config_fields() {
+                if !fields.contains(&field) {
+                    fields.push(field);
+                }
+            }
+        }
+    }
+    if state.config.strict_secrets {
+        require_references(config, &fields)?;
+    }
+    let config = state.request_secrets.resolve_value(config).await?;
+    redact::register_config(&config, &fields);
+    Ok(config)
+}
+
+/// Build a template context with config and the connector's secret references
+async fn build_context(
+    state: &AppState,
+    connector: &ConnectorDefinition,
+    config: Value,
+) -> Result<TemplateContext> {
+    let definition = serde_json::to_string(connector)?;
+    let mut context = TemplateContext::new();
+    context.set_config(config);
+    context.set_secrets(state.secrets.prefetch(&definition).await?);
+    for auth in connector.auth_definitions() {
+        redact::register_auth_definition(auth, &context);
+    }
+    Ok(context)
+}
+
+/// Error response for secret resolution failures
+fn secret_error_response(e: &Error) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse::<()>::error(format!("Secret error: {e}"))),
+    )
+        .into_response()
+}
+
 /// Check connection to API
 async fn check_connection(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<ConnectorRequest>,
+    Json(mut req): Json<ConnectorRequest>,
 ) -> impl IntoResponse {
+    req.config = match resolve_request_config(&state, &req.connector, &req.config).await {
+        Ok(config) => config,
+        Err(e) => return secret_error_response(&e),
+    };
+
     // Check if this is a database connector
     if is_database_connector(&req.connector) {
         return check_database_connection(&req);
@@ -335,8 +448,10 @@ async fn check_connection(
     };
 
     // Build template context
-    let mut context = TemplateContext::new();
-    context.set_config(req.config);
+    let context = match build_context(&state, &connector, req.config).await {
+        Ok(context) => context,
+        Err(e) => return secret_error_response(&e),
+    };
 
     // Render base URL
     let base_url = match template::render(&connector.base_url, &context) {
@@ -350,21 +465,31 @@ async fn check_connection(
         }
     };
 
-    // Build auth headers
-    let auth_headers = match build_auth_headers_async(&connector.auth, &context).await {
-        Ok(h) => h,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(ApiResponse::<()>::error(format!("Auth error: {e}"))),
-            )
-                .into_response();
-        }
+    // Build auth headers (the stream's profile when checking via the first stream)
+    let profile = if connector.check.is_none() {
+        connector.streams.first().and_then(|s| s.auth.as_deref())
+    } else {
+        None
     };
+    let mut auth_profiles = AuthProfiles::new().with_refresh_margin(
+        std::time::Duration::from_secs(connector.http.token_refresh_margin_secs),
+    );
+    let (auth_headers, authenticator) =
+        match profile_auth(&connector, profile, &context, &mut auth_profiles) {
+            Ok(auth) => auth,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::<()>::error(format!("Auth error: {e}"))),
+                )
+                    .into_response();
+            }
+        };
 
     // Build HTTP client
     let http_config = build_http_config(&connector, &base_url);
-    let client = HttpClient::with_config(http_config);
+    let mut client = HttpClient::with_config(http_config);
+    client.set_shared_authenticator(authenticator);
 
     // Build check URL
     let test_url = if let Some(check_def) = &connector.check {
@@ -438,7 +563,7 @@ async fn check_connection(
                 "type": "CONNECTION_STATUS",
                 "connectionStatus": {
                     "status": "FAILED",
-                    "message": format!("Connection failed: {}", e)
+                    "message": redact::redact_str(&format!("Connection failed: {e}"))
                 }
             }))),
         )
@@ -449,8 +574,13 @@ async fn check_connection(
 /// Discover streams with schemas
 async fn discover(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<DiscoverRequest>,
+    Json(mut req): Json<DiscoverRequest>,
 ) -> impl IntoResponse {
+    req.config = match resolve_request_config(&state, &req.connector, &req.config).await {
+        Ok(config) => config,
+        Err(e) => return secret_error_response(&e),
+    };
+
     let connector = match resolve_connector(&req.connector, &state.config.connectors_dir) {
         Ok(c) => c,
         Err(e) => {
@@ -465,8 +595,10 @@ async fn discover(
     };
 
     // Build template context
-    let mut context = TemplateContext::new();
-    context.set_config(req.config.clone());
+    let context = match build_context(&state, &connector, req.config.clone()).await {
+        Ok(context) => context,
+        Err(e) => return secret_error_response(&e),
+    };
 
     // Sample for schema inference if requested
     let inferred_schemas = if req.sample > 0 {
@@ -546,57 +678,6 @@ fn build_http_config(connector: &ConnectorDefinition, base_url: &str) -> HttpCli
     builder.build()
 }
 
-async fn build_auth_headers_async(
-    auth: &Option<AuthDefinition>,
-    context: &TemplateContext,
-) -> Result<HashMap<String, String>> {
-    use base64::Engine as _;
-
-    let mut headers = HashMap::new();
-
-    let Some(auth_def) = auth else {
-        return Ok(headers);
-    };
-
-    match auth_def {
-        AuthDefinition::ApiKey {
-            key,
-            value,
-            location,
-        } => {
-            let rendered_value = template::render(value, context)?;
-            if location == "header" {
-                headers.insert(key.clone(), rendered_value);
-            }
-        }
-        AuthDefinition::Bearer { token } => {
-            let rendered_token = template::render(token, context)?;
-            headers.insert(
-                "Authorization".to_string(),
-                format!("Bearer {rendered_token}"),
-            );
-        }
-        AuthDefinition::Basic { username, password } => {
-            let rendered_username = template::render(username, context)?;
-            let rendered_password = template::render(password, context)?;
-            let credentials = base64::engine::general_purpose::STANDARD
-                .encode(format!("{rendered_username}:{rendered_password}"));
-            headers.insert("Authorization".to_string(), format!("Basic {credentials}"));
-        }
-        AuthDefinition::None => {}
-        // OAuth2 types would need the full authenticator - for now just return error
-        AuthDefinition::OAuth2ClientCredentials { .. }
-        | AuthDefinition::OAuth2RefreshToken { .. }
-        | AuthDefinition::SessionToken { .. } => {
-            return Err(Error::auth(
-                "OAuth2/Session auth not yet supported in HTTP mode",
-            ));
-        }
-    }
-
-    Ok(headers)
-}
-
 async fn sample_streams_for_schema(
     connector: &ConnectorDefinition,
     context: &TemplateContext,
@@ -605,9 +686,11 @@ async fn sample_streams_for_schema(
     let mut schemas = HashMap::new();
 
     let base_url = template::render(&connector.base_url, context)?;
-    let auth_headers = build_auth_headers_async(&connector.auth, context).await?;
     let http_config = build_http_config(connector, &base_url);
-    let client = HttpClient::with_config(http_config);
+    let mut client = HttpClient::with_config(http_config);
+    let mut auth_profiles = AuthProfiles::new().with_refresh_margin(
+        std::time::Duration::from_secs(connector.http.token_refresh_margin_secs),
+    );
 
     for stream_def in &connector.streams {
         // Skip streams with partitions
@@ -638,7 +721,13 @@ async fn sample_streams_for_schema(
         }
 
         // Merge headers
-        let mut headers = auth_headers.clone();
+        let (mut headers, authenticator) = profile_auth(
+            connector,
+            stream_def.auth.as_deref(),
+            context,
+            &mut auth_profiles,
+        )?;
+        client.set_shared_authenticator(authenticator);
         headers.extend(connector.headers.clone());
         headers.extend(stream_def.headers.clone());
 
@@ -668,32 +757,12 @@ async fn sample_streams_for_schema(
 
 fn extract_records(value: &Value, decoder: &DecoderDefinition) -> Vec<Value> {
     match decoder {
-        DecoderDefinition::Json { records_path } => {
-            if let Some(path) = records_path {
-                let mut current = value;
-                for part in path.split('.') {
-                    if let Some(obj) = current.as_object() {
-                        if let Some(next) = obj.get(part) {
-                            current = next;
-                        } else {
-                            return vec![];
-                        }
-                    } else {
-                        return vec![];
-                    }
-                }
-                if let Some(arr) = current.as_array() {
-                    arr.clone()
-                } else {
-                    vec![current.clone()]
-                }
-            } else if let Some(arr) = value.as_array() {
-                arr.clone()
-            } else {
-                vec![value.clone()]
-            }
-        }
-        DecoderDefinition::Jsonl => {
+        DecoderDefinition::Json { .. } => decoder
+            .json_decoder()
+            .unwrap_or_default()
+            .extract_records(value)
+            .unwrap_or_default(),
+        DecoderDefinition::Jsonl { .. } | DecoderDefinition::Stream { .. } => {
             if let Some(arr) = value.as_array() {
                 arr.clone()
             } else {
@@ -707,8 +776,13 @@ fn extract_records(value: &Value, decoder: &DecoderDefinition) -> Vec<Value> {
 /// Sync data from a connector
 async fn sync_data(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<SyncRequest>,
+    Json(mut req): Json<SyncRequest>,
 ) -> impl IntoResponse {
+    req.config = match resolve_request_config(&state, &req.connector, &req.config).await {
+        Ok(config) => config,
+        Err(e) => return secret_error_response(&e),
+    };
+
     // Check if this is a database connector
     if is_database_connector(&req.connector) {
         return sync_database_data(&req);
@@ -728,8 +802,10 @@ async fn sync_data(
     };
 
     // Build template context
-    let mut context = TemplateContext::new();
-    context.set_config(req.config.clone());
+    let context = match build_context(&state, &connector, req.config.clone()).await {
+        Ok(context) => context,
+        Err(e) => return secret_error_response(&e),
+    };
 
     // Render base URL
     let base_url = match template::render(&connector.base_url, &context) {
@@ -743,29 +819,10 @@ async fn sync_data(
         }
     };
 
-    // Build auth
-    let authenticator = match create_authenticator(&connector.auth, &context) {
-        Ok(a) => a,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(ApiResponse::<()>::error(format!("Auth error: {e}"))),
-            )
-                .into_response();
-        }
-    };
-
-    let auth_headers =
-        match build_auth_headers_full(&connector.auth, &context, authenticator.as_ref()).await {
-            Ok(h) => h,
-            Err(e) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(ApiResponse::<()>::error(format!("Auth error: {e}"))),
-                )
-                    .into_response();
-            }
-        };
+    // Authenticators per auth profile, shared across streams
+    let mut auth_profiles = AuthProfiles::new().with_refresh_margin(
+        std::time::Duration::from_secs(connector.http.token_refresh_margin_secs),
+    );
 
     // Parse output destination
     let destination = match req
@@ -784,6 +841,16 @@ async fn sync_data(
         }
     };
 
+    if req.retry_failed && req.state.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                "retry_failed requires state".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
     // Initialize state manager
     let state_manager = if let Some(state_value) = &req.state {
         match StateManager::from_json(&state_value.to_string()) {
@@ -812,6 +879,10 @@ async fn sync_data(
     if let Some(max) = req.max_records {
         sync_config = sync_config.with_max_records(max);
     }
+    sync_config = sync_config
+        .with_partition_retries(req.partition_retries, std::time::Duration::from_secs(1))
+        .with_failed_partitions_only(req.retry_failed)
+        .with_max_rejected(req.max_rejected);
 
     let mut engine = SyncEngine::new(client, state_manager).with_config(sync_config);
 
@@ -831,82 +902,167 @@ async fn sync_data(
             }
         }
 
+        // Only streams with failed partitions are re-run in retry-failed mode
+        if req.retry_failed
+            && engine
+                .state()
+                .failed_partitions(&stream_def.name)
+                .await
+                .is_empty()
+        {
+            continue;
+        }
+
         let stream_start = std::time::Instant::now();
         let records_before = engine.stats().records_synced;
+        let pagination_events_before = engine.stats().pagination_events();
+        let rejected_before = engine.stats().records_rejected;
 
         // Build decoder
-        let decoder: Box<dyn RecordDecoder> = build_decoder(&stream_def.decoder);
+        let decoder: Box<dyn RecordDecoder> =
+            build_decoder(&stream_def.decoder, stream_def.schema.as_ref());
 
         // Build paginator
         let paginator: Box<dyn Paginator> = build_paginator(stream_def.pagination.as_ref());
 
-        // Merge headers
-        let mut headers = auth_headers.clone();
+        // Merge headers: stream auth headers + connector headers + stream headers
+        let (mut headers, authenticator) = match profile_auth(
+            &connector,
+            stream_def.auth.as_deref(),
+            &context,
+            &mut auth_profiles,
+        ) {
+            Ok(auth) => auth,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::<()>::error(format!("Auth error: {e}"))),
+                )
+                    .into_response();
+            }
+        };
+        engine.set_authenticator(authenticator);
+        if let Err(e) = configure_request(&mut engine, stream_def) {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error(e.to_string())),
+            )
+                .into_response();
+        }
         headers.extend(connector.headers.clone());
         headers.extend(stream_def.headers.clone());
 
-        // Sync stream
-        let sync_result = if let Some(partition_def) = &stream_def.partition {
-            let router: Box<dyn PartitionRouter> = build_router(partition_def);
-            engine
-                .sync_partitioned_stream(
-                    &stream_def.name,
-                    &base_url,
-                    &stream_def.request.path,
-                    &stream_def.request.params,
-                    &headers,
-                    decoder.as_ref(),
-                    paginator.as_ref(),
-                    router.as_ref(),
-                    &context,
-                )
-                .await
-        } else {
-            engine
-                .sync_stream(
-                    &stream_def.name,
-                    &base_url,
-                    &stream_def.request.path,
-                    &stream_def.request.params,
-                    &headers,
-                    decoder.as_ref(),
-                    paginator.as_ref(),
-                    &context,
-                    stream_def.cursor_field.as_deref(),
-                )
-                .await
+        // Sync stream; streamed responses are written while the sync runs
+        let (output, streamed) = mpsc::channel(STREAM_OUTPUT_BUFFER);
+        engine.set_output(Some(output));
+        let checkpoints = engine.state().share();
+        let sync = async {
+            let result = if let Some(job) = stream_def.partition.as_ref().and_then(build_async_job)
+            {
+                engine
+                    .sync_async_job_stream(
+                        &stream_def.name,
+                        &base_url,
+                        &headers,
+                        decoder.as_ref(),
+                        &job,
+                        &context,
+                        stream_def.cursor_field.as_deref(),
+                    )
+                    .await
+            } else if let Some(partition_def) = &stream_def.partition {
+                match build_adaptive_windows(partition_def, &context) {
+                    Ok(Some(windows)) => {
+                        engine
+                            .sync_adaptive_stream(
+                                &stream_def.name,
+                                &base_url,
+                                &stream_def.request.path,
+                                &stream_def.request.params,
+                                &headers,
+                                decoder.as_ref(),
+                                paginator.as_ref(),
+                                windows,
+                                &context,
+                            )
+                            .await
+                    }
+                    Ok(None) => {
+                        match build_router(
+                            partition_def,
+                            &mut engine,
+                            stream_def,
+                            &connector,
+                            &mut auth_profiles,
+                            &base_url,
+                            &headers,
+                            &context,
+                        )
+                        .await
+                        {
+                            Ok(router) => {
+                                engine
+                                    .sync_partitioned_stream(
+                                        &stream_def.name,
+                                        &base_url,
+                                        &stream_def.request.path,
+                                        &stream_def.request.params,
+                                        &headers,
+                                        decoder.as_ref(),
+                                        paginator.as_ref(),
+                                        router.as_ref(),
+                                        &context,
+                                        stream_def.cursor_field.as_deref(),
+                                    )
+                                    .await
+                            }
+                            Err(e) => Err(e),
+                        }
+                    }
+                    Err(e) => Err(e),
+                }
+            } else {
+                engine
+                    .sync_stream(
+                        &stream_def.name,
+                        &base_url,
+                        &stream_def.request.path,
+                        &stream_def.request.params,
+                        &headers,
+                        decoder.as_ref(),
+                        paginator.as_ref(),
+                        &context,
+                        stream_def.cursor_field.as_deref(),
+                    )
+                    .await
+            };
+            engine.set_output(None);
+            result
         };
+        let (sync_result, written) = tokio::join!(
+            sync,
+            write_streamed(
+                streamed,
+                is_parquet,
+                destination.as_ref(),
+                &mut all_records,
+                &checkpoints,
+            )
+        );
+        let sync_result = written.and(sync_result);
 
         let stream_duration_ms = stream_start.elapsed().as_millis() as u64;
         let records_after = engine.stats().records_synced;
         let stream_records = records_after - records_before;
+        let pagination_events = engine.stats().pagination_events() - pagination_events_before;
+        let stream_rejected = engine.stats().records_rejected - rejected_before;
+        let failed_partitions = failed_partitions(engine.state(), &stream_def.name).await;
 
         match sync_result {
             Ok(messages) => {
                 // Process messages
                 for msg in messages {
-                    if let Message::Record { stream, batch } = &msg {
-                        if is_parquet {
-                            // Write to cloud storage if destination specified
-                            if let Some(dest) = destination.as_ref() {
-                                if let Ok(parquet_bytes) = batch_to_parquet_bytes(batch) {
-                                    let _ = dest.write_parquet(stream, parquet_bytes).await;
-                                }
-                            }
-                        } else {
-                            // Collect records as JSON
-                            if let Ok(records) = crate::output::arrow_to_json(batch) {
-                                let emitted_at = chrono::Utc::now().timestamp_millis();
-                                for record in records {
-                                    all_records.push(json!({
-                                        "stream": stream,
-                                        "data": record,
-                                        "emitted_at": emitted_at
-                                    }));
-                                }
-                            }
-                        }
-                    }
+                    write_message(&msg, is_parquet, destination.as_ref(), &mut all_records).await;
                 }
 
                 total_records += stream_records;
@@ -915,6 +1071,9 @@ async fn sync_data(
                     "stream": stream_def.name,
                     "status": "SUCCESS",
                     "records_synced": stream_records,
+                    "records_rejected": stream_rejected,
+                    "pagination_events": pagination_events,
+                    "failed_partitions": failed_partitions,
                     "duration_ms": stream_duration_ms
                 });
 
@@ -933,8 +1092,11 @@ async fn sync_data(
                 stream_results.push(json!({
                     "stream": stream_def.name,
                     "status": "FAILED",
-                    "error": e.to_string(),
+                    "error": redact::redact_str(&e.to_string()),
                     "records_synced": stream_records,
+                    "records_rejected": stream_rejected,
+                    "pagination_events": pagination_events,
+                    "failed_partitions": failed_partitions,
                     "duration_ms": stream_duration_ms
                 }));
             }
@@ -974,9 +1136,12 @@ async fn sync_data(
             "status": status,
             "connector": connector.name,
             "total_records": total_records,
+            "total_rejected": engine.stats().records_rejected,
             "total_streams": stream_results.len(),
             "successful_streams": successful_streams,
             "failed_streams": failed_streams,
+            "pagination": pagination_summary(engine.stats()),
+            "partitions": partition_summary(engine.stats()),
             "duration_ms": total_duration_ms,
             "output": {
                 "format": req.format,
@@ -991,11 +1156,67 @@ async fn sync_data(
     (StatusCode::OK, Json(ApiResponse::success(response))).into_response()
 }
 
-/// Build auth headers with full OAuth2 support
-async fn build_auth_headers_full(
-    auth: &Option<AuthDefinition>,
+/// Add a record message to the response records or the Parquet destination
+async fn write_message(
+    msg: &Message,
+    is_parquet: bool,
+    destination: Option<&CloudDestination>,
+    all_records: &mut Vec<Value>,
+) {
+    let Message::Record { stream, batch } = msg else {
+        return;
+    };
+    if is_parquet {
+        // Write to cloud storage if destination specified
+        if let Some(dest) = destination {
+            if let Ok(parquet_bytes) = batch_to_parquet_bytes(batch) {
+                let _ = dest.write_parquet(stream, parquet_bytes).await;
+            }
+        }
+    } else {
+        // Collect records as JSON
+        if let Ok(records) = crate::output::arrow_to_json(batch) {
+            let emitted_at = chrono::Utc::now().timestamp_millis();
+            for record in records {
+                all_records.push(json!({
+                    "stream": stream,
+                    "data": record,
+                    "emitted_at": emitted_at
+                }));
+            }
+        }
+    }
+}
+
+/// Write streamed response messages as they arrive
+///
+/// Event ID checkpoints are committed only after the records sent before
+/// them are written, so the returned state resumes without gaps.
+async fn write_streamed(
+    mut streamed: mpsc::Receiver<Message>,
+    is_parquet: bool,
+    destination: Option<&CloudDestination>,
+    all_records: &mut Vec<Value>,
+    state: &StateManager,
+) -> Result<()> {
+    while let Some(msg) = streamed.recv().await {
+        write_message(&msg, is_parquet, destination, all_records).await;
+        if let Some((stream, partition_id, event_id)) = msg.event_checkpoint() {
+            state
+                .set_event_id(stream, partition_id, event_id.to_string())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Build auth headers for static auth types (API key, Bearer, Basic)
+///
+/// OAuth2 and session auth types add no headers here; their `Authenticator`
+/// is attached to the HTTP client and applied per request.
+fn build_auth_headers(
+    auth: Option<&AuthDefinition>,
     context: &TemplateContext,
-    authenticator: Option<&Arc<Authenticator>>,
 ) -> Result<HashMap<String, String>> {
     let mut headers = HashMap::new();
 
@@ -1028,37 +1249,46 @@ async fn build_auth_headers_full(
                 .encode(format!("{rendered_username}:{rendered_password}"));
             headers.insert("Authorization".to_string(), format!("Basic {credentials}"));
         }
-        AuthDefinition::None => {}
-        AuthDefinition::OAuth2ClientCredentials { .. }
+        AuthDefinition::None
+        | AuthDefinition::OAuth2ClientCredentials { .. }
         | AuthDefinition::OAuth2RefreshToken { .. }
-        | AuthDefinition::SessionToken { .. } => {
-            if let Some(auth) = authenticator {
-                let req = reqwest::Client::new().get("http://dummy");
-                let req = auth.apply(req).await?;
-                if let Some(auth_header) = req
-                    .build()
-                    .ok()
-                    .and_then(|r| r.headers().get("Authorization").cloned())
-                {
-                    headers.insert(
-                        "Authorization".to_string(),
-                        auth_header.to_str().unwrap_or_default().to_string(),
-                    );
-                }
-            } else {
-                return Err(Error::auth("OAuth2/Session auth requires authenticator"));
-            }
-        }
+        | AuthDefinition::SessionToken { .. }
+        | AuthDefinition::CookieSession { .. } => {}
     }
 
     Ok(headers)
 }
 
-/// Create authenticator for OAuth2 types
-fn create_authenticator(
-    auth: &Option<AuthDefinition>,
+/// Static auth headers and the optional authenticator of an auth profile
+type ProfileAuth = (HashMap<String, String>, Option<Arc<Authenticator>>);
+
+/// Resolve auth for a profile (`None` is the connector-level auth)
+///
+/// Returns the static auth headers and, for OAuth2/session profiles, the
+/// profile's `Authenticator`. Authenticators are cached in `profiles`, so
+/// each profile fetches its token or logs in once per sync.
+fn profile_auth(
+    connector: &ConnectorDefinition,
+    profile: Option<&str>,
+    context: &TemplateContext,
+    profiles: &mut AuthProfiles,
+) -> Result<ProfileAuth> {
+    let auth = connector.auth_profile(profile)?;
+    let authenticator = match profiles.get(profile) {
+        Some(cached) => Some(cached),
+        None => match create_auth_config(auth, context)? {
+            Some(config) => Some(profiles.get_or_create(profile, || Ok(config))?),
+            None => None,
+        },
+    };
+    Ok((build_auth_headers(auth, context)?, authenticator))
+}
+
+/// Build the auth config for OAuth2 and session types (`None` for static auth)
+fn create_auth_config(
+    auth: Option<&AuthDefinition>,
     context: &TemplateContext,
-) -> Result<Option<Arc<Authenticator>>> {
+) -> Result<Option<AuthConfig>> {
     let Some(auth_def) = auth else {
         return Ok(None);
     };
@@ -1077,7 +1307,7 @@ fn create_authenticator(
                 scopes: scopes.clone(),
                 token_body: HashMap::new(),
             };
-            Ok(Some(Arc::new(Authenticator::new(config))))
+            Ok(Some(config))
         }
         AuthDefinition::OAuth2RefreshToken {
             token_url,
@@ -1091,7 +1321,7 @@ fn create_authenticator(
                 client_secret: template::render(client_secret, context)?,
                 refresh_token: template::render(refresh_token, context)?,
             };
-            Ok(Some(Arc::new(Authenticator::new(config))))
+            Ok(Some(config))
         }
         AuthDefinition::SessionToken {
             login_url,
@@ -1116,28 +1346,99 @@ fn create_authenticator(
                 },
                 expires_in_path: None,
             };
-            Ok(Some(Arc::new(Authenticator::new(config))))
+            Ok(Some(config))
+        }
+        AuthDefinition::CookieSession {
+            login_url,
+            login_method,
+            body,
+            content_type,
+            csrf,
+            relogin_statuses,
+        } => {
+            let rendered_body = template::render(body, context)?;
+            let login_body: HashMap<String, String> =
+                serde_json::from_str(&rendered_body).unwrap_or_default();
+            let config = AuthConfig::CookieSession {
+                login_url: template::render(login_url, context)?,
+                login_method: login_method
+                    .to_uppercase()
+                    .parse()
+                    .map_err(|_| Error::config(format!("Invalid login method: {login_method}")))?,
+                login_body,
+                login_form: content_type == "form",
+                csrf: csrf.as_ref().map(CsrfDefinition::to_config).transpose()?,
+                relogin_statuses: relogin_statuses.clone(),
+            };
+            Ok(Some(config))
         }
         _ => Ok(None),
     }
 }
 
 /// Build decoder from definition
-fn build_decoder(def: &DecoderDefinition) -> Box<dyn RecordDecoder> {
+fn build_decoder(def: &DecoderDefinition, schema: Option<&JsonSchema>) -> Box<dyn RecordDecoder> {
     match def {
-        DecoderDefinition::Json { records_path } => {
-            if let Some(path) = records_path {
-                Box::new(JsonDecoder::with_path(path))
-            } else {
-                Box::new(JsonDecoder::new())
-            }
-        }
-        DecoderDefinition::Jsonl => Box::new(JsonlDecoder::new()),
+        DecoderDefinition::Json { .. } => Box::new(def.json_decoder().unwrap_or_default()),
+        DecoderDefinition::Jsonl { on_error } => Box::new(
+            JsonlDecoder::new()
+                .with_error_policy(DecodeErrorPolicy::parse(on_error).unwrap_or_default()),
+        ),
         DecoderDefinition::Csv {
             delimiter,
             has_header,
-        } => Box::new(CsvDecoder::with_options(*delimiter, *has_header)),
-        DecoderDefinition::Xml { .. } => Box::new(JsonDecoder::new()),
+            quote,
+            escape,
+            coerce,
+            on_error,
+        } => {
+            let mut decoder = CsvDecoder::with_options(*delimiter, *has_header)
+                .with_quote(*quote)
+                .with_escape(*escape)
+                .with_coercion(CsvCoercion::parse(coerce).unwrap_or_default())
+                .with_error_policy(DecodeErrorPolicy::parse(on_error).unwrap_or_default());
+            if let Some(schema) = schema {
+                decoder = decoder.with_schema(schema);
+            }
+            Box::new(decoder)
+        }
+        DecoderDefinition::Xml {
+            records_path,
+            keep_prefixes,
+            coerce,
+        } => {
+            let mut decoder = XmlDecoder::with_element(records_path)
+                .with_prefixes(*keep_prefixes)
+                .with_coercion(CsvCoercion::parse(coerce).unwrap_or_default());
+            if let Some(schema) = schema {
+                decoder = decoder.with_schema(schema);
+            }
+            Box::new(decoder)
+        }
+        DecoderDefinition::Graphql { connection_path } => {
+            Box::new(GraphqlDecoder::new(connection_path))
+        }
+        DecoderDefinition::Parquet => Box::new(ParquetDecoder::new()),
+        DecoderDefinition::Avro => Box::new(AvroDecoder::new()),
+        DecoderDefinition::Archive {
+            format,
+            files,
+            max_unpacked_mb,
+            inner,
+        } => {
+            let decoder = ArchiveDecoder::new(
+                ArchiveFormat::parse(format).unwrap_or_default(),
+                build_decoder(inner, schema),
+            )
+            .with_max_size(max_unpacked_mb.saturating_mul(1024 * 1024));
+            match files {
+                Some(pattern) => Box::new(decoder.with_files(pattern)),
+                None => Box::new(decoder),
+            }
+        }
+        DecoderDefinition::Stream { format, events } => Box::new(
+            StreamDecoder::new(StreamFormat::parse(format).unwrap_or_default()).with_events(events),
+        ),
     }
 }
 
@@ -1177,19 +1478,72 @@ fn build_paginator(def: Option<&PaginationDefinition>) -> Box<dyn Paginator> {
         Some(PaginationDefinition::Cursor {
             cursor_param,
             cursor_path,
-            ..
-        }) => Box::new(CursorPaginator::new(
-            cursor_param,
-            cursor_path,
-            StopCondition::EmptyPage,
-        )),
+            cursor_header,
+            location,
+            stop,
+        }) => {
+            let stop_condition = build_stop_condition(stop);
+            let mut pag = CursorPaginator::new(
+                cursor_param,
+                cursor_path.as_deref().unwrap_or_default(),
+                stop_condition,
+            )
+            .with_location(RequestLocation::parse(location).unwrap_or_default());
+            if let Some(header) = cursor_header {
+                pag = pag.with_source(CursorSource::Header(header.clone()));
+            }
+            Box::new(pag)
+        }
+        Some(PaginationDefinition::Keyset {
+            key_param,
+            key_field,
+            location,
+            order,
+            stop,
+        }) => Box::new(
+            KeysetPaginator::new(key_param, key_field, build_stop_condition(stop))
+                .with_location(RequestLocation::parse(location).unwrap_or_default())
+                .with_order(KeyOrder::parse(order).unwrap_or_default()),
+        ),
         Some(PaginationDefinition::LinkHeader { rel }) => Box::new(LinkHeaderPaginator::new(rel)),
         Some(PaginationDefinition::NextUrl { next_url_path }) => {
             Box::new(NextUrlPaginator::new(next_url_path))
         }
+        Some(PaginationDefinition::Relay {
+            page_info_path,
+            cursor_variable,
+        }) => Box::new(RelayPaginator::new(page_info_path, cursor_variable)),
     }
 }
 
+/// Summarize pagination safeguard events for the sync result
+fn pagination_summary(stats: &SyncStats) -> Value {
+    json!({
+        "max_pages_reached": stats.max_pages_reached,
+        "repeated_cursors": stats.repeated_cursors,
+        "repeated_pages": stats.repeated_pages
+    })
+}
+
+/// Partition counters for the sync result
+fn partition_summary(stats: &SyncStats) -> Value {
+    json!({
+        "synced": stats.partitions_synced,
+        "failed": stats.partitions_failed,
+        "retries": stats.partition_retries
+    })
+}
+
+/// Failed partitions of a stream with their last error
+async fn failed_partitions(state: &StateManager, stream: &str) -> Value {
+    state
+        .failed_partitions(stream)
+        .await
+        .into_iter()
+        .map(|(partition, error)| json!({ "partition": partition, "error": error }))
+        .collect()
+}
+
 /// Build stop condition from definition
 fn build_stop_condition(def: &StopConditionDefinition) -> StopCondition {
     match def {
@@ -1200,16 +1554,253 @@ fn build_stop_condition(def: &StopConditionDefinition) -> StopCondition {
     }
 }
 
+/// Apply a stream's request method, body, GraphQL query, pagination limits and transformations
+fn configure_request(engine: &mut SyncEngine, stream_def: &StreamDefinition) -> Result<()> {
+    engine.set_request(
+        stream_def.request.http_method()?,
+        stream_def.request.body.clone(),
+    );
+    engine.set_graphql(
+        stream_def
+            .request
+            .graphql
+            .as_ref()
+            .map(GraphqlDefinition::to_request),
+    );
+    engine.set_pagination_limits(stream_def.pagination_limits.to_limits());
+    engine.set_transformations(stream_def.transform_pipeline()?);
+    Ok(())
+}
+
+/// Build a parent filter, rendering its value
+fn build_parent_filter(
+    def: &ParentFilterDefinition,
+    context: &TemplateContext,
+) -> Result<ParentFilter> {
+    let op = CompareOp::parse(&def.op)
+        .ok_or_else(|| Error::config(format!("Invalid parent filter op '{}'", def.op)))?;
+    Ok(match &def.value {
+        Some(value) => ParentFilter::new(&def.field, op, template::render_value(value, context)?),
+        None => ParentFilter::child_cursor(&def.field, op),
+    })
+}
+
+/// Build adaptive date windows when a date range partition is adaptive
+fn build_adaptive_windows(
+    def: &PartitionDefinition,
+    context: &TemplateContext,
+) -> Result<Option<AdaptiveWindows>> {
+    let PartitionDefinition::DateRange {
+        start,
+        end,
+        step,
+        start_field,
+        end_field,
+        format,
+        adaptive: Some(adaptive),
+    } = def
+    else {
+        return Ok(None);
+    };
+
+    let mut windows = AdaptiveWindows::from_strings(
+        &template::render_optional(start, context),
+        &template::render_optional(end, context),
+        step,
+        adaptive.max_records,
+    )?
+    .with_step_bounds(&adaptive.min_step, &adaptive.max_step)?
+    .with_format(format)
+    .with_params(start_field, end_field);
+    if let Some(path) = &adaptive.total_path {
+        windows = windows.with_total_path(path);
+    }
+    Ok(Some(windows))
+}
+
+/// Build the async job config for an `async_job` partition
+fn build_async_job(def: &PartitionDefinition) -> Option<AsyncJobConfig> {
+    let PartitionDefinition::AsyncJob {
+        create,
+        poll,
+        download,
+    } = def
+    else {
+        return None;
+    };
+
+    Some(
+        AsyncJobConfig::new()
+            .with_create(&create.method, &create.path, create.body.as_deref())
+            .with_job_id_path(&create.job_id_path)
+            .with_poll(&poll.path, poll.interval_secs, poll.max_attempts)
+            .with_status(
+                &poll.status_path,
+                &poll.completed_value,
+                poll.failed_values.iter().map(String::as_str).collect(),
+            )
+            .with_download(&download.path, download.records_path.as_deref()),
+    )
+}
+
+/// Build a parent router from the parent stream's records
+async fn build_parent_router(
+    def: &PartitionDefinition,
+    engine: &mut SyncEngine,
+    child: &StreamDefinition,
+    connector: &ConnectorDefinition,
+    auth_profiles: &mut AuthProfiles,
+    base_url: &str,
+    context: &TemplateContext,
+) -> Result<Box<dyn PartitionRouter>> {
+    let PartitionDefinition::Parent {
+        stream,
+        parent_field,
+        partition_field,
+        fields,
+        filters,
+    } = def
+    else {
+        return Err(Error::config("Expected a parent partition"));
+    };
+    let parent = connector
+        .streams
+        .iter()
+        .find(|s| &s.name == stream)
+        .ok_or_else(|| Error::config(format!("Unknown parent stream '{stream}'")))?;
+    let filters = filters
+        .iter()
+        .map(|filter| build_parent_filter(filter, context))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Read the parent stream with its own request settings and auth profile
+    let decoder = build_decoder(&parent.decoder, parent.schema.as_ref());
+    let paginator = build_paginator(parent.pagination.as_ref());
+    let (mut parent_headers, parent_auth) =
+        profile_auth(connector, parent.auth.as_deref(), context, auth_profiles)?;
+    parent_headers.extend(connector.headers.clone());
+    parent_headers.extend(parent.headers.clone());
+    let child_auth = engine.authenticator();
+    engine.set_authenticator(parent_auth);
+    configure_request(engine, parent)?;
+    let records = engine
+        .collect_records(
+            &parent.name,
+            base_url,
+            &parent.request.path,
+            &parent.request.params,
+            &parent_headers,
+            decoder.as_ref(),
+            paginator.as_ref(),
+            context,
+        )
+        .await;
+    engine.set_authenticator(child_auth);
+    configure_request(engine, child)?;
+
+    Ok(Box::new(
+        ParentRouter::new(records?, parent_field, partition_field)
+            .with_fields(fields.clone())
+            .with_filters(filters)
+            .with_cursors(engine.state().partition_cursors(&child.name).await),
+    ))
+}
+
 /// Build partition router from definition
-fn build_router(def: &PartitionDefinition) -> Box<dyn PartitionRouter> {
-    match def {
-        PartitionDefinition::List { field, values } => {
-            Box::new(ListRouter::new(values.clone(), field))
+///
+/// List values may come from config (`values_from`) or a lookup request;
+/// parent partitions read the parent stream's records first; cartesian
+/// partitions build each component router in turn.
+#[allow(clippy::too_many_arguments)]
+async fn build_router(
+    def: &PartitionDefinition,
+    engine: &mut SyncEngine,
+    child: &StreamDefinition,
+    connector: &ConnectorDefinition,
+    auth_profiles: &mut AuthProfiles,
+    base_url: &str,
+    headers: &HashMap<String, String>,
+    context: &TemplateContext,
+) -> Result<Box<dyn PartitionRouter>> {
+    Ok(match def {
+        PartitionDefinition::List {
+            field,
+            fields,
+            values,
+            values_from,
+            lookup,
+        } => {
+            let values = if let Some(lookup) = lookup {
+                let decoder = build_decoder(&lookup.decoder, None);
+                engine
+                    .fetch_records(
+                        &child.name,
+                        base_url,
+                        lookup.request.http_method()?,
+                        &lookup.request.path,
+                        &lookup.request.params,
+                        lookup.request.body.as_deref(),
+                        headers,
+                        decoder.as_ref(),
+                        context,
+                    )
+                    .await?
+            } else if let Some(template) = values_from {
+                ListRouter::parse_values(&template::render(template, context)?)
+            } else {
+                values.clone()
+            };
+            Box::new(ListRouter::from_values(values, field).with_fields(fields.clone()))
+        }
+        PartitionDefinition::Parent { .. } => {
+            build_parent_router(
+                def,
+                engine,
+                child,
+                connector,
+                auth_profiles,
+                base_url,
+                context,
+            )
+            .await?
+        }
+        PartitionDefinition::DateRange {
+            start,
+            end,
+            step,
+            start_field,
+            end_field,
+            format,
+            ..
+        } => Box::new(DatetimeRouter::from_strings(
+            &template::render_optional(start, context),
+            &template::render_optional(end, context),
+            step,
+            format,
+            start_field,
+            end_field,
+        )?),
+        PartitionDefinition::Cartesian { routers } => {
+            let mut components = Vec::with_capacity(routers.len());
+            for router in routers {
+                components.push(
+                    Box::pin(build_router(
+                        router,
+                        engine,
+                        child,
+                        connector,
+                        auth_profiles,
+                        base_url,
+                        headers,
+                        context,
+                    ))
+                    .await?,
+                );
+            }
+            Box::new(CartesianRouter::new(components))
         }
-        PartitionDefinition::Parent { .. } => Box::new(ListRouter::new(vec![], "parent_id")),
-        PartitionDefinition::DateRange { .. } => Box::new(ListRouter::new(vec![], "date")),
         PartitionDefinition::AsyncJob { .. } => Box::new(ListRouter::new(vec![], "job_id")),
-    }
+    })
 }
 
 /// Convert Arrow batch to Parquet bytes
@@ -1460,7 +2051,7 @@ fn sync_database_data(req: &SyncRequest) -> axum::response::Response {
                 stream_results.push(json!({
                     "stream": table,
                     "status": "FAILED",
-                    "error": e.to_string(),
+                    "error": redact::redact_str(&e.to_string()),
                     "records_synced": 0,
                     "duration_ms": stream_duration_ms
                 }));
@@ -1604,7 +2195,7 @@ fn check_database_connection(req: &ConnectorRequest) -> axum::response::Response
                         "type": "CONNECTION_STATUS",
                         "connectionStatus": {
                             "status": "FAILED",
-                            "message": format!("Connection check failed: {}", e)
+                            "message": redact::redact_str(&format!("Connection check failed: {e}"))
                         }
                     }))),
                 )
@@ -1617,7 +2208,7 @@ fn check_database_connection(req: &ConnectorRequest) -> axum::response::Response
                 "type": "CONNECTION_STATUS",
                 "connectionStatus": {
                     "status": "FAILED",
-                    "message": format!("Failed to connect: {}", e)
+                    "message": redact::redact_str(&format!("Failed to connect: {e}"))
                 }
             }))),
         )
@@ -1716,3 +2307,168 @@ fn get_database_streams(req: &ConnectorRequest) -> axum::response::Response {
             .into_response(),
     }
 }
+
+#[cfg(test)]
+mod server_tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Run `POST /sync` against a connector written to a temporary directory
+    async fn sync(connector_yaml: &str, request: Value) -> Value {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("test.yaml"), connector_yaml).unwrap();
+        let state = AppState {
+            config: ServerConfig {
+                connectors_dir: dir.path().to_path_buf(),
+                strict_secrets: false,
+            },
+            secrets: Arc::new(SecretResolver::new()),
+            request_secrets: Arc::new(SecretResolver::new()),
+        };
+
+        let response = sync_data(
+            State(Arc::new(state)),
+            Json(serde_json::from_value(request).unwrap()),
+        )
+        .await
+        .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sync_writes_streamed_messages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "id: 1\ndata: {\"id\": 1}\n\nid: 2\ndata: {\"id\": 2}\n\n",
+                "text/event-stream",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/export"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("{\"id\": 1}\n{broken\n{\"id\": 2,\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let connector = r#"
+name: test
+base_url: "{{ config.base_url }}"
+streams:
+  - name: events
+    request:
+      path: /events
+    decoder:
+      type: stream
+      format: sse
+  - name: export
+    request:
+      path: /export
+    decoder:
+      type: jsonl
+      on_error: quarantine
+"#;
+        let result = sync(
+            connector,
+            json!({"connector": "test", "config": {"base_url": server.uri()}, "max_rejected": 1}),
+        )
+        .await;
+        let result = &result["data"]["result"];
+
+        // Streamed records are returned with their committed event ID
+        let records = result["records"].as_array().unwrap();
+        let streamed: Vec<_> = records.iter().filter(|r| r["stream"] == "events").collect();
+        assert_eq!(streamed.len(), 2);
+        assert_eq!(result["state"]["streams"]["events"]["event_id"], json!("2"));
+
+        // Rows that spent the error budget are returned although the stream failed
+        assert_eq!(result["streams"][1]["status"], "FAILED");
+        let rejected = records
+            .iter()
+            .filter(|r| r["stream"] == "export_rejected")
+            .count();
+        assert_eq!(rejected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_parent_uses_own_auth_profile() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "child-token",
+                "expires_in": 3600
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs"))
+            .and(header("X-Admin-Key", "admin-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": "o1"}])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/o1/members"))
+            .and(header("Authorization", "Bearer child-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": "m1"}])))
+            .mount(&server)
+            .await;
+
+        let connector = r#"
+name: test
+base_url: "{{ config.base_url }}"
+auth:
+  type: oauth2_client_credentials
+  token_url: "{{ config.base_url }}/token"
+  client_id: id
+  client_secret: "{{ config.client_secret }}"
+auth_profiles:
+  admin:
+    type: api_key
+    key: X-Admin-Key
+    value: admin-key
+streams:
+  - name: orgs
+    auth: admin
+    request:
+      path: /orgs
+  - name: members
+    request:
+      path: "/orgs/{{ partition.org_id }}/members"
+    partition:
+      type: parent
+      stream: orgs
+      parent_field: id
+      partition_field: org_id
+"#;
+        let result = sync(
+            connector,
+            json!({
+                "connector": "test",
+                "config": {"base_url": server.uri(), "client_secret": "s3cret"},
+                "streams": ["members"]
+            }),
+        )
+        .await;
+        let result = &result["data"]["result"];
+        assert_eq!(result["streams"][0]["status"], "SUCCESS", "{result}");
+        assert_eq!(result["records"][0]["data"]["id"], "m1");
+
+        // Each stream sends only its own profile's credentials
+        let requests = server.received_requests().await.unwrap();
+        let parent = requests.iter().find(|r| r.url.path() == "/orgs").unwrap();
+        assert!(!parent.headers.contains_key("authorization"));
+        let child = requests
+            .iter()
+            .find(|r| r.url.path() == "/orgs/o1/members")
+            .unwrap();
+        assert!(!child.headers.contains_key("x-admin-key"));
+    }
+}
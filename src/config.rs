@@ -97,6 +97,38 @@ pub struct SpecConfig {
     pub properties: HashMap<String, PropertyConfig>,
 }
 
+impl SpecConfig {
+    /// Names of properties marked `secret`, using dot notation for nested
+    /// object properties (e.g., "credentials.client_secret")
+    pub fn secret_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        collect_secret_fields(&self.properties, "", &mut fields);
+        fields.sort();
+        fields
+    }
+}
+
+/// Recursively collect secret property paths
+fn collect_secret_fields(
+    properties: &HashMap<String, PropertyConfig>,
+    prefix: &str,
+    fields: &mut Vec<String>,
+) {
+    for (name, property) in properties {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        if property.secret {
+            fields.push(path.clone());
+        }
+        if let Some(nested) = &property.properties {
+            collect_secret_fields(nested, &path, fields);
+        }
+    }
+}
+
 /// Configuration property definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PropertyConfig {
@@ -957,4 +989,30 @@ pagination:
         assert_eq!(config.max_retries, 5);
         assert_eq!(config.retry_statuses, vec![429, 500, 502, 503, 504]);
     }
+
+    #[test]
+    fn test_spec_secret_fields() {
+        let yaml = r#"
+properties:
+  api_key:
+    type: string
+    secret: true
+  region:
+    type: string
+  credentials:
+    type: object
+    properties:
+      client_id:
+        type: string
+      client_secret:
+        type: string
+        secret: true
+"#;
+
+        let spec: SpecConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            spec.secret_fields(),
+            vec!["api_key", "credentials.client_secret"]
+        );
+    }
 }
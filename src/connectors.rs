@@ -497,6 +497,26 @@ pub fn list_builtin_info() -> Vec<ConnectorInfo> {
     ]
 }
 
+/// Get info for a built-in connector by name or alias
+pub fn get_builtin_info(name: &str) -> Option<ConnectorInfo> {
+    list_builtin_info()
+        .into_iter()
+        .find(|info| info.name == name || info.aliases.contains(&name))
+}
+
+/// Config fields marked secret for a built-in connector
+pub fn builtin_secret_fields(name: &str) -> Vec<&'static str> {
+    get_builtin_info(name)
+        .map(|info| {
+            info.config_schema
+                .iter()
+                .filter(|f| f.secret)
+                .map(|f| f.name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Database connector types
 pub static DATABASE_CONNECTORS: &[&str] = &["postgres", "postgresql", "mysql", "mariadb", "sqlite"];
 
@@ -536,4 +556,14 @@ mod tests {
         assert!(list.contains(&"openai"));
         assert!(list.contains(&"github"));
     }
+
+    #[test]
+    fn test_builtin_secret_fields() {
+        assert_eq!(builtin_secret_fields("stripe"), vec!["api_key"]);
+        assert_eq!(
+            builtin_secret_fields("postgresql"),
+            vec!["connection_string"]
+        );
+        assert!(builtin_secret_fields("unknown").is_empty());
+    }
 }
@@ -0,0 +1,341 @@
+//! Archive decoder
+//!
+//! Unwraps compressed or archived downloads (gzip, zstd, zip, tar) and
+//! decodes the inner files with another decoder. Export APIs often return
+//! gzipped JSONL or zipped CSV behind a download URL.
+
+use super::types::{DecodedPage, RecordDecoder, RejectedRecord};
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::io::{Cursor, Read};
+
+/// Default cap on the total unpacked size of an archive (1 GiB)
+const DEFAULT_MAX_UNPACKED_SIZE: u64 = 1 << 30;
+
+/// Container format of an archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveFormat {
+    /// Detect from the leading bytes (uncompressed bodies pass through)
+    #[default]
+    Auto,
+    /// Gzip stream
+    Gzip,
+    /// Zstandard stream
+    Zstd,
+    /// Zip archive
+    Zip,
+    /// Tar archive
+    Tar,
+}
+
+impl ArchiveFormat {
+    /// Parse a format name ("auto", "gzip", "zstd", "zip" or "tar")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "gzip" | "gz" => Some(Self::Gzip),
+            "zstd" | "zst" => Some(Self::Zstd),
+            "zip" => Some(Self::Zip),
+            "tar" => Some(Self::Tar),
+            _ => None,
+        }
+    }
+
+    /// Detect a format from magic bytes
+    fn detect(body: &[u8]) -> Option<Self> {
+        if body.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::Gzip)
+        } else if body.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::Zstd)
+        } else if body.starts_with(b"PK\x03\x04") || body.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else if body.get(257..262) == Some(b"ustar".as_slice()) {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// A file unpacked from an archive
+struct ArchiveEntry {
+    /// Path inside the archive (empty for gzip/zstd streams)
+    name: String,
+    /// File contents
+    data: Vec<u8>,
+}
+
+impl ArchiveEntry {
+    /// Record which file rejected records came from
+    fn label(&self, rejected: &mut [RejectedRecord]) {
+        if self.name.is_empty() {
+            return;
+        }
+        for record in rejected {
+            record.file = Some(self.name.clone());
+        }
+    }
+}
+
+/// Remaining unpacked bytes an archive may produce
+struct UnpackBudget {
+    /// Total unpacked size allowed
+    max: u64,
+    /// Bytes left
+    remaining: u64,
+}
+
+impl UnpackBudget {
+    fn new(max: u64) -> Self {
+        Self {
+            max,
+            remaining: max,
+        }
+    }
+
+    /// Read a stream to the end, failing once the archive exceeds its budget
+    fn read(&mut self, reader: impl Read, format: &str) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        reader
+            .take(self.remaining.saturating_add(1))
+            .read_to_end(&mut data)
+            .map_err(|e| archive_error(format, &e))?;
+        let size = data.len() as u64;
+        if size > self.remaining {
+            return Err(Error::Decode {
+                message: format!("{format} archive unpacks to more than {} bytes", self.max),
+            });
+        }
+        self.remaining -= size;
+        Ok(data)
+    }
+}
+
+/// Decoder for compressed and archived responses
+///
+/// Gzip and zstd streams are decompressed; a tar inside them (`.tar.gz`) is
+/// unpacked too. Zip and tar files matching the `files` glob are decoded in
+/// name order by the inner decoder and their records concatenated. Unpacking
+/// fails once the files exceed `max_size` bytes in total, so a small
+/// decompression bomb cannot exhaust memory.
+pub struct ArchiveDecoder {
+    /// Container format
+    format: ArchiveFormat,
+    /// Glob selecting inner files (zip/tar)
+    files: Option<String>,
+    /// Maximum total unpacked size in bytes
+    max_size: u64,
+    /// Decoder for the inner files
+    inner: Box<dyn RecordDecoder>,
+}
+
+impl ArchiveDecoder {
+    /// Create an archive decoder wrapping an inner decoder
+    pub fn new(format: ArchiveFormat, inner: Box<dyn RecordDecoder>) -> Self {
+        Self {
+            format,
+            files: None,
+            max_size: DEFAULT_MAX_UNPACKED_SIZE,
+            inner,
+        }
+    }
+
+    /// Set the maximum total unpacked size in bytes
+    #[must_use]
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Only decode inner files matching a glob (e.g., `*.csv`, `results/**`)
+    #[must_use]
+    pub fn with_files(mut self, pattern: impl Into<String>) -> Self {
+        self.files = Some(pattern.into());
+        self
+    }
+
+    /// Unpack the body into its files
+    fn unpack(&self, body: &[u8]) -> Result<Vec<ArchiveEntry>> {
+        let format = match self.format {
+            ArchiveFormat::Auto => ArchiveFormat::detect(body),
+            format => Some(format),
+        };
+        let mut budget = UnpackBudget::new(self.max_size);
+        let entries = match format {
+            None => vec![ArchiveEntry {
+                name: String::new(),
+                data: body.to_vec(),
+            }],
+            Some(ArchiveFormat::Gzip) => {
+                let data = budget.read(flate2::read::MultiGzDecoder::new(body), "gzip")?;
+                unpack_nested_tar(data)?
+            }
+            Some(ArchiveFormat::Zstd) => {
+                let decoder =
+                    zstd::stream::Decoder::new(body).map_err(|e| archive_error("zstd", &e))?;
+                let data = budget.read(decoder, "zstd")?;
+                unpack_nested_tar(data)?
+            }
+            Some(ArchiveFormat::Zip) => unpack_zip(body, &mut budget)?,
+            Some(ArchiveFormat::Tar | ArchiveFormat::Auto) => unpack_tar(body, &mut budget)?,
+        };
+
+        let mut entries: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| match &self.files {
+                Some(pattern) if !entry.name.is_empty() => glob_match(pattern, &entry.name),
+                _ => true,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+}
+
+impl std::fmt::Debug for ArchiveDecoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveDecoder")
+            .field("format", &self.format)
+            .field("files", &self.files)
+            .field("max_size", &self.max_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RecordDecoder for ArchiveDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        Ok(self.decode_page(body)?.records)
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        Ok(self.decode_page(body)?.response)
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let mut page = self.decode_bytes(body.as_bytes())?;
+        page.materialize()?;
+        Ok(page)
+    }
+
+    /// Decode every selected file; a single file keeps its own response view
+    fn decode_bytes(&self, body: &[u8]) -> Result<DecodedPage> {
+        let entries = self.unpack(body)?;
+        if let [entry] = entries.as_slice() {
+            let mut page = self.inner.decode_bytes(&entry.data)?;
+            entry.label(&mut page.rejected);
+            return Ok(page);
+        }
+
+        let mut records = Vec::new();
+        let mut batches = Some(Vec::new());
+        let mut rejected = Vec::new();
+        for entry in &entries {
+            let mut page = self.inner.decode_bytes(&entry.data)?;
+            entry.label(&mut page.rejected);
+            records.extend(page.records);
+            rejected.extend(page.rejected);
+            batches = batches.zip(page.batches).map(|(mut all, page)| {
+                all.extend(page);
+                all
+            });
+        }
+        Ok(DecodedPage {
+            response: Value::Array(records.clone()),
+            records,
+            batches,
+            rejected,
+        })
+    }
+}
+
+/// Unpack a decompressed stream, which may itself be a tar archive
+///
+/// The stream was already read within the budget, and its files are no
+/// larger than the stream.
+fn unpack_nested_tar(data: Vec<u8>) -> Result<Vec<ArchiveEntry>> {
+    if ArchiveFormat::detect(&data) == Some(ArchiveFormat::Tar) {
+        return unpack_tar(&data, &mut UnpackBudget::new(data.len() as u64));
+    }
+    Ok(vec![ArchiveEntry {
+        name: String::new(),
+        data,
+    }])
+}
+
+/// Unpack the regular files of a zip archive
+fn unpack_zip(body: &[u8], budget: &mut UnpackBudget) -> Result<Vec<ArchiveEntry>> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(body)).map_err(|e| archive_error("zip", &e))?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let file = archive
+            .by_index(index)
+            .map_err(|e| archive_error("zip", &e))?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let data = budget.read(file, "zip")?;
+        entries.push(ArchiveEntry { name, data });
+    }
+    Ok(entries)
+}
+
+/// Unpack the regular files of a tar archive
+fn unpack_tar(body: &[u8], budget: &mut UnpackBudget) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(body);
+    let mut entries = Vec::new();
+    for entry in archive.entries().map_err(|e| archive_error("tar", &e))? {
+        let entry = entry.map_err(|e| archive_error("tar", &e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| archive_error("tar", &e))?
+            .to_string_lossy()
+            .into_owned();
+        let data = budget.read(entry, "tar")?;
+        entries.push(ArchiveEntry { name, data });
+    }
+    Ok(entries)
+}
+
+/// Build a decode error for an archive format
+fn archive_error(format: &str, e: &dyn std::fmt::Display) -> Error {
+    Error::Decode {
+        message: format!("Failed to read {format} archive: {e}"),
+    }
+}
+
+/// Match a path against a glob
+///
+/// `*` matches within a path segment, `**` across segments and `?` one
+/// character. Patterns without `/` match the file name in any directory.
+fn glob_match(pattern: &str, path: &str) -> bool {
+    let path = if pattern.contains('/') {
+        path
+    } else {
+        path.rsplit('/').next().unwrap_or(path)
+    };
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    glob_match_chars(&pattern, &path)
+}
+
+fn glob_match_chars(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            let rest = rest.strip_prefix(&['/']).unwrap_or(rest);
+            (0..=path.len()).any(|i| glob_match_chars(rest, &path[i..]))
+        }
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob_match_chars(rest, &path[i..])),
+        ['?', rest @ ..] => {
+            matches!(path, [c, ..] if *c != '/') && glob_match_chars(rest, &path[1..])
+        }
+        [c, rest @ ..] => path.first() == Some(c) && glob_match_chars(rest, &path[1..]),
+    }
+}
@@ -0,0 +1,693 @@
+//! Columnar decoders
+//!
+//! Decodes Parquet and Avro files into Arrow record batches that keep the
+//! file's own schema. The engine emits these batches directly instead of
+//! inferring a schema from JSON records; Avro values are written straight
+//! into Arrow arrays.
+//!
+//! # Avro to Arrow mapping
+//!
+//! - `int`/`long`/`float`/`double`/`boolean`/`string` map to the matching
+//!   Arrow types; `enum` and `uuid` become strings
+//! - `bytes` and `fixed` become base64 strings
+//! - `decimal` becomes `Decimal128`, `date` `Date32`, `time-*` `Time32`/`Time64`
+//!   and `timestamp-*` UTC timestamps (`local-timestamp-*` without a zone)
+//! - Records become structs, arrays lists and maps string-keyed maps
+//! - `["null", T]` unions become nullable `T`; other unions become strings
+
+use super::types::{DecodedPage, RecordDecoder};
+use crate::error::{Error, Result};
+use apache_avro::schema::Schema as AvroSchema;
+use apache_avro::types::Value as AvroValue;
+use arrow::array::{
+    ArrayRef, AsArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array,
+    Int32Array, Int64Array, ListArray, MapArray, NullArray, StringArray, StructArray,
+    Time32MillisecondArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt32Array,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{
+    DataType, Decimal128Type, DecimalType, Field, FieldRef, Fields, Schema, TimeUnit,
+};
+use arrow::record_batch::RecordBatch;
+use base64::Engine;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Rows per Arrow batch decoded from an Avro file
+const AVRO_BATCH_SIZE: usize = 8192;
+
+// ============================================================================
+// Parquet Decoder
+// ============================================================================
+
+/// Parquet file decoder
+#[derive(Debug, Clone, Default)]
+pub struct ParquetDecoder;
+
+impl ParquetDecoder {
+    /// Create a new Parquet decoder
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read every row group of a Parquet file
+    fn read_batches(body: &[u8]) -> Result<Vec<RecordBatch>> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::copy_from_slice(body))
+            .and_then(ParquetRecordBatchReaderBuilder::build)
+            .map_err(|e| parquet_error(&e))?;
+        reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| parquet_error(&e))
+    }
+}
+
+impl RecordDecoder for ParquetDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        Ok(self.decode_page(body)?.records)
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        Ok(self.decode_page(body)?.response)
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let mut page = self.decode_bytes(body.as_bytes())?;
+        page.materialize()?;
+        Ok(page)
+    }
+
+    fn decode_bytes(&self, body: &[u8]) -> Result<DecodedPage> {
+        Ok(DecodedPage::from_batches(Self::read_batches(body)?))
+    }
+}
+
+/// Build a decode error for a Parquet file
+fn parquet_error(e: &dyn std::fmt::Display) -> Error {
+    Error::Decode {
+        message: format!("Failed to read Parquet: {e}"),
+    }
+}
+
+// ============================================================================
+// Avro Decoder
+// ============================================================================
+
+/// Avro object container file decoder
+///
+/// The writer schema embedded in the file sets the Arrow schema, so every
+/// page of a stream has the same columns even when values are missing.
+#[derive(Debug, Clone, Default)]
+pub struct AvroDecoder;
+
+impl AvroDecoder {
+    /// Create a new Avro decoder
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read every record of an Avro file
+    fn read_batches(body: &[u8]) -> Result<Vec<RecordBatch>> {
+        let reader = apache_avro::Reader::new(body).map_err(|e| avro_error(&e))?;
+        let schema = Arc::new(avro_to_arrow_schema(reader.writer_schema())?);
+
+        let mut batches = Vec::new();
+        let mut rows = Vec::with_capacity(AVRO_BATCH_SIZE);
+        for value in reader {
+            rows.push(value.map_err(|e| avro_error(&e))?);
+            if rows.len() == AVRO_BATCH_SIZE {
+                batches.push(avro_batch(std::mem::take(&mut rows), &schema)?);
+            }
+        }
+        if !rows.is_empty() {
+            batches.push(avro_batch(rows, &schema)?);
+        }
+        Ok(batches)
+    }
+}
+
+impl RecordDecoder for AvroDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        Ok(self.decode_page(body)?.records)
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        Ok(self.decode_page(body)?.response)
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let mut page = self.decode_bytes(body.as_bytes())?;
+        page.materialize()?;
+        Ok(page)
+    }
+
+    fn decode_bytes(&self, body: &[u8]) -> Result<DecodedPage> {
+        Ok(DecodedPage::from_batches(Self::read_batches(body)?))
+    }
+}
+
+/// Build a decode error for an Avro file
+fn avro_error(e: &dyn std::fmt::Display) -> Error {
+    Error::Decode {
+        message: format!("Failed to read Avro: {e}"),
+    }
+}
+
+/// Convert an Avro file's record schema to an Arrow schema
+fn avro_to_arrow_schema(schema: &AvroSchema) -> Result<Schema> {
+    let mut named = HashMap::new();
+    match avro_to_arrow_type(schema, &mut named)? {
+        (DataType::Struct(fields), _) => Ok(Schema::new(fields)),
+        _ => Err(Error::Decode {
+            message: "Failed to read Avro: file schema must be a record".to_string(),
+        }),
+    }
+}
+
+/// Convert an Avro schema to an Arrow type and nullability
+///
+/// `named` holds the types of named schemas seen so far, for references.
+#[allow(clippy::too_many_lines)]
+fn avro_to_arrow_type(
+    schema: &AvroSchema,
+    named: &mut HashMap<String, DataType>,
+) -> Result<(DataType, bool)> {
+    let data_type = match schema {
+        AvroSchema::Null => return Ok((DataType::Null, true)),
+        AvroSchema::Boolean => DataType::Boolean,
+        AvroSchema::Int => DataType::Int32,
+        AvroSchema::Long => DataType::Int64,
+        AvroSchema::Float => DataType::Float32,
+        AvroSchema::Double => DataType::Float64,
+        AvroSchema::Bytes | AvroSchema::String | AvroSchema::Uuid | AvroSchema::BigDecimal => {
+            DataType::Utf8
+        }
+        AvroSchema::Enum(enum_schema) => {
+            named.insert(enum_schema.name.fullname(None), DataType::Utf8);
+            DataType::Utf8
+        }
+        AvroSchema::Fixed(fixed) => {
+            named.insert(fixed.name.fullname(None), DataType::Utf8);
+            DataType::Utf8
+        }
+        AvroSchema::Decimal(decimal) => {
+            let precision = u8::try_from(decimal.precision)
+                .ok()
+                .filter(|p| *p <= 38)
+                .ok_or_else(|| Error::Decode {
+                    message: format!(
+                        "Failed to read Avro: decimal precision {} exceeds 38",
+                        decimal.precision
+                    ),
+                })?;
+            let scale = i8::try_from(decimal.scale).map_err(|_| Error::Decode {
+                message: format!(
+                    "Failed to read Avro: invalid decimal scale {}",
+                    decimal.scale
+                ),
+            })?;
+            DataType::Decimal128(precision, scale)
+        }
+        AvroSchema::Date => DataType::Date32,
+        AvroSchema::TimeMillis => DataType::Time32(TimeUnit::Millisecond),
+        AvroSchema::TimeMicros => DataType::Time64(TimeUnit::Microsecond),
+        AvroSchema::TimestampMillis => {
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+        }
+        AvroSchema::TimestampMicros => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        }
+        AvroSchema::TimestampNanos => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        AvroSchema::LocalTimestampMillis => DataType::Timestamp(TimeUnit::Millisecond, None),
+        AvroSchema::LocalTimestampMicros => DataType::Timestamp(TimeUnit::Microsecond, None),
+        AvroSchema::LocalTimestampNanos => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        AvroSchema::Duration => DataType::Struct(Fields::from(vec![
+            Field::new("months", DataType::UInt32, false),
+            Field::new("days", DataType::UInt32, false),
+            Field::new("millis", DataType::UInt32, false),
+        ])),
+        AvroSchema::Array(array) => {
+            let (item, nullable) = avro_to_arrow_type(&array.items, named)?;
+            DataType::List(Arc::new(Field::new("item", item, nullable)))
+        }
+        AvroSchema::Map(map) => {
+            let (value, nullable) = avro_to_arrow_type(&map.types, named)?;
+            let entries = Fields::from(vec![
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", value, nullable),
+            ]);
+            DataType::Map(
+                Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+                false,
+            )
+        }
+        AvroSchema::Record(record) => {
+            let mut fields = Vec::with_capacity(record.fields.len());
+            for field in &record.fields {
+                let (data_type, nullable) = avro_to_arrow_type(&field.schema, named)?;
+                fields.push(Field::new(&field.name, data_type, nullable));
+            }
+            let data_type = DataType::Struct(Fields::from(fields));
+            named.insert(record.name.fullname(None), data_type.clone());
+            data_type
+        }
+        AvroSchema::Union(union) => {
+            let variants: Vec<_> = union
+                .variants()
+                .iter()
+                .filter(|v| !matches!(v, AvroSchema::Null))
+                .collect();
+            return match variants.as_slice() {
+                [] => Ok((DataType::Null, true)),
+                [variant] => {
+                    let (data_type, _) = avro_to_arrow_type(variant, named)?;
+                    Ok((data_type, union.is_nullable()))
+                }
+                _ => Ok((DataType::Utf8, union.is_nullable())),
+            };
+        }
+        AvroSchema::Ref { name } => {
+            named
+                .get(&name.fullname(None))
+                .cloned()
+                .ok_or_else(|| Error::Decode {
+                    message: format!(
+                        "Failed to read Avro: recursive type '{}' is not supported",
+                        name.fullname(None)
+                    ),
+                })?
+        }
+    };
+    Ok((data_type, false))
+}
+
+/// Build a record batch from Avro records
+fn avro_batch(rows: Vec<AvroValue>, schema: &Arc<Schema>) -> Result<RecordBatch> {
+    let array = avro_to_array(rows, &DataType::Struct(schema.fields().clone()))?;
+    RecordBatch::try_new(schema.clone(), array.as_struct().columns().to_vec())
+        .map_err(|e| avro_error(&e))
+}
+
+/// Build an Arrow array of `data_type` from Avro values
+///
+/// Values that do not match the type (e.g., the null branch of a union)
+/// become nulls.
+#[allow(clippy::too_many_lines)]
+fn avro_to_array(values: Vec<AvroValue>, data_type: &DataType) -> Result<ArrayRef> {
+    let values = values.into_iter().map(|value| match value {
+        AvroValue::Union(_, value) => *value,
+        value => value,
+    });
+    let array: ArrayRef = match data_type {
+        DataType::Null => Arc::new(NullArray::new(values.len())),
+        DataType::Boolean => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Boolean(b) => Some(b),
+                    _ => None,
+                })
+                .collect::<BooleanArray>(),
+        ),
+        DataType::Int32 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Int(n) => Some(n),
+                    _ => None,
+                })
+                .collect::<Int32Array>(),
+        ),
+        DataType::Int64 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Int(n) => Some(i64::from(n)),
+                    AvroValue::Long(n) => Some(n),
+                    _ => None,
+                })
+                .collect::<Int64Array>(),
+        ),
+        DataType::UInt32 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Long(n) => u32::try_from(n).ok(),
+                    _ => None,
+                })
+                .collect::<UInt32Array>(),
+        ),
+        DataType::Float32 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Float(f) => Some(f),
+                    _ => None,
+                })
+                .collect::<Float32Array>(),
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Float(f) => Some(f64::from(f)),
+                    AvroValue::Double(f) => Some(f),
+                    _ => None,
+                })
+                .collect::<Float64Array>(),
+        ),
+        DataType::Utf8 => Arc::new(values.map(avro_to_string).collect::<StringArray>()),
+        DataType::Decimal128(precision, scale) => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Decimal(decimal) => Some(unscaled_decimal(
+                        &Vec::<u8>::try_from(&decimal).unwrap_or_default(),
+                    )),
+                    _ => None,
+                })
+                .collect::<Decimal128Array>()
+                .with_precision_and_scale(*precision, *scale)
+                .map_err(|e| avro_error(&e))?,
+        ),
+        DataType::Date32 => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::Date(n) => Some(n),
+                    _ => None,
+                })
+                .collect::<Date32Array>(),
+        ),
+        DataType::Time32(TimeUnit::Millisecond) => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::TimeMillis(n) => Some(n),
+                    _ => None,
+                })
+                .collect::<Time32MillisecondArray>(),
+        ),
+        DataType::Time64(TimeUnit::Microsecond) => Arc::new(
+            values
+                .map(|v| match v {
+                    AvroValue::TimeMicros(n) => Some(n),
+                    _ => None,
+                })
+                .collect::<Time64MicrosecondArray>(),
+        ),
+        DataType::Timestamp(unit, tz) => {
+            let values = values.map(|v| match v {
+                AvroValue::TimestampMillis(n)
+                | AvroValue::TimestampMicros(n)
+                | AvroValue::TimestampNanos(n)
+                | AvroValue::LocalTimestampMillis(n)
+                | AvroValue::LocalTimestampMicros(n)
+                | AvroValue::LocalTimestampNanos(n) => Some(n),
+                _ => None,
+            });
+            match unit {
+                TimeUnit::Millisecond => Arc::new(
+                    values
+                        .collect::<TimestampMillisecondArray>()
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Microsecond => Arc::new(
+                    values
+                        .collect::<TimestampMicrosecondArray>()
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Nanosecond => Arc::new(
+                    values
+                        .collect::<TimestampNanosecondArray>()
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Second => Arc::new(
+                    values
+                        .collect::<TimestampSecondArray>()
+                        .with_timezone_opt(tz.clone()),
+                ),
+            }
+        }
+        DataType::Struct(fields) => avro_to_struct(values.collect(), fields)?,
+        DataType::List(field) => avro_to_list(values.collect(), field)?,
+        DataType::Map(field, ordered) => avro_to_map(values.collect(), field, *ordered)?,
+        other => return Err(avro_error(&format!("unsupported Arrow type {other}"))),
+    };
+    Ok(array)
+}
+
+/// Build a list array from Avro arrays
+fn avro_to_list(values: Vec<AvroValue>, field: &FieldRef) -> Result<ArrayRef> {
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut valid = Vec::with_capacity(values.len());
+    let mut items = Vec::new();
+    for value in values {
+        let array = match value {
+            AvroValue::Array(array) => Some(array),
+            _ => None,
+        };
+        valid.push(array.is_some());
+        let array = array.unwrap_or_default();
+        lengths.push(array.len());
+        items.extend(array);
+    }
+    let items = avro_to_array(items, field.data_type())?;
+    Ok(Arc::new(
+        ListArray::try_new(
+            field.clone(),
+            OffsetBuffer::from_lengths(lengths),
+            items,
+            null_buffer(valid),
+        )
+        .map_err(|e| avro_error(&e))?,
+    ))
+}
+
+/// Build a map array from Avro maps (entries sorted by key)
+fn avro_to_map(values: Vec<AvroValue>, field: &FieldRef, ordered: bool) -> Result<ArrayRef> {
+    let DataType::Struct(entry_fields) = field.data_type() else {
+        return Err(avro_error(&"map entries must be a struct"));
+    };
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut valid = Vec::with_capacity(values.len());
+    let mut keys = Vec::new();
+    let mut items = Vec::new();
+    for value in values {
+        let entries = match value {
+            AvroValue::Map(entries) => Some(entries),
+            _ => None,
+        };
+        valid.push(entries.is_some());
+        let mut entries: Vec<_> = entries.unwrap_or_default().into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        lengths.push(entries.len());
+        for (key, item) in entries {
+            keys.push(key);
+            items.push(item);
+        }
+    }
+    let items = avro_to_array(items, entry_fields[1].data_type())?;
+    let entries = StructArray::try_new(
+        entry_fields.clone(),
+        vec![Arc::new(StringArray::from(keys)), items],
+        None,
+    )
+    .map_err(|e| avro_error(&e))?;
+    Ok(Arc::new(
+        MapArray::try_new(
+            field.clone(),
+            OffsetBuffer::from_lengths(lengths),
+            entries,
+            null_buffer(valid),
+            ordered,
+        )
+        .map_err(|e| avro_error(&e))?,
+    ))
+}
+
+/// Build a struct array from Avro records (durations are months/days/millis structs)
+fn avro_to_struct(values: Vec<AvroValue>, fields: &Fields) -> Result<ArrayRef> {
+    let mut columns: Vec<Vec<AvroValue>> = fields
+        .iter()
+        .map(|_| Vec::with_capacity(values.len()))
+        .collect();
+    let mut valid = Vec::with_capacity(values.len());
+    for value in values {
+        let record = match value {
+            AvroValue::Record(record) => Some(record),
+            AvroValue::Duration(duration) => Some(vec![
+                (
+                    "months".to_string(),
+                    AvroValue::Long(u32::from(duration.months()).into()),
+                ),
+                (
+                    "days".to_string(),
+                    AvroValue::Long(u32::from(duration.days()).into()),
+                ),
+                (
+                    "millis".to_string(),
+                    AvroValue::Long(u32::from(duration.millis()).into()),
+                ),
+            ]),
+            _ => None,
+        };
+        valid.push(record.is_some());
+        let mut record = record.unwrap_or_default();
+        for (index, (column, field)) in columns.iter_mut().zip(fields.iter()).enumerate() {
+            // Records follow the writer schema, so fields are usually in order
+            let position = match record.get(index) {
+                Some((name, _)) if name == field.name() => Some(index),
+                _ => record.iter().position(|(name, _)| name == field.name()),
+            };
+            column.push(position.map_or(AvroValue::Null, |i| {
+                std::mem::replace(&mut record[i].1, AvroValue::Null)
+            }));
+        }
+    }
+
+    let nulls = null_buffer(valid);
+    if fields.is_empty() {
+        return Ok(Arc::new(StructArray::new_empty_fields(
+            columns.len(),
+            nulls,
+        )));
+    }
+    let arrays = columns
+        .into_iter()
+        .zip(fields.iter())
+        .map(|(column, field)| avro_to_array(column, field.data_type()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Arc::new(
+        StructArray::try_new(fields.clone(), arrays, nulls).map_err(|e| avro_error(&e))?,
+    ))
+}
+
+/// Validity of a nullable array (`None` when every value is present)
+fn null_buffer(valid: Vec<bool>) -> Option<NullBuffer> {
+    if valid.iter().all(|v| *v) {
+        None
+    } else {
+        Some(NullBuffer::from(valid))
+    }
+}
+
+/// Convert an Avro value to a string column value
+///
+/// Mixed unions map to strings: scalars as their text, nested values as JSON.
+fn avro_to_string(value: AvroValue) -> Option<String> {
+    match value {
+        AvroValue::Null => None,
+        AvroValue::String(s) | AvroValue::Enum(_, s) => Some(s),
+        AvroValue::Uuid(uuid) => Some(uuid.to_string()),
+        AvroValue::BigDecimal(decimal) => Some(decimal.to_string()),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => {
+            Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+        value => match avro_to_json(value, &DataType::Utf8) {
+            Value::Null => None,
+            Value::String(s) => Some(s),
+            json => Some(json.to_string()),
+        },
+    }
+}
+
+/// Convert an Avro value to JSON shaped for its Arrow type
+fn avro_to_json(value: AvroValue, data_type: &DataType) -> Value {
+    let json = match value {
+        AvroValue::Union(_, value) => return avro_to_json(*value, data_type),
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(b) => Value::Bool(b),
+        AvroValue::Int(n) | AvroValue::Date(n) | AvroValue::TimeMillis(n) => n.into(),
+        AvroValue::Long(n)
+        | AvroValue::TimeMicros(n)
+        | AvroValue::TimestampMillis(n)
+        | AvroValue::TimestampMicros(n)
+        | AvroValue::TimestampNanos(n)
+        | AvroValue::LocalTimestampMillis(n)
+        | AvroValue::LocalTimestampMicros(n)
+        | AvroValue::LocalTimestampNanos(n) => n.into(),
+        AvroValue::Float(f) => float_to_json(f64::from(f)),
+        AvroValue::Double(f) => float_to_json(f),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => {
+            Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+        AvroValue::String(s) | AvroValue::Enum(_, s) => Value::String(s),
+        AvroValue::Uuid(uuid) => Value::String(uuid.to_string()),
+        AvroValue::BigDecimal(decimal) => Value::String(decimal.to_string()),
+        AvroValue::Decimal(decimal) => {
+            let unscaled = unscaled_decimal(&Vec::<u8>::try_from(&decimal).unwrap_or_default());
+            match data_type {
+                DataType::Decimal128(precision, scale) => {
+                    Value::String(Decimal128Type::format_decimal(unscaled, *precision, *scale))
+                }
+                _ => Value::String(unscaled.to_string()),
+            }
+        }
+        AvroValue::Duration(duration) => serde_json::json!({
+            "months": u32::from(duration.months()),
+            "days": u32::from(duration.days()),
+            "millis": u32::from(duration.millis()),
+        }),
+        AvroValue::Array(items) => {
+            let item_type = match data_type {
+                DataType::List(field) => field.data_type(),
+                _ => &DataType::Utf8,
+            };
+            Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| avro_to_json(item, item_type))
+                    .collect(),
+            )
+        }
+        AvroValue::Map(entries) => {
+            let value_type = match data_type {
+                DataType::Map(field, _) => match field.data_type() {
+                    DataType::Struct(fields) if fields.len() == 2 => fields[1].data_type(),
+                    _ => &DataType::Utf8,
+                },
+                _ => &DataType::Utf8,
+            };
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, avro_to_json(value, value_type)))
+                    .collect(),
+            )
+        }
+        AvroValue::Record(fields) => {
+            let mut object = Map::with_capacity(fields.len());
+            for (name, value) in fields {
+                let field_type = match data_type {
+                    DataType::Struct(children) => children
+                        .find(&name)
+                        .map_or(&DataType::Utf8, |(_, f)| f.data_type()),
+                    _ => &DataType::Utf8,
+                };
+                let value = avro_to_json(value, field_type);
+                object.insert(name, value);
+            }
+            Value::Object(object)
+        }
+    };
+
+    // Mixed unions map to strings; nested values are kept as JSON text
+    match (data_type, json) {
+        (DataType::Utf8, json @ (Value::Array(_) | Value::Object(_))) => {
+            Value::String(json.to_string())
+        }
+        (_, json) => json,
+    }
+}
+
+/// Convert a float to JSON (NaN and infinities become null)
+fn float_to_json(f: f64) -> Value {
+    serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number)
+}
+
+/// Read a big-endian two's-complement unscaled decimal
+fn unscaled_decimal(bytes: &[u8]) -> i128 {
+    let bytes = &bytes[bytes.len().saturating_sub(16)..];
+    let fill = if bytes.first().is_some_and(|b| b & 0x80 != 0) {
+        0xff
+    } else {
+        0
+    };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    i128::from_be_bytes(buf)
+}
@@ -2,9 +2,18 @@
 //!
 //! Each decoder handles a specific response format.
 
-use super::types::RecordDecoder;
+use super::types::{
+    CsvCoercion, DecodeErrorPolicy, DecodedPage, RecordDecoder, RejectedRecord, StreamParser,
+};
 use crate::error::{Error, Result};
+use crate::jsonpath::{self, JsonPath};
+use crate::pagination::set_body_field;
+use crate::schema::{JsonSchema, JsonType};
+use crate::transform::remove_field;
 use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::Read;
+use std::ops::Range;
 
 // ============================================================================
 // JSON Decoder
@@ -13,8 +22,12 @@ use serde_json::{Map, Value};
 /// JSON decoder with optional record path extraction
 #[derive(Debug, Clone, Default)]
 pub struct JsonDecoder {
-    /// JSONPath to extract records
-    record_path: Option<String>,
+    /// JSONPaths to extract records from, concatenated in order
+    record_paths: Vec<String>,
+    /// Turn object entries into records, with the key under this field
+    map_key_field: Option<String>,
+    /// Nested array to unnest into one record per element
+    unnest: Option<String>,
 }
 
 impl JsonDecoder {
@@ -26,35 +39,79 @@ impl JsonDecoder {
     /// Create a JSON decoder with a record path
     pub fn with_path(path: impl Into<String>) -> Self {
         Self {
-            record_path: Some(path.into()),
+            record_paths: vec![path.into()],
+            ..Self::default()
         }
     }
 
-    /// Extract records from a JSON value using a path
-    fn extract_records(&self, value: &Value) -> Result<Vec<Value>> {
-        match &self.record_path {
-            Some(path) => {
-                // Use simple path first - handles negative indices and basic paths
-                // Only use jsonpath-rust for complex patterns like wildcards
-                if path.contains('*') && !path.contains("[-") {
-                    extract_with_jsonpath(value, path)
-                } else {
-                    // Simple dot-notation path (also handles array indexing)
-                    match extract_simple_path(value, path) {
-                        Some(Value::Array(arr)) => Ok(arr),
-                        Some(v) => Ok(vec![v]),
-                        None => Ok(vec![]),
-                    }
-                }
+    /// Create a JSON decoder gathering records from several paths
+    /// (e.g., `data[*]` and `included[*]` in JSON:API responses)
+    pub fn with_paths<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            record_paths: paths.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Explode map-shaped responses into records
+    ///
+    /// Each entry of an object at the record path becomes a record, with the
+    /// entry's key injected under `key_field`:
+    /// `{"2024-01-01": {"cost": 1}}` becomes `{"date": "2024-01-01", "cost": 1}`
+    /// with `key_field` "date". Non-object entries are kept under `value`.
+    #[must_use]
+    pub fn with_map_key(mut self, key_field: impl Into<String>) -> Self {
+        self.map_key_field = Some(key_field.into());
+        self
+    }
+
+    /// Unnest a nested array into one record per element
+    ///
+    /// Parent fields are carried into every element's record; object elements
+    /// are merged in (their fields win), other elements replace the array.
+    /// Records with an empty array are dropped; records without it are kept.
+    #[must_use]
+    pub fn with_unnest(mut self, path: impl Into<String>) -> Self {
+        self.unnest = Some(path.into());
+        self
+    }
+
+    /// Extract records from a parsed JSON response
+    ///
+    /// A path pointing at an array yields its elements; wildcard, slice and
+    /// filter paths yield each match. Without a path the whole response is
+    /// the records. Map explosion and unnesting apply afterwards.
+    pub fn extract_records(&self, value: &Value) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        if self.record_paths.is_empty() {
+            match &self.map_key_field {
+                Some(key_field) => explode_map(value.clone(), key_field, &mut records),
+                None => records.extend(JsonPath::parse("$")?.records(value)),
             }
-            None => {
-                // No path - treat entire response as records
-                match value {
-                    Value::Array(arr) => Ok(arr.clone()),
-                    _ => Ok(vec![value.clone()]),
+        }
+        for path in &self.record_paths {
+            let path = JsonPath::parse(path)?;
+            match &self.map_key_field {
+                Some(key_field) => {
+                    for selected in path.find(value) {
+                        explode_map(selected, key_field, &mut records);
+                    }
                 }
+                None => records.extend(path.records(value)),
             }
         }
+
+        match &self.unnest {
+            Some(path) => Ok(records
+                .into_iter()
+                .flat_map(|record| unnest_record(record, path))
+                .collect()),
+            None => Ok(records),
+        }
     }
 }
 
@@ -71,6 +128,124 @@ impl RecordDecoder for JsonDecoder {
             message: format!("Failed to parse JSON: {e}"),
         })
     }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let response = self.decode_raw(body)?;
+        Ok(DecodedPage {
+            records: self.extract_records(&response)?,
+            response,
+            batches: None,
+            rejected: Vec::new(),
+        })
+    }
+}
+
+/// Push the entries of a map-shaped value as records
+///
+/// Arrays are already records and are pushed element by element.
+fn explode_map(selected: Value, key_field: &str, records: &mut Vec<Value>) {
+    match selected {
+        Value::Object(map) => {
+            for (key, entry) in map {
+                let mut record = match entry {
+                    Value::Object(fields) => fields,
+                    other => Map::from_iter([("value".to_string(), other)]),
+                };
+                record.insert(key_field.to_string(), Value::String(key));
+                records.push(Value::Object(record));
+            }
+        }
+        Value::Array(arr) => records.extend(arr),
+        other => records.push(other),
+    }
+}
+
+/// Unnest the array at `path` into one record per element
+fn unnest_record(mut record: Value, path: &str) -> Vec<Value> {
+    let elements = match remove_field(&mut record, path) {
+        Some(Value::Array(elements)) => elements,
+        Some(other) => {
+            set_body_field(&mut record, path, other);
+            return vec![record];
+        }
+        None => return vec![record],
+    };
+
+    elements
+        .into_iter()
+        .map(|element| {
+            let mut unnested = record.clone();
+            match (element, &mut unnested) {
+                (Value::Object(fields), Value::Object(parent)) => parent.extend(fields),
+                (element, _) => set_body_field(&mut unnested, path, element),
+            }
+            unnested
+        })
+        .collect()
+}
+
+// ============================================================================
+// GraphQL Decoder
+// ============================================================================
+
+/// GraphQL connection decoder
+///
+/// Extracts records from a Relay connection at the configured path, taking
+/// `edges[].node` and falling back to the `nodes` shorthand.
+#[derive(Debug, Clone)]
+pub struct GraphqlDecoder {
+    /// JSONPath to the connection object (e.g., "data.orders")
+    connection_path: String,
+}
+
+impl GraphqlDecoder {
+    /// Create a GraphQL decoder for a connection path
+    pub fn new(connection_path: impl Into<String>) -> Self {
+        Self {
+            connection_path: connection_path.into(),
+        }
+    }
+
+    /// Extract the connection's nodes from a response
+    fn extract_nodes(&self, value: &Value) -> Vec<Value> {
+        let Some(connection) = jsonpath::select_first(value, &self.connection_path) else {
+            return vec![];
+        };
+
+        if let Some(edges) = connection.get("edges").and_then(Value::as_array) {
+            return edges
+                .iter()
+                .filter_map(|edge| edge.get("node").cloned())
+                .collect();
+        }
+        match connection.get("nodes") {
+            Some(Value::Array(nodes)) => nodes.clone(),
+            _ => vec![],
+        }
+    }
+}
+
+impl RecordDecoder for GraphqlDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        let value = self.decode_raw(body)?;
+        Ok(self.extract_nodes(&value))
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        serde_json::from_str(body).map_err(|e| Error::Decode {
+            message: format!("Failed to parse JSON: {e}"),
+        })
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let response = self.decode_raw(body)?;
+        Ok(DecodedPage {
+            records: self.extract_nodes(&response),
+            response,
+            batches: None,
+            rejected: Vec::new(),
+        })
+    }
 }
 
 // ============================================================================
@@ -79,33 +254,60 @@ impl RecordDecoder for JsonDecoder {
 
 /// JSON Lines decoder (one JSON object per line)
 #[derive(Debug, Clone, Default)]
-pub struct JsonlDecoder;
+pub struct JsonlDecoder {
+    /// What to do with lines that are not valid JSON
+    on_error: DecodeErrorPolicy,
+}
 
 impl JsonlDecoder {
     /// Create a new JSONL decoder
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
-}
 
-impl RecordDecoder for JsonlDecoder {
-    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+    /// Set what happens to lines that are not valid JSON
+    #[must_use]
+    pub fn with_error_policy(mut self, on_error: DecodeErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Parse every line, setting invalid ones aside when quarantining
+    fn parse(&self, body: &str) -> Result<(Vec<Value>, Vec<RejectedRecord>)> {
         let mut records = Vec::new();
+        let mut rejected = Vec::new();
 
-        for (line_num, line) in body.lines().enumerate() {
+        for (line_num, line) in (1u64..).zip(body.lines()) {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
 
-            let value: Value = serde_json::from_str(line).map_err(|e| Error::Decode {
-                message: format!("Failed to parse JSONL at line {}: {e}", line_num + 1),
-            })?;
-
-            records.push(value);
+            match serde_json::from_str(line) {
+                Ok(value) => records.push(value),
+                Err(e) if self.on_error == DecodeErrorPolicy::Quarantine => {
+                    rejected.push(RejectedRecord {
+                        raw: line.to_string(),
+                        error: e.to_string(),
+                        line: line_num,
+                        file: None,
+                    });
+                }
+                Err(e) => {
+                    return Err(Error::Decode {
+                        message: format!("Failed to parse JSONL at line {line_num}: {e}"),
+                    })
+                }
+            }
         }
 
-        Ok(records)
+        Ok((records, rejected))
+    }
+}
+
+impl RecordDecoder for JsonlDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        Ok(self.parse(body)?.0)
     }
 
     fn decode_raw(&self, body: &str) -> Result<Value> {
@@ -113,19 +315,44 @@ impl RecordDecoder for JsonlDecoder {
         let records = self.decode(body)?;
         Ok(Value::Array(records))
     }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let (records, rejected) = self.parse(body)?;
+        Ok(DecodedPage {
+            response: Value::Array(records.clone()),
+            records,
+            batches: None,
+            rejected,
+        })
+    }
 }
 
 // ============================================================================
 // CSV Decoder
 // ============================================================================
 
-/// CSV decoder with configurable delimiter and header handling
+/// RFC 4180 CSV decoder
+///
+/// Quoted fields may contain delimiters and line breaks; quotes inside them
+/// are doubled or preceded by an optional escape character. A UTF-8 BOM is
+/// skipped. Cells are kept as strings (empty cells are null) unless a
+/// [`CsvCoercion`] is set.
 #[derive(Debug, Clone)]
 pub struct CsvDecoder {
     /// Field delimiter
     delimiter: char,
     /// Whether the first row is a header
     has_header: bool,
+    /// Quote character
+    quote: char,
+    /// Escape character inside quoted fields
+    escape: Option<char>,
+    /// Cell type coercion
+    coercion: CsvCoercion,
+    /// Column types from the stream schema (for `CsvCoercion::Schema`)
+    column_types: HashMap<String, JsonType>,
+    /// What to do with unreadable or ragged rows
+    on_error: DecodeErrorPolicy,
 }
 
 impl Default for CsvDecoder {
@@ -133,6 +360,11 @@ impl Default for CsvDecoder {
         Self {
             delimiter: ',',
             has_header: true,
+            quote: '"',
+            escape: None,
+            coercion: CsvCoercion::None,
+            column_types: HashMap::new(),
+            on_error: DecodeErrorPolicy::Fail,
         }
     }
 }
@@ -148,402 +380,477 @@ impl CsvDecoder {
         Self {
             delimiter,
             has_header,
+            ..Self::default()
         }
     }
-}
-
-impl RecordDecoder for CsvDecoder {
-    fn decode(&self, body: &str) -> Result<Vec<Value>> {
-        let mut records = Vec::new();
-        let mut lines = body.lines().peekable();
 
-        // Get headers
-        let headers: Vec<String> = if self.has_header {
-            match lines.next() {
-                Some(header_line) => parse_csv_line(header_line, self.delimiter),
-                None => return Ok(records),
-            }
-        } else {
-            // Generate numeric column names
-            if let Some(first_line) = lines.peek() {
-                let field_count = parse_csv_line(first_line, self.delimiter).len();
-                (0..field_count).map(|i| format!("column_{i}")).collect()
-            } else {
-                return Ok(records);
-            }
-        };
-
-        // Parse data rows
-        for line in lines {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-
-            let fields = parse_csv_line(line, self.delimiter);
-            let mut obj = Map::new();
+    /// Set the quote character
+    #[must_use]
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
 
-            for (i, header) in headers.iter().enumerate() {
-                let value = fields.get(i).cloned().unwrap_or_default();
-                // Try to parse as number or boolean
-                let json_value = parse_csv_value(&value);
-                obj.insert(header.clone(), json_value);
-            }
+    /// Set the escape character used inside quoted fields (e.g., `\`)
+    #[must_use]
+    pub fn with_escape(mut self, escape: Option<char>) -> Self {
+        self.escape = escape;
+        self
+    }
 
-            records.push(Value::Object(obj));
-        }
+    /// Set cell type coercion
+    #[must_use]
+    pub fn with_coercion(mut self, coercion: CsvCoercion) -> Self {
+        self.coercion = coercion;
+        self
+    }
 
-        Ok(records)
+    /// Take column types from a stream schema's properties
+    #[must_use]
+    pub fn with_schema(mut self, schema: &JsonSchema) -> Self {
+        self.column_types = schema
+            .properties
+            .iter()
+            .filter_map(|(name, prop)| Some((name.clone(), prop.json_type.primary_type()?.clone())))
+            .collect();
+        self
     }
 
-    fn decode_raw(&self, body: &str) -> Result<Value> {
-        let records = self.decode(body)?;
-        Ok(Value::Array(records))
+    /// Set what happens to rows that cannot be read
+    ///
+    /// When quarantining, rows whose field count differs from the header are
+    /// rejected too, instead of being padded with nulls or cut short.
+    #[must_use]
+    pub fn with_error_policy(mut self, on_error: DecodeErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
     }
-}
 
-/// Parse a CSV line into fields
-fn parse_csv_line(line: &str, delimiter: char) -> Vec<String> {
-    let mut fields = Vec::new();
-    let mut current = String::new();
-    let mut in_quotes = false;
-    let mut chars = line.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '"' {
-            if in_quotes {
-                // Check for escaped quote
-                if chars.peek() == Some(&'"') {
-                    current.push('"');
-                    chars.next();
-                } else {
-                    in_quotes = false;
+    /// Parse a body, setting bad rows aside when quarantining
+    fn parse(&self, body: &str) -> Result<(Vec<Value>, Vec<RejectedRecord>)> {
+        let mut records = Vec::new();
+        let mut rejected = Vec::new();
+        let mut rows = self.records(body.as_bytes());
+        self.read_rows(&mut rows, body.as_bytes(), 0, &mut records, &mut rejected)?;
+        Ok((records, rejected))
+    }
+
+    /// Read every row of `body`, setting bad rows aside when quarantining
+    ///
+    /// `line_offset` is the number of lines read before `body`.
+    fn read_rows(
+        &self,
+        rows: &mut CsvRecords<'_, &[u8]>,
+        body: &[u8],
+        line_offset: u64,
+        records: &mut Vec<Value>,
+        rejected: &mut Vec<RejectedRecord>,
+    ) -> Result<()> {
+        while let Some(row) = rows.next_row() {
+            match row {
+                Ok(record) => records.push(record),
+                // A row the reader could not get past would be read again forever
+                Err(e) if self.on_error == DecodeErrorPolicy::Quarantine && !e.span.is_empty() => {
+                    let raw = body.get(e.span).unwrap_or_default();
+                    rejected.push(RejectedRecord {
+                        raw: String::from_utf8_lossy(raw).trim_end().to_string(),
+                        error: e.reason,
+                        line: e.line + line_offset,
+                        file: None,
+                    });
+                }
+                Err(mut e) => {
+                    e.line += line_offset;
+                    return Err(e.into_error());
                 }
-            } else {
-                in_quotes = true;
             }
-        } else if c == delimiter && !in_quotes {
-            fields.push(current.trim().to_string());
-            current = String::new();
-        } else {
-            current.push(c);
+        }
+        Ok(())
+    }
+
+    /// Parse records incrementally from a reader
+    ///
+    /// The sync engine feeds each page's body to a [`CsvParser`] as it
+    /// arrives, which reads its complete rows the same way.
+    pub fn records<R: Read>(&self, reader: R) -> CsvRecords<'_, R> {
+        self.records_after(reader, None)
+    }
+
+    /// Parse records from a reader, with column names already read
+    fn records_after<R: Read>(&self, reader: R, headers: Option<Vec<String>>) -> CsvRecords<'_, R> {
+        let reader = csv::ReaderBuilder::new()
+            .delimiter(ascii_byte(self.delimiter))
+            .quote(ascii_byte(self.quote))
+            .escape(self.escape.map(ascii_byte))
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader);
+        CsvRecords {
+            decoder: self,
+            reader,
+            headers,
+            row: csv::StringRecord::new(),
         }
     }
 
-    fields.push(current.trim().to_string());
-    fields
+    /// Convert a CSV cell to a JSON value
+    fn cell_value(&self, column: &str, cell: &str) -> Value {
+        if cell.is_empty() {
+            return Value::Null;
+        }
+        match self.coercion {
+            CsvCoercion::None => Value::String(cell.to_string()),
+            CsvCoercion::Schema => match self.column_types.get(column) {
+                Some(json_type) => cast_csv_value(cell, json_type),
+                None => Value::String(cell.to_string()),
+            },
+            CsvCoercion::Infer => parse_csv_value(cell),
+        }
+    }
 }
 
-/// Parse a CSV value into JSON value
-fn parse_csv_value(value: &str) -> Value {
-    // Try integer
-    if let Ok(n) = value.parse::<i64>() {
-        return Value::Number(n.into());
+impl RecordDecoder for CsvDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        Ok(self.parse(body)?.0)
     }
 
-    // Try float
-    if let Ok(n) = value.parse::<f64>() {
-        if let Some(num) = serde_json::Number::from_f64(n) {
-            return Value::Number(num);
-        }
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        let records = self.decode(body)?;
+        Ok(Value::Array(records))
     }
 
-    // Try boolean
-    match value.to_lowercase().as_str() {
-        "true" | "yes" | "1" => return Value::Bool(true),
-        "false" | "no" | "0" if value != "0" => return Value::Bool(false),
-        _ => {}
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let (records, rejected) = self.parse(body)?;
+        Ok(DecodedPage {
+            response: Value::Array(records.clone()),
+            records,
+            batches: None,
+            rejected,
+        })
     }
 
-    // Null/empty
-    if value.is_empty() || value.eq_ignore_ascii_case("null") || value.eq_ignore_ascii_case("none")
-    {
-        return Value::Null;
+    fn page_parser(&self) -> Option<Box<dyn StreamParser>> {
+        Some(Box::new(CsvParser::new(self.clone())))
     }
-
-    // String
-    Value::String(value.to_string())
 }
 
-// ============================================================================
-// XML Decoder
-// ============================================================================
-
-/// XML decoder with record element extraction
-#[derive(Debug, Clone, Default)]
-pub struct XmlDecoder {
-    /// Element name containing records
-    record_element: Option<String>,
+/// Incremental CSV parser fed a page's body as it arrives
+///
+/// Rows are read with [`CsvDecoder::records`] once their line is complete;
+/// a quoted field with line breaks keeps its row buffered until it closes.
+#[derive(Debug)]
+struct CsvParser {
+    /// Decoder settings
+    decoder: CsvDecoder,
+    /// Bytes not yet read as rows
+    buffer: Vec<u8>,
+    /// Bytes of `buffer` already scanned for row ends
+    scanned: usize,
+    /// Whether the scan stopped inside a quoted field
+    in_quotes: bool,
+    /// Whether the scan stopped after an escape character
+    escaped: bool,
+    /// Column names, once read
+    headers: Option<Vec<String>>,
+    /// Lines read before `buffer`
+    lines_read: u64,
+    /// Rows set aside since the last `take_rejected`
+    rejected: Vec<RejectedRecord>,
 }
 
-impl XmlDecoder {
-    /// Create a new XML decoder
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Create an XML decoder with a record element name
-    pub fn with_element(element: impl Into<String>) -> Self {
+impl CsvParser {
+    /// Create a parser with the decoder's settings
+    fn new(decoder: CsvDecoder) -> Self {
         Self {
-            record_element: Some(element.into()),
+            decoder,
+            buffer: Vec::new(),
+            scanned: 0,
+            in_quotes: false,
+            escaped: false,
+            headers: None,
+            lines_read: 0,
+            rejected: Vec::new(),
         }
     }
-}
 
-impl RecordDecoder for XmlDecoder {
-    fn decode(&self, body: &str) -> Result<Vec<Value>> {
-        // Simple XML to JSON conversion
-        // For a full implementation, we'd use quick-xml or similar
-        // This is a basic implementation for common patterns
-
-        let json = xml_to_json(body)?;
-
-        match &self.record_element {
-            Some(element) => {
-                // Extract records from specified element
-                if let Some(records) = extract_simple_path(&json, element) {
-                    match records {
-                        Value::Array(arr) => Ok(arr),
-                        v => Ok(vec![v]),
-                    }
-                } else {
-                    Ok(vec![])
-                }
-            }
-            None => {
-                // Return entire parsed result
-                match json {
-                    Value::Array(arr) => Ok(arr),
-                    v => Ok(vec![v]),
-                }
+    /// End of the last complete row in the buffer, outside quoted fields
+    fn complete_rows(&mut self) -> usize {
+        let quote = ascii_byte(self.decoder.quote);
+        let escape = self.decoder.escape.map(ascii_byte);
+        let mut end = 0;
+        for (i, &byte) in self.buffer.iter().enumerate().skip(self.scanned) {
+            if self.escaped {
+                self.escaped = false;
+            } else if self.in_quotes && Some(byte) == escape {
+                self.escaped = true;
+            } else if byte == quote {
+                self.in_quotes = !self.in_quotes;
+            } else if byte == b'\n' && !self.in_quotes {
+                end = i + 1;
             }
         }
+        self.scanned = self.buffer.len();
+        end
     }
 
-    fn decode_raw(&self, body: &str) -> Result<Value> {
-        xml_to_json(body)
-    }
-}
-
-/// Basic XML to JSON conversion
-/// This handles simple XML structures - for complex XML, consider quick-xml
-fn xml_to_json(xml: &str) -> Result<Value> {
-    // Very basic XML parsing - handles simple element structures
-    // Real implementation would use a proper XML parser
-
-    let xml = xml.trim();
-
-    // Check if this looks like XML
-    if !xml.starts_with('<') {
-        return Err(Error::XmlParse {
-            message: "Input does not appear to be XML".to_string(),
-        });
+    /// Read the first `end` bytes of the buffer as rows
+    fn read(&mut self, end: usize) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        if end == 0 {
+            return Ok(records);
+        }
+        let body = &self.buffer[..end];
+        let mut rows = self.decoder.records_after(body, self.headers.take());
+        let read = self.decoder.read_rows(
+            &mut rows,
+            body,
+            self.lines_read,
+            &mut records,
+            &mut self.rejected,
+        );
+        self.headers = rows.headers;
+        read?;
+
+        #[allow(clippy::naive_bytecount)]
+        let lines = body.iter().filter(|&&byte| byte == b'\n').count();
+        self.lines_read += lines as u64;
+        self.buffer.drain(..end);
+        self.scanned -= end;
+        Ok(records)
     }
-
-    // Simple recursive descent parser for basic XML
-    parse_xml_element(xml).map(|(v, _)| v)
 }
 
-/// Parse an XML element and return the JSON value and remaining string
-fn parse_xml_element(input: &str) -> Result<(Value, &str)> {
-    let input = input.trim();
-
-    // Check for text content (no tags)
-    if !input.starts_with('<') {
-        // Find the end of text content
-        if let Some(pos) = input.find('<') {
-            let text = input[..pos].trim();
-            if text.is_empty() {
-                return parse_xml_element(&input[pos..]);
-            }
-            return Ok((parse_text_value(text), &input[pos..]));
-        }
-        return Ok((parse_text_value(input.trim()), ""));
+impl StreamParser for CsvParser {
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Value>> {
+        self.buffer.extend_from_slice(chunk);
+        let end = self.complete_rows();
+        self.read(end)
     }
 
-    // Skip XML declaration
-    if input.starts_with("<?") {
-        if let Some(end) = input.find("?>") {
-            return parse_xml_element(&input[end + 2..]);
-        }
+    fn finish(&mut self) -> Result<Vec<Value>> {
+        self.read(self.buffer.len())
     }
 
-    // Find tag name
-    let tag_start = input.find('<').ok_or_else(|| Error::XmlParse {
-        message: "Expected opening tag".to_string(),
-    })?;
-
-    let tag_end = input[tag_start..]
-        .find(['>', ' ', '/'])
-        .ok_or_else(|| Error::XmlParse {
-            message: "Malformed tag".to_string(),
-        })?;
-
-    let tag_name = &input[tag_start + 1..tag_start + tag_end];
-
-    // Self-closing tag
-    if input[tag_start..].contains("/>") {
-        let close_pos = input.find("/>").unwrap();
-        return Ok((Value::Null, &input[close_pos + 2..]));
+    fn take_rejected(&mut self) -> Vec<RejectedRecord> {
+        std::mem::take(&mut self.rejected)
     }
+}
 
-    // Find closing tag
-    let close_tag = format!("</{tag_name}>");
-    let close_pos = input.find(&close_tag).ok_or_else(|| Error::XmlParse {
-        message: format!("Missing closing tag for {tag_name}"),
-    })?;
+/// Iterator over the records of a CSV reader
+///
+/// Rows are read one at a time, so only the reader's buffer is held besides
+/// the current row.
+pub struct CsvRecords<'a, R> {
+    /// Decoder settings
+    decoder: &'a CsvDecoder,
+    /// Underlying CSV reader
+    reader: csv::Reader<R>,
+    /// Column names (from the header row or generated)
+    headers: Option<Vec<String>>,
+    /// Reused row buffer
+    row: csv::StringRecord,
+}
 
-    // Find content start (after opening tag >)
-    let content_start = input.find('>').unwrap() + 1;
-    let content = &input[content_start..close_pos];
-    let remaining = &input[close_pos + close_tag.len()..];
+/// A CSV row that could not be read
+struct CsvRowError {
+    /// Line where the row starts
+    line: u64,
+    /// Byte range of the row in the input
+    span: Range<usize>,
+    /// What went wrong
+    reason: String,
+}
 
-    // Parse content
-    if content.trim().is_empty() {
-        return Ok((Value::Null, remaining));
+impl CsvRowError {
+    /// Convert into a decode error with the row's line
+    fn into_error(self) -> Error {
+        Error::Decode {
+            message: format!("Failed to parse CSV at line {}: {}", self.line, self.reason),
+        }
     }
+}
 
-    // Check if content contains child elements
-    if content.contains('<') {
-        // Parse child elements
-        let mut obj = Map::new();
-        let mut current = content.trim();
-
-        while !current.is_empty() && current.contains('<') {
-            // Skip text nodes
-            if !current.starts_with('<') {
-                if let Some(pos) = current.find('<') {
-                    current = &current[pos..];
-                } else {
-                    break;
+impl<R: Read> CsvRecords<'_, R> {
+    /// Read the next record, reporting bad rows with their location
+    fn next_row(&mut self) -> Option<std::result::Result<Value, CsvRowError>> {
+        loop {
+            let start = self.reader.position().clone();
+            let read = self.reader.read_record(&mut self.row);
+            let span = byte_offset(&start)..byte_offset(self.reader.position());
+            match read {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => {
+                    return Some(Err(CsvRowError {
+                        line: e.position().map_or(start.line(), csv::Position::line),
+                        span,
+                        reason: e.to_string(),
+                    }))
                 }
             }
 
-            // Get child tag name
-            if let Some(child_tag_end) = current[1..].find(['>', ' ', '/']) {
-                let child_tag = &current[1..=child_tag_end];
-
-                // Skip closing tags
-                if child_tag.starts_with('/') {
-                    break;
-                }
-
-                let (child_value, rest) = parse_xml_element(current)?;
+            // Skip blank lines
+            if self.row.len() == 1 && self.row[0].trim().is_empty() {
+                continue;
+            }
 
-                // Handle arrays (multiple elements with same name)
-                if let Some(existing) = obj.get_mut(child_tag) {
-                    if let Value::Array(arr) = existing {
-                        arr.push(child_value);
-                    } else {
-                        let arr = vec![existing.clone(), child_value];
-                        obj.insert(child_tag.to_string(), Value::Array(arr));
-                    }
-                } else {
-                    obj.insert(child_tag.to_string(), child_value);
+            if self.headers.is_none() {
+                if self.decoder.has_header {
+                    let names = self.row.iter().enumerate().map(|(i, name)| {
+                        // A UTF-8 BOM ends up in the first header
+                        let name = if i == 0 {
+                            name.trim_start_matches('\u{feff}')
+                        } else {
+                            name
+                        };
+                        name.trim().to_string()
+                    });
+                    self.headers = Some(names.collect());
+                    continue;
                 }
+                // Generate numeric column names
+                self.headers = Some((0..self.row.len()).map(|i| format!("column_{i}")).collect());
+            }
+            let headers = self.headers.as_ref()?;
+
+            if self.decoder.on_error == DecodeErrorPolicy::Quarantine
+                && self.row.len() != headers.len()
+            {
+                return Some(Err(CsvRowError {
+                    line: self
+                        .row
+                        .position()
+                        .map_or(start.line(), csv::Position::line),
+                    span,
+                    reason: format!(
+                        "expected {} fields, found {}",
+                        headers.len(),
+                        self.row.len()
+                    ),
+                }));
+            }
 
-                current = rest.trim();
-            } else {
-                break;
+            let mut obj = Map::new();
+            for (i, header) in headers.iter().enumerate() {
+                let value = self
+                    .row
+                    .get(i)
+                    .map_or(Value::Null, |cell| self.decoder.cell_value(header, cell));
+                obj.insert(header.clone(), value);
             }
+            return Some(Ok(Value::Object(obj)));
         }
+    }
+}
+
+impl<R: Read> Iterator for CsvRecords<'_, R> {
+    type Item = Result<Value>;
 
-        return Ok((Value::Object(obj), remaining));
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_row()?.map_err(CsvRowError::into_error))
     }
+}
 
-    // Text content only
-    Ok((parse_text_value(content.trim()), remaining))
+/// Byte offset of a CSV reader position
+fn byte_offset(position: &csv::Position) -> usize {
+    usize::try_from(position.byte()).unwrap_or(usize::MAX)
+}
+/// Convert a delimiter, quote or escape character to a byte
+///
+/// The loader rejects non-ASCII characters; others map to `?`.
+fn ascii_byte(c: char) -> u8 {
+    u8::try_from(c).unwrap_or(b'?')
 }
 
-/// Parse text content into appropriate JSON value
-fn parse_text_value(text: &str) -> Value {
-    // Try integer
-    if let Ok(n) = text.parse::<i64>() {
-        return Value::Number(n.into());
-    }
+/// Cast a CSV cell or XML value to a schema type
+///
+/// Values that do not parse as the type are kept as strings.
+pub(super) fn cast_csv_value(value: &str, json_type: &JsonType) -> Value {
+    let parsed = match json_type {
+        JsonType::Integer => value.parse::<i64>().ok().map(Value::from),
+        JsonType::Number => value
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        JsonType::Boolean => match value.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Some(Value::Bool(true)),
+            "false" | "no" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Value::String(value.to_string()))
+}
+
+/// Infer a JSON value from a CSV cell
+///
+/// Values with leading zeros (zip codes, IDs) stay strings.
+fn parse_csv_value(value: &str) -> Value {
+    if !is_zero_padded(value) {
+        // Try integer
+        if let Ok(n) = value.parse::<i64>() {
+            return Value::Number(n.into());
+        }
 
-    // Try float
-    if let Ok(n) = text.parse::<f64>() {
-        if let Some(num) = serde_json::Number::from_f64(n) {
-            return Value::Number(num);
+        // Try float
+        if let Ok(n) = value.parse::<f64>() {
+            if let Some(num) = serde_json::Number::from_f64(n) {
+                return Value::Number(num);
+            }
         }
     }
 
     // Try boolean
-    match text.to_lowercase().as_str() {
-        "true" => return Value::Bool(true),
-        "false" => return Value::Bool(false),
+    match value.to_lowercase().as_str() {
+        "true" | "yes" => return Value::Bool(true),
+        "false" | "no" => return Value::Bool(false),
         _ => {}
     }
 
-    Value::String(text.to_string())
+    // Null
+    if value.eq_ignore_ascii_case("null") || value.eq_ignore_ascii_case("none") {
+        return Value::Null;
+    }
+
+    // String
+    Value::String(value.to_string())
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Extract a value using simple dot-notation path
-fn extract_simple_path(value: &Value, path: &str) -> Option<Value> {
-    let path = path.strip_prefix("$.").unwrap_or(path);
-    let parts: Vec<&str> = path.split('.').collect();
-
-    let mut current = value;
-    for part in parts {
-        // Handle array indexing like "data[0]" or "items[-1]"
-        if let Some(bracket_pos) = part.find('[') {
-            let name = &part[..bracket_pos];
-            let index_str = &part[bracket_pos + 1..part.len() - 1];
+/// Check for a number with leading zeros (zip codes, IDs), kept as a string
+fn is_zero_padded(value: &str) -> bool {
+    let digits = value.trim_start_matches(['-', '+']);
+    digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.")
+}
 
-            if !name.is_empty() {
-                current = current.get(name)?;
-            }
+/// Parse XML text content into appropriate JSON value
+///
+/// Values with leading zeros stay strings.
+pub(super) fn parse_text_value(text: &str) -> Value {
+    if !is_zero_padded(text) {
+        // Try integer
+        if let Ok(n) = text.parse::<i64>() {
+            return Value::Number(n.into());
+        }
 
-            // Handle special array syntax
-            if index_str == "*" {
-                // Return the array as-is for extraction
-                return Some(current.clone());
-            } else if let Ok(index) = index_str.parse::<i64>() {
-                if let Value::Array(arr) = current {
-                    #[allow(
-                        clippy::cast_possible_truncation,
-                        clippy::cast_sign_loss,
-                        clippy::cast_possible_wrap
-                    )]
-                    let idx = if index < 0 {
-                        (arr.len() as i64 + index) as usize
-                    } else {
-                        index as usize
-                    };
-                    current = arr.get(idx)?;
-                } else {
-                    return None;
-                }
-            } else {
-                return None;
+        // Try float
+        if let Ok(n) = text.parse::<f64>() {
+            if let Some(num) = serde_json::Number::from_f64(n) {
+                return Value::Number(num);
             }
-        } else {
-            current = current.get(part)?;
         }
     }
 
-    Some(current.clone())
-}
-
-/// Extract records using jsonpath-rust
-fn extract_with_jsonpath(value: &Value, path: &str) -> Result<Vec<Value>> {
-    use jsonpath_rust::JsonPath;
-
-    let jp = JsonPath::try_from(path).map_err(|e| Error::JsonPath {
-        message: format!("Invalid JSONPath: {e}"),
-    })?;
-
-    let result = jp.find(value);
-
-    // Handle the result based on what was returned
-    match result {
-        Value::Array(arr) => Ok(arr),
-        Value::Null => Ok(vec![]),
-        other => Ok(vec![other]),
+    // Try boolean
+    match text.to_lowercase().as_str() {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
     }
+
+    Value::String(text.to_string())
 }
@@ -1,17 +1,40 @@
 //! Response decoder module
 //!
-//! Supports: JSON, JSONL, CSV, XML
+//! Supports: JSON, JSONL, CSV, XML, GraphQL connections, Parquet, Avro,
+//! gzip/zstd/zip/tar archives wrapping any of them, and streaming NDJSON or
+//! Server-Sent Events responses
 //!
 //! # Overview
 //!
 //! The decode module provides parsers for common API response formats.
 //! Each decoder extracts records from the response body using a configured path.
+//! Decoders own parsing: `decode_page` returns the records together with a
+//! JSON view of the response for paginators and stop conditions, so non-JSON
+//! streams page through the engine like JSON ones. Streaming decoders return
+//! a `StreamParser` instead, which the engine feeds as the body arrives.
+//! CSV pages are parsed the same way, through a page parser, and still
+//! paginate.
+//!
+//! JSONL and CSV decoders can quarantine unreadable lines and rows
+//! (`DecodeErrorPolicy::Quarantine`): the page keeps its good records and
+//! lists the rejected ones with their raw text, error and line.
 
+mod archive;
+mod columnar;
 mod decoders;
+mod stream;
 mod types;
+mod xml;
 
-pub use decoders::{CsvDecoder, JsonDecoder, JsonlDecoder, XmlDecoder};
-pub use types::{DecoderConfig, DecoderFormat, RecordDecoder};
+pub use archive::{ArchiveDecoder, ArchiveFormat};
+pub use columnar::{AvroDecoder, ParquetDecoder};
+pub use decoders::{CsvDecoder, CsvRecords, GraphqlDecoder, JsonDecoder, JsonlDecoder};
+pub use stream::{StreamDecoder, StreamFormat};
+pub use types::{
+    CsvCoercion, DecodeErrorPolicy, DecodedPage, DecoderConfig, DecoderFormat, RecordDecoder,
+    RejectedRecord, StreamParser,
+};
+pub use xml::XmlDecoder;
 
 #[cfg(test)]
 mod tests;
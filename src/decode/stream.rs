@@ -0,0 +1,298 @@
+//! Streaming decoder
+//!
+//! Decodes long-lived responses that deliver records as they are produced:
+//! newline-delimited JSON and Server-Sent Events. The engine feeds the body
+//! chunk by chunk, so one request can carry millions of records without
+//! buffering the whole response.
+
+use super::types::{DecodedPage, RecordDecoder, StreamParser};
+use crate::error::{Error, Result};
+use serde_json::Value;
+
+/// Wire format of a streaming response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamFormat {
+    /// One JSON record per line
+    #[default]
+    Ndjson,
+    /// Server-Sent Events with a JSON `data:` payload per event
+    Sse,
+}
+
+impl StreamFormat {
+    /// Parse a format name ("ndjson" or "sse")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "sse" | "event-stream" => Some(Self::Sse),
+            _ => None,
+        }
+    }
+}
+
+/// Decoder for NDJSON and Server-Sent Events streams
+///
+/// SSE events are dispatched on blank lines; the `data:` lines of an event
+/// are joined and parsed as JSON (an array yields one record per element).
+/// Comments and events without data are skipped. The last event `id:` is
+/// exposed for checkpoints and sent back as `Last-Event-ID` on resume.
+#[derive(Debug, Clone, Default)]
+pub struct StreamDecoder {
+    /// Wire format
+    format: StreamFormat,
+    /// SSE event types to keep (all when empty)
+    events: Vec<String>,
+}
+
+impl StreamDecoder {
+    /// Create a streaming decoder for a format
+    pub fn new(format: StreamFormat) -> Self {
+        Self {
+            format,
+            events: Vec::new(),
+        }
+    }
+
+    /// Only keep SSE events of these types (`message` for events without `event:`)
+    #[must_use]
+    pub fn with_events<I, S>(mut self, events: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Create a parser for one response
+    fn parser(&self) -> Box<dyn StreamParser> {
+        match self.format {
+            StreamFormat::Ndjson => Box::new(NdjsonParser::default()),
+            StreamFormat::Sse => Box::new(SseParser::new(self.events.clone())),
+        }
+    }
+}
+
+impl RecordDecoder for StreamDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        let mut parser = self.parser();
+        let mut records = parser.feed(body.as_bytes())?;
+        records.extend(parser.finish()?);
+        Ok(records)
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        Ok(Value::Array(self.decode(body)?))
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let records = self.decode(body)?;
+        Ok(DecodedPage {
+            response: Value::Array(records.clone()),
+            records,
+            batches: None,
+            rejected: Vec::new(),
+        })
+    }
+
+    fn stream_parser(&self) -> Option<Box<dyn StreamParser>> {
+        Some(self.parser())
+    }
+}
+
+// ============================================================================
+// Line Splitting
+// ============================================================================
+
+/// Splits a byte stream into lines ending in `\n`, `\r\n` or `\r`
+#[derive(Debug, Default)]
+struct LineBuffer {
+    /// Bytes of the incomplete last line
+    partial: Vec<u8>,
+    /// The previous chunk ended in `\r` (a leading `\n` belongs to it)
+    after_cr: bool,
+}
+
+impl LineBuffer {
+    /// Feed a chunk, returning the lines it completes
+    fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::take(&mut self.after_cr);
+            match byte {
+                b'\n' if after_cr => {}
+                b'\n' | b'\r' => {
+                    lines.push(String::from_utf8_lossy(&self.partial).into_owned());
+                    self.partial.clear();
+                    self.after_cr = byte == b'\r';
+                }
+                _ => self.partial.push(byte),
+            }
+        }
+        lines
+    }
+
+    /// Take the unterminated last line, if any
+    fn finish(&mut self) -> Option<String> {
+        let line = String::from_utf8_lossy(&std::mem::take(&mut self.partial)).into_owned();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+// ============================================================================
+// NDJSON
+// ============================================================================
+
+/// Incremental NDJSON parser
+#[derive(Debug, Default)]
+struct NdjsonParser {
+    /// Line splitter
+    lines: LineBuffer,
+    /// Lines read so far (for error messages)
+    line_count: usize,
+}
+
+impl NdjsonParser {
+    /// Parse one line, skipping blank ones
+    fn parse_line(&mut self, line: &str, records: &mut Vec<Value>) -> Result<()> {
+        self.line_count += 1;
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let record = serde_json::from_str(line).map_err(|e| Error::Decode {
+            message: format!("Failed to parse NDJSON at line {}: {e}", self.line_count),
+        })?;
+        records.push(record);
+        Ok(())
+    }
+}
+
+impl StreamParser for NdjsonParser {
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        for line in self.lines.feed(chunk) {
+            self.parse_line(&line, &mut records)?;
+        }
+        Ok(records)
+    }
+
+    fn finish(&mut self) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        if let Some(line) = self.lines.finish() {
+            self.parse_line(&line, &mut records)?;
+        }
+        Ok(records)
+    }
+}
+
+// ============================================================================
+// Server-Sent Events
+// ============================================================================
+
+/// Incremental Server-Sent Events parser (WHATWG event stream format)
+#[derive(Debug, Default)]
+struct SseParser {
+    /// Line splitter
+    lines: LineBuffer,
+    /// Event types to keep (all when empty)
+    events: Vec<String>,
+    /// `data:` lines of the event being read
+    data: Vec<String>,
+    /// `event:` type of the event being read
+    event_type: Option<String>,
+    /// Last `id:` received
+    id_buffer: Option<String>,
+    /// ID of the last dispatched event
+    last_event_id: Option<String>,
+}
+
+impl SseParser {
+    /// Create a parser keeping the given event types
+    fn new(events: Vec<String>) -> Self {
+        Self {
+            events,
+            ..Self::default()
+        }
+    }
+
+    /// Process one line of the stream
+    fn process_line(&mut self, line: &str, records: &mut Vec<Value>) -> Result<()> {
+        if line.is_empty() {
+            return self.dispatch(records);
+        }
+        if line.starts_with(':') {
+            // Comment (often a keep-alive)
+            return Ok(());
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => self.data.push(value.to_string()),
+            "event" => self.event_type = Some(value.to_string()),
+            "id" if !value.contains('\0') => {
+                self.id_buffer = (!value.is_empty()).then(|| value.to_string());
+            }
+            // `retry` and unknown fields are ignored
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Dispatch the event read so far
+    fn dispatch(&mut self, records: &mut Vec<Value>) -> Result<()> {
+        self.last_event_id.clone_from(&self.id_buffer);
+        let event_type = self.event_type.take();
+        let data = std::mem::take(&mut self.data);
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let event_type = event_type.as_deref().unwrap_or("message");
+        if !self.events.is_empty() && !self.events.iter().any(|e| e == event_type) {
+            return Ok(());
+        }
+
+        let data = data.join("\n");
+        let payload: Value = serde_json::from_str(&data).map_err(|e| Error::Decode {
+            message: format!(
+                "Failed to parse SSE event{} data as JSON: {e}",
+                self.last_event_id
+                    .as_ref()
+                    .map(|id| format!(" '{id}'"))
+                    .unwrap_or_default()
+            ),
+        })?;
+        match payload {
+            Value::Array(items) => records.extend(items),
+            payload => records.push(payload),
+        }
+        Ok(())
+    }
+}
+
+impl StreamParser for SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        for line in self.lines.feed(chunk) {
+            self.process_line(&line, &mut records)?;
+        }
+        Ok(records)
+    }
+
+    fn finish(&mut self) -> Result<Vec<Value>> {
+        // An event not terminated by a blank line is discarded
+        let mut records = Vec::new();
+        if let Some(line) = self.lines.finish() {
+            self.process_line(&line, &mut records)?;
+        }
+        Ok(records)
+    }
+
+    fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+}
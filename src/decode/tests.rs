@@ -125,6 +125,110 @@ fn test_json_decoder_jsonpath_wildcard() {
     assert_eq!(records.len(), 2);
 }
 
+#[test]
+fn test_json_decoder_jsonpath_filter() {
+    let decoder = JsonDecoder::with_path("$.data[?(@.type == 'invoice')]");
+    let body = r#"{"data": [{"id": 1, "type": "invoice"}, {"id": 2, "type": "refund"}]}"#;
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["id"], 1);
+}
+
+#[test]
+fn test_json_decoder_multiple_paths() {
+    let decoder = JsonDecoder::with_paths(["data[*]", "included[*]"]);
+    let body = r#"{
+        "data": [{"id": "1", "type": "articles"}],
+        "included": [{"id": "9", "type": "people"}, {"id": "5", "type": "comments"}]
+    }"#;
+
+    let records = decoder.decode(body).unwrap();
+    let types: Vec<_> = records.iter().map(|r| r["type"].clone()).collect();
+    assert_eq!(types, vec!["articles", "people", "comments"]);
+}
+
+#[test]
+fn test_json_decoder_map_key() {
+    let body = r#"{
+        "2024-01-01": {"cost": 1.5, "requests": 10},
+        "2024-01-02": {"cost": 2.0, "requests": 12}
+    }"#;
+
+    let records = JsonDecoder::new()
+        .with_map_key("date")
+        .decode(body)
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0],
+        serde_json::json!({"date": "2024-01-01", "cost": 1.5, "requests": 10})
+    );
+    assert_eq!(records[1]["date"], "2024-01-02");
+
+    // Without the option the map is a single record
+    assert_eq!(JsonDecoder::new().decode(body).unwrap().len(), 1);
+}
+
+#[test]
+fn test_json_decoder_map_key_at_path() {
+    let decoder = JsonDecoder::with_path("result.by_zone").with_map_key("zone_id");
+    let body = r#"{"result": {"by_zone": {"z1": {"hits": 3}, "z2": 7}}}"#;
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0], serde_json::json!({"zone_id": "z1", "hits": 3}));
+    // Non-object entries are kept under `value`
+    assert_eq!(records[1], serde_json::json!({"zone_id": "z2", "value": 7}));
+}
+
+#[test]
+fn test_json_decoder_unnest() {
+    let decoder = JsonDecoder::with_path("data").with_unnest("results");
+    let body = r#"{"data": [
+        {"start_time": 100, "results": [
+            {"model": "gpt-4o", "input_tokens": 10},
+            {"model": "gpt-4o-mini", "input_tokens": 20}
+        ]},
+        {"start_time": 200, "results": []},
+        {"start_time": 300}
+    ]}"#;
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(
+        records,
+        vec![
+            serde_json::json!({"start_time": 100, "model": "gpt-4o", "input_tokens": 10}),
+            serde_json::json!({"start_time": 100, "model": "gpt-4o-mini", "input_tokens": 20}),
+            // Empty arrays drop the record; a missing array keeps it
+            serde_json::json!({"start_time": 300}),
+        ]
+    );
+}
+
+#[test]
+fn test_json_decoder_map_key_and_unnest() {
+    let decoder = JsonDecoder::new()
+        .with_map_key("date")
+        .with_unnest("usage.tags");
+    let body = r#"{"2024-01-01": {"usage": {"total": 5, "tags": ["a", "b"]}}}"#;
+
+    let records = decoder.decode(body).unwrap();
+    // Scalar elements replace the array
+    assert_eq!(
+        records,
+        vec![
+            serde_json::json!({"date": "2024-01-01", "usage": {"total": 5, "tags": "a"}}),
+            serde_json::json!({"date": "2024-01-01", "usage": {"total": 5, "tags": "b"}}),
+        ]
+    );
+}
+
+#[test]
+fn test_json_decoder_invalid_path() {
+    let decoder = JsonDecoder::with_path("data[?(@.type ==");
+    assert!(decoder.decode(r#"{"data": []}"#).is_err());
+}
+
 #[test]
 fn test_json_decoder_raw() {
     let decoder = JsonDecoder::new();
@@ -134,6 +238,16 @@ fn test_json_decoder_raw() {
     assert_eq!(raw["status"], "ok");
 }
 
+#[test]
+fn test_json_decoder_page() {
+    let decoder = JsonDecoder::with_path("data");
+    let body = r#"{"data": [{"id": 1}, {"id": 2}], "next": "abc"}"#;
+
+    let page = decoder.decode_page(body).unwrap();
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.response["next"], "abc");
+}
+
 #[test]
 fn test_json_decoder_invalid() {
     let decoder = JsonDecoder::new();
@@ -195,6 +309,44 @@ not valid json
     assert!(result.is_err());
 }
 
+#[test]
+fn test_decode_error_policy_parse() {
+    assert_eq!(
+        DecodeErrorPolicy::parse("fail"),
+        Some(DecodeErrorPolicy::Fail)
+    );
+    assert_eq!(
+        DecodeErrorPolicy::parse("Quarantine"),
+        Some(DecodeErrorPolicy::Quarantine)
+    );
+    assert_eq!(DecodeErrorPolicy::parse("skip"), None);
+}
+
+#[test]
+fn test_jsonl_decoder_quarantine() {
+    let decoder = JsonlDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine);
+    let body = "{\"id\": 1}\nnot valid json\n\n{\"id\": 2\n{\"id\": 3}\n";
+
+    let page = decoder.decode_page(body).unwrap();
+    assert_eq!(
+        page.records,
+        vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 3})]
+    );
+    assert_eq!(page.response.as_array().unwrap().len(), 2);
+
+    let lines: Vec<_> = page
+        .rejected
+        .iter()
+        .map(|r| (r.line, r.raw.as_str()))
+        .collect();
+    assert_eq!(lines, vec![(2, "not valid json"), (4, "{\"id\": 2")]);
+    assert!(page.rejected[0].error.contains("expected"));
+    assert!(page.rejected[0].file.is_none());
+
+    // `decode` keeps the good records
+    assert_eq!(decoder.decode(body).unwrap().len(), 2);
+}
+
 // ============================================================================
 // CSV Decoder Tests
 // ============================================================================
@@ -206,12 +358,13 @@ fn test_csv_decoder_basic() {
 1,Alice,30
 2,Bob,25"#;
 
+    // Cells stay strings without coercion
     let records = decoder.decode(body).unwrap();
     assert_eq!(records.len(), 2);
-    assert_eq!(records[0]["id"], 1);
+    assert_eq!(records[0]["id"], "1");
     assert_eq!(records[0]["name"], "Alice");
-    assert_eq!(records[0]["age"], 30);
-    assert_eq!(records[1]["id"], 2);
+    assert_eq!(records[0]["age"], "30");
+    assert_eq!(records[1]["id"], "2");
     assert_eq!(records[1]["name"], "Bob");
 }
 
@@ -228,6 +381,68 @@ fn test_csv_decoder_quoted_fields() {
     assert_eq!(records[1]["description"], "He said \"Hi\"");
 }
 
+#[test]
+fn test_csv_decoder_multiline_fields() {
+    let decoder = CsvDecoder::new();
+    let body = "Id,Description\r\n1,\"Line one\r\nLine two\"\r\n2,\"Ends with \"\"quote\"\"\"\r\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["Description"], "Line one\r\nLine two");
+    assert_eq!(records[1]["Description"], "Ends with \"quote\"");
+}
+
+#[test]
+fn test_csv_decoder_bom_and_blank_lines() {
+    let decoder = CsvDecoder::new();
+    let body = "\u{feff}id,name\n1,Alice\n\n2,Bob\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["id"], "1");
+    assert_eq!(records[1]["name"], "Bob");
+}
+
+#[test]
+fn test_csv_decoder_quote_and_escape() {
+    let decoder = CsvDecoder::with_options(';', true)
+        .with_quote('\'')
+        .with_escape(Some('\\'));
+    let body = "id;note\n1;'it\\'s; fine'\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["note"], "it's; fine");
+}
+
+#[test]
+fn test_csv_decoder_ragged_rows() {
+    let decoder = CsvDecoder::new();
+    let body = "id,name,email\n1,Alice\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["name"], "Alice");
+    assert!(records[0]["email"].is_null());
+}
+
+#[test]
+fn test_csv_decoder_quarantine() {
+    let decoder = CsvDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine);
+    let body = "id,name\n1,Alice\n2,Bob,extra\n3,\"Carol\nSmith\"\n4\n";
+
+    let page = decoder.decode_page(body).unwrap();
+    let ids: Vec<_> = page.records.iter().map(|r| r["id"].clone()).collect();
+    assert_eq!(ids, vec!["1", "3"]);
+    assert_eq!(page.records[1]["name"], "Carol\nSmith");
+
+    // Ragged rows are set aside with their raw text instead of being padded
+    assert_eq!(page.rejected.len(), 2);
+    assert_eq!(page.rejected[0].line, 3);
+    assert_eq!(page.rejected[0].raw, "2,Bob,extra");
+    assert_eq!(page.rejected[0].error, "expected 2 fields, found 3");
+    assert_eq!(page.rejected[1].line, 6);
+    assert_eq!(page.rejected[1].raw, "4");
+}
+
 #[test]
 fn test_csv_decoder_no_header() {
     let decoder = CsvDecoder::with_options(',', false);
@@ -236,7 +451,7 @@ fn test_csv_decoder_no_header() {
 
     let records = decoder.decode(body).unwrap();
     assert_eq!(records.len(), 2);
-    assert_eq!(records[0]["column_0"], 1);
+    assert_eq!(records[0]["column_0"], "1");
     assert_eq!(records[0]["column_1"], "Alice");
 }
 
@@ -247,27 +462,64 @@ fn test_csv_decoder_tab_delimiter() {
 
     let records = decoder.decode(body).unwrap();
     assert_eq!(records.len(), 2);
-    assert_eq!(records[0]["id"], 1);
+    assert_eq!(records[0]["id"], "1");
     assert_eq!(records[0]["name"], "Alice");
 }
 
+#[test]
+fn test_csv_decoder_schema_coercion() {
+    let schema: crate::schema::JsonSchema = serde_json::from_value(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "amount": {"type": ["number", "null"]},
+            "active": {"type": "boolean"}
+        }
+    }))
+    .unwrap();
+    let decoder = CsvDecoder::new()
+        .with_coercion(CsvCoercion::Schema)
+        .with_schema(&schema);
+    let body = "id,zip,amount,active\n7,02134,12.5,yes\n8,10001,,false\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["id"], 7);
+    assert_eq!(records[0]["zip"], "02134");
+    assert_eq!(records[0]["amount"], 12.5);
+    assert_eq!(records[0]["active"], true);
+    assert!(records[1]["amount"].is_null());
+    assert_eq!(records[1]["active"], false);
+}
+
 #[test]
 fn test_csv_decoder_booleans() {
-    let decoder = CsvDecoder::new();
+    let decoder = CsvDecoder::new().with_coercion(CsvCoercion::Infer);
     let body = r#"id,active,deleted
 1,true,false
 2,yes,no"#;
 
     let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["id"], 1);
     assert_eq!(records[0]["active"], true);
     assert_eq!(records[0]["deleted"], false);
     assert_eq!(records[1]["active"], true);
     assert_eq!(records[1]["deleted"], false);
 }
 
+#[test]
+fn test_csv_decoder_infer_keeps_leading_zeros() {
+    let decoder = CsvDecoder::new().with_coercion(CsvCoercion::Infer);
+    let body = "zip,ratio,count\n02134,0.5,0\n";
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["zip"], "02134");
+    assert_eq!(records[0]["ratio"], 0.5);
+    assert_eq!(records[0]["count"], 0);
+}
+
 #[test]
 fn test_csv_decoder_nulls() {
-    let decoder = CsvDecoder::new();
+    let decoder = CsvDecoder::new().with_coercion(CsvCoercion::Infer);
     let body = r#"id,value
 1,
 2,null
@@ -279,6 +531,65 @@ fn test_csv_decoder_nulls() {
     assert!(records[2]["value"].is_null());
 }
 
+#[test]
+fn test_csv_decoder_reader() {
+    let decoder = CsvDecoder::new();
+    let body = "id,name\n1,\"multi\nline\"\n2,Bob\n";
+
+    // Records are parsed incrementally from a byte stream
+    let mut records = decoder.records(std::io::BufReader::with_capacity(4, body.as_bytes()));
+    assert_eq!(records.next().unwrap().unwrap()["name"], "multi\nline");
+    assert_eq!(records.next().unwrap().unwrap()["id"], "2");
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_csv_decoder_page_parser() {
+    let decoder = CsvDecoder::new();
+    let mut parser = decoder.page_parser().unwrap();
+
+    // Complete rows are returned as they arrive; an open quoted field waits
+    assert!(parser.feed(b"id,na").unwrap().is_empty());
+    assert!(parser.feed(b"me\n1,\"multi\n").unwrap().is_empty());
+    let records = parser.feed(b"line\"\n2,B").unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["name"], "multi\nline");
+    let records = parser.finish().unwrap();
+    assert_eq!(records, vec![serde_json::json!({"id": "2", "name": "B"})]);
+}
+
+#[test]
+fn test_csv_decoder_page_parser_quarantine() {
+    let decoder = CsvDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine);
+    let body = "id,name\n1,Alice\n2,Bob,extra\n3,\"Carol\nSmith\"\n4\n";
+
+    // Chunked parsing matches the buffered page, line numbers included
+    let mut parser = decoder.page_parser().unwrap();
+    let mut records = Vec::new();
+    for chunk in body.as_bytes().chunks(3) {
+        records.extend(parser.feed(chunk).unwrap());
+    }
+    records.extend(parser.finish().unwrap());
+    let rejected = parser.take_rejected();
+
+    let page = decoder.decode_page(body).unwrap();
+    assert_eq!(records, page.records);
+    assert_eq!(rejected.len(), 2);
+    for (streamed, buffered) in rejected.iter().zip(&page.rejected) {
+        assert_eq!(streamed.line, buffered.line);
+        assert_eq!(streamed.raw, buffered.raw);
+    }
+}
+
+#[test]
+fn test_csv_decoder_invalid_utf8() {
+    let decoder = CsvDecoder::new();
+    let body: &[u8] = b"id,name\n1,\xff\xfe\n";
+
+    let err = decoder.records(body).next().unwrap().unwrap_err();
+    assert!(err.to_string().contains("line 2"), "{err}");
+}
+
 #[test]
 fn test_csv_decoder_raw() {
     let decoder = CsvDecoder::new();
@@ -288,6 +599,20 @@ fn test_csv_decoder_raw() {
     assert!(raw.is_array());
 }
 
+#[test]
+fn test_csv_decoder_page() {
+    let decoder = CsvDecoder::new();
+    let body = "id,name\n1,Alice\n2,Bob";
+
+    // The pagination view of a CSV page is its records
+    let page = decoder.decode_page(body).unwrap();
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(
+        page.response,
+        serde_json::Value::Array(page.records.clone())
+    );
+}
+
 // ============================================================================
 // XML Decoder Tests
 // ============================================================================
@@ -300,7 +625,7 @@ fn test_xml_decoder_basic() {
     let records = decoder.decode(body).unwrap();
     assert_eq!(records.len(), 1);
     assert_eq!(records[0]["name"], "Test");
-    assert_eq!(records[0]["value"], 42);
+    assert_eq!(records[0]["value"], "42");
 }
 
 #[test]
@@ -313,6 +638,16 @@ fn test_xml_decoder_with_element() {
     assert!(raw["items"]["item"].is_array());
 }
 
+#[test]
+fn test_xml_decoder_page() {
+    let decoder = XmlDecoder::with_element("items.item");
+    let body = r#"<root><items><item><id>1</id></item><item><id>2</id></item></items><next>2</next></root>"#;
+
+    let page = decoder.decode_page(body).unwrap();
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.response["next"], "2");
+}
+
 #[test]
 fn test_xml_decoder_raw() {
     let decoder = XmlDecoder::new();
@@ -329,7 +664,7 @@ fn test_xml_decoder_nested() {
 
     let raw = decoder.decode_raw(body).unwrap();
     assert_eq!(raw["profile"]["name"], "Alice");
-    assert_eq!(raw["profile"]["age"], 30);
+    assert_eq!(raw["profile"]["age"], "30");
 }
 
 #[test]
@@ -341,6 +676,675 @@ fn test_xml_decoder_invalid() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_xml_decoder_attributes_and_text() {
+    let decoder = XmlDecoder::new();
+    let body = r#"<?xml version="1.0"?>
+<invoice id="INV-1" paid="true">
+  <total currency="USD">12.50</total>
+  <zip>02134</zip>
+  <note><![CDATA[5 < 6 & "quoted"]]></note>
+  <memo>Tom &amp; Jerry</memo>
+  <empty/>
+</invoice>"#;
+
+    let raw = decoder.decode_raw(body).unwrap();
+    assert_eq!(raw["@id"], "INV-1");
+    assert_eq!(raw["@paid"], "true");
+    assert_eq!(raw["total"]["@currency"], "USD");
+    assert_eq!(raw["total"]["#text"], "12.50");
+    assert_eq!(raw["zip"], "02134");
+    assert_eq!(raw["note"], "5 < 6 & \"quoted\"");
+    assert_eq!(raw["memo"], "Tom & Jerry");
+    assert!(raw["empty"].is_null());
+
+    // Inferred types keep zero-padded numbers and CDATA as strings
+    let raw = decoder
+        .with_coercion(CsvCoercion::Infer)
+        .decode_raw(body)
+        .unwrap();
+    assert_eq!(raw["@paid"], true);
+    assert_eq!(raw["total"]["#text"], 12.5);
+    assert_eq!(raw["zip"], "02134");
+    assert_eq!(raw["note"], "5 < 6 & \"quoted\"");
+}
+
+#[test]
+fn test_xml_decoder_schema_coercion() {
+    let schema: crate::schema::JsonSchema = serde_json::from_value(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "@active": {"type": "boolean"},
+            "zip": {"type": "string"}
+        }
+    }))
+    .unwrap();
+    let decoder = XmlDecoder::with_element("//user")
+        .with_coercion(CsvCoercion::Schema)
+        .with_schema(&schema);
+    let body =
+        r#"<users><user active="true"><id>7</id><zip>02134</zip><score>9</score></user></users>"#;
+
+    // Only typed record fields are cast
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records[0]["id"], 7);
+    assert_eq!(records[0]["@active"], true);
+    assert_eq!(records[0]["zip"], "02134");
+    assert_eq!(records[0]["score"], "9");
+}
+
+#[test]
+fn test_xml_decoder_namespaces() {
+    let body = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/" xmlns:ns="urn:erp">
+  <soap:Body>
+    <ns:searchResponse>
+      <ns:record ns:type="customer"><ns:id>1</ns:id></ns:record>
+      <ns:record ns:type="vendor"><ns:id>2</ns:id></ns:record>
+      <ns:record ns:type="customer"><ns:id>3</ns:id></ns:record>
+    </ns:searchResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    // Local names by default; xmlns declarations are skipped
+    let decoder = XmlDecoder::with_element("/Envelope/Body/searchResponse/record");
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0]["@type"], "customer");
+    assert!(decoder.decode_raw(body).unwrap().get("@soap").is_none());
+
+    let decoder = XmlDecoder::with_element("//record[@type='customer']");
+    let ids: Vec<_> = decoder
+        .decode(body)
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].clone())
+        .collect();
+    assert_eq!(ids, vec!["1", "3"]);
+
+    let decoder =
+        XmlDecoder::with_element("/soap:Envelope/soap:Body/*/ns:record").with_prefixes(true);
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[1]["ns:id"], "2");
+}
+
+#[test]
+fn test_xml_decoder_repeated_elements() {
+    let body = r#"<feed>
+  <entry><id>1</id><tag>a</tag><tag>b</tag></entry>
+  <entry><id>2</id><tag>c</tag></entry>
+  <meta><entry><id>3</id></entry></meta>
+</feed>"#;
+
+    let raw = XmlDecoder::new().decode_raw(body).unwrap();
+    assert_eq!(raw["entry"][0]["tag"], serde_json::json!(["a", "b"]));
+    assert_eq!(raw["entry"][1]["tag"], "c");
+
+    // Child selection is direct, descendant selection reaches any depth
+    let direct = XmlDecoder::with_element("/feed/entry")
+        .decode(body)
+        .unwrap();
+    assert_eq!(direct.len(), 2);
+    let all = XmlDecoder::with_element("//entry").decode(body).unwrap();
+    assert_eq!(all.len(), 3);
+    let filtered = XmlDecoder::with_element("/feed/entry[id='2']")
+        .decode(body)
+        .unwrap();
+    assert_eq!(filtered[0]["tag"], "c");
+}
+
+#[test]
+fn test_xml_decoder_malformed() {
+    let decoder = XmlDecoder::new();
+
+    let err = decoder.decode("<a><b></a>").unwrap_err();
+    assert!(matches!(err, crate::error::Error::XmlParse { .. }));
+    let err = decoder.decode("<a><b>").unwrap_err();
+    assert!(err.to_string().contains("<b> is not closed"), "{err}");
+    assert!(XmlDecoder::with_element("/a/")
+        .decode("<a/>")
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid XML record path"));
+}
+
+// ============================================================================
+// Archive Decoder Tests
+// ============================================================================
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn tar(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, contents.as_bytes())
+            .unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        writer.start_file(*name, options).unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_archive_format_parse() {
+    assert_eq!(ArchiveFormat::parse("auto"), Some(ArchiveFormat::Auto));
+    assert_eq!(ArchiveFormat::parse("gz"), Some(ArchiveFormat::Gzip));
+    assert_eq!(ArchiveFormat::parse("ZSTD"), Some(ArchiveFormat::Zstd));
+    assert_eq!(ArchiveFormat::parse("zip"), Some(ArchiveFormat::Zip));
+    assert_eq!(ArchiveFormat::parse("tar"), Some(ArchiveFormat::Tar));
+    assert_eq!(ArchiveFormat::parse("rar"), None);
+}
+
+#[test]
+fn test_archive_gzip_jsonl() {
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Gzip, Box::new(JsonlDecoder::new()));
+    let body = gzip(b"{\"id\": 1}\n{\"id\": 2}\n");
+
+    let records = decoder.decode_bytes(&body).unwrap().records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1]["id"], 2);
+}
+
+#[test]
+fn test_archive_auto_detects_zstd() {
+    let decoder = ArchiveDecoder::new(
+        ArchiveFormat::Auto,
+        Box::new(JsonDecoder::with_path("data")),
+    );
+    let body =
+        zstd::stream::encode_all(&br#"{"data": [{"id": 1}], "next": "abc"}"#[..], 0).unwrap();
+
+    let page = decoder.decode_bytes(&body).unwrap();
+    assert_eq!(page.records.len(), 1);
+    // A single file keeps its own response for pagination
+    assert_eq!(page.response["next"], "abc");
+}
+
+#[test]
+fn test_archive_auto_passes_through_plain_body() {
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Auto, Box::new(JsonlDecoder::new()));
+    let records = decoder.decode_bytes(b"{\"id\": 1}\n").unwrap().records;
+    assert_eq!(records.len(), 1);
+}
+
+#[test]
+fn test_archive_zip_csv_with_glob() {
+    let decoder =
+        ArchiveDecoder::new(ArchiveFormat::Auto, Box::new(CsvDecoder::new())).with_files("*.csv");
+    let body = zip(&[
+        ("export/part-2.csv", "id,name\n3,Carol\n"),
+        ("export/README.txt", "not a csv"),
+        ("export/part-1.csv", "id,name\n1,Alice\n2,Bob\n"),
+    ]);
+
+    let page = decoder.decode_bytes(&body).unwrap();
+    let ids: Vec<_> = page.records.iter().map(|r| r["id"].clone()).collect();
+    // Files are decoded in name order
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    assert_eq!(page.response.as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_archive_tar_gz() {
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Gzip, Box::new(JsonlDecoder::new()))
+        .with_files("data/**/*.jsonl");
+    let body = gzip(&tar(&[
+        ("data/a.jsonl", "{\"id\": 1}\n"),
+        ("data/2024/b.jsonl", "{\"id\": 2}\n"),
+        ("other/c.jsonl", "{\"id\": 3}\n"),
+    ]));
+
+    let records = decoder.decode_bytes(&body).unwrap().records;
+    let ids: Vec<_> = records.iter().map(|r| r["id"].clone()).collect();
+    assert_eq!(ids, vec![2, 1]);
+}
+
+#[test]
+fn test_archive_labels_rejected_records() {
+    let inner = JsonlDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine);
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Zip, Box::new(inner));
+    let body = zip(&[
+        ("b.jsonl", "{\"id\": 3}\n{oops\n"),
+        ("a.jsonl", "{\"id\": 1}\n{\"id\": 2}\n"),
+    ]);
+
+    let page = decoder.decode_bytes(&body).unwrap();
+    assert_eq!(page.records.len(), 3);
+    assert_eq!(page.rejected.len(), 1);
+    assert_eq!(page.rejected[0].file.as_deref(), Some("b.jsonl"));
+    assert_eq!(page.rejected[0].line, 2);
+}
+
+#[test]
+fn test_archive_max_unpacked_size() {
+    // 1 MiB of zeros compresses to about a kilobyte
+    let bomb = gzip(&vec![b'0'; 1024 * 1024]);
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Gzip, Box::new(JsonlDecoder::new()))
+        .with_max_size(64 * 1024);
+    let err = decoder.decode_bytes(&bomb).unwrap_err();
+    assert!(err.to_string().contains("more than 65536 bytes"));
+
+    // The limit covers all files of a zip together
+    let body = zip(&[("a.jsonl", "{\"id\": 1}\n"), ("b.jsonl", "{\"id\": 2}\n")]);
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Zip, Box::new(JsonlDecoder::new()));
+    assert_eq!(
+        decoder
+            .with_max_size(20)
+            .decode_bytes(&body)
+            .unwrap()
+            .records
+            .len(),
+        2
+    );
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Zip, Box::new(JsonlDecoder::new()));
+    assert!(decoder.with_max_size(19).decode_bytes(&body).is_err());
+}
+
+#[test]
+fn test_archive_invalid_body() {
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Zip, Box::new(JsonlDecoder::new()));
+    let err = decoder.decode_bytes(b"not a zip").unwrap_err();
+    assert!(err.to_string().contains("zip"));
+}
+
+// ============================================================================
+// Columnar Decoder Tests
+// ============================================================================
+
+fn parquet(batch: &arrow::record_batch::RecordBatch) -> Vec<u8> {
+    let mut writer =
+        parquet::arrow::ArrowWriter::try_new(Vec::new(), batch.schema(), None).unwrap();
+    writer.write(batch).unwrap();
+    writer.into_inner().unwrap()
+}
+
+fn avro(schema: &str, records: Vec<apache_avro::types::Value>) -> Vec<u8> {
+    let schema = apache_avro::Schema::parse_str(schema).unwrap();
+    let mut writer = apache_avro::Writer::new(&schema, Vec::new());
+    for record in records {
+        writer.append(record).unwrap();
+    }
+    writer.into_inner().unwrap()
+}
+
+fn sample_batch() -> arrow::record_batch::RecordBatch {
+    use arrow::array::{Int32Array, StringArray, TimestampMillisecondArray};
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use std::sync::Arc;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new(
+            "created_at",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+    ]);
+    arrow::record_batch::RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec![Some("Alice"), None])),
+            Arc::new(
+                TimestampMillisecondArray::from(vec![1_704_067_200_000, 1_704_153_600_000])
+                    .with_timezone("UTC"),
+            ),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_parquet_decoder_keeps_schema() {
+    let batch = sample_batch();
+    let mut page = ParquetDecoder::new()
+        .decode_bytes(&parquet(&batch))
+        .unwrap();
+
+    let batches = page.batches.as_ref().unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].schema(), batch.schema());
+    assert_eq!(batches[0].num_rows(), 2);
+
+    // The JSON view is only built on demand (cursors, transforms, pagination)
+    assert!(page.records.is_empty());
+    assert_eq!(page.record_count(), 2);
+    page.materialize().unwrap();
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.response.as_array().unwrap().len(), 2);
+    assert_eq!(page.records[0]["id"], 1);
+    assert_eq!(page.records[0]["created_at"], "2024-01-01T00:00:00Z");
+    assert_eq!(page.records[1]["name"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_parquet_decoder_invalid() {
+    let err = ParquetDecoder::new()
+        .decode_bytes(b"id,name\n1,a")
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to read Parquet"));
+}
+
+#[test]
+fn test_avro_decoder_maps_schema() {
+    use apache_avro::types::Value as Avro;
+    use arrow::datatypes::{DataType, TimeUnit};
+
+    let schema = r#"{
+        "type": "record",
+        "name": "Order",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "email", "type": ["null", "string"]},
+            {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["OPEN", "PAID"]}},
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}},
+            {"name": "placed_at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "tags", "type": {"type": "array", "items": "string"}}
+        ]
+    }"#;
+    let order = |id: i64, email: Option<&str>, cents: i64| {
+        Avro::Record(vec![
+            ("id".into(), Avro::Long(id)),
+            (
+                "email".into(),
+                match email {
+                    Some(email) => Avro::Union(1, Box::new(Avro::String(email.into()))),
+                    None => Avro::Union(0, Box::new(Avro::Null)),
+                },
+            ),
+            ("status".into(), Avro::Enum(1, "PAID".into())),
+            (
+                "amount".into(),
+                Avro::Decimal(apache_avro::Decimal::from(cents.to_be_bytes().to_vec())),
+            ),
+            ("placed_at".into(), Avro::TimestampMillis(1_704_067_200_000)),
+            ("tags".into(), Avro::Array(vec![Avro::String("vip".into())])),
+        ])
+    };
+    let body = avro(
+        schema,
+        vec![order(1, Some("a@example.com"), 1999), order(2, None, -500)],
+    );
+
+    let mut page = AvroDecoder::new().decode_bytes(&body).unwrap();
+    page.materialize().unwrap();
+    let batch = &page.batches.as_ref().unwrap()[0];
+    let schema = batch.schema();
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(
+        schema.field_with_name("id").unwrap().data_type(),
+        &DataType::Int64
+    );
+    assert!(!schema.field_with_name("id").unwrap().is_nullable());
+    assert!(schema.field_with_name("email").unwrap().is_nullable());
+    assert_eq!(
+        schema.field_with_name("amount").unwrap().data_type(),
+        &DataType::Decimal128(10, 2)
+    );
+    assert_eq!(
+        schema.field_with_name("placed_at").unwrap().data_type(),
+        &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+    );
+
+    assert_eq!(page.records[0]["email"], "a@example.com");
+    assert_eq!(page.records[0]["status"], "PAID");
+    assert_eq!(page.records[0]["amount"], 19.99);
+    assert_eq!(page.records[1]["amount"], -5.0);
+    assert_eq!(page.records[1]["email"], serde_json::Value::Null);
+    assert_eq!(page.records[1]["tags"], serde_json::json!(["vip"]));
+}
+
+#[test]
+fn test_avro_decoder_nested_types() {
+    use apache_avro::types::Value as Avro;
+    use arrow::array::{Array, AsArray, StringArray};
+    use arrow::datatypes::DataType;
+    use std::collections::HashMap;
+
+    let schema = r#"{
+        "type": "record",
+        "name": "Event",
+        "fields": [
+            {"name": "address", "type": ["null", {
+                "type": "record",
+                "name": "Address",
+                "fields": [{"name": "city", "type": "string"}]
+            }]},
+            {"name": "labels", "type": {"type": "map", "values": "long"}},
+            {"name": "value", "type": ["string", "long"]}
+        ]
+    }"#;
+    let event = |city: Option<&str>, value: Avro| {
+        Avro::Record(vec![
+            (
+                "address".into(),
+                match city {
+                    Some(city) => Avro::Union(
+                        1,
+                        Box::new(Avro::Record(vec![(
+                            "city".into(),
+                            Avro::String(city.into()),
+                        )])),
+                    ),
+                    None => Avro::Union(0, Box::new(Avro::Null)),
+                },
+            ),
+            (
+                "labels".into(),
+                Avro::Map(HashMap::from([
+                    ("b".to_string(), Avro::Long(2)),
+                    ("a".to_string(), Avro::Long(1)),
+                ])),
+            ),
+            ("value".into(), value),
+        ])
+    };
+    let body = avro(
+        schema,
+        vec![
+            event(
+                Some("Oslo"),
+                Avro::Union(0, Box::new(Avro::String("x".into()))),
+            ),
+            event(None, Avro::Union(1, Box::new(Avro::Long(7)))),
+        ],
+    );
+
+    let mut page = AvroDecoder::new().decode_bytes(&body).unwrap();
+    let batch = &page.batches.as_ref().unwrap()[0];
+    let address = batch.column_by_name("address").unwrap().as_struct();
+    assert!(address.is_valid(0));
+    assert!(address.is_null(1));
+    assert!(matches!(
+        batch.column_by_name("labels").unwrap().data_type(),
+        DataType::Map(..)
+    ));
+    // Mixed unions become strings
+    let value = batch.column_by_name("value").unwrap();
+    let value = value.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(value.value(0), "x");
+    assert_eq!(value.value(1), "7");
+
+    page.materialize().unwrap();
+    assert_eq!(page.records[0]["address"]["city"], "Oslo");
+    assert_eq!(page.records[1]["address"], serde_json::Value::Null);
+    assert_eq!(
+        page.records[0]["labels"],
+        serde_json::json!({"a": 1, "b": 2})
+    );
+}
+
+#[test]
+fn test_avro_decoder_invalid() {
+    let err = AvroDecoder::new().decode_bytes(b"{\"id\": 1}").unwrap_err();
+    assert!(err.to_string().contains("Failed to read Avro"));
+}
+
+#[test]
+fn test_archive_of_parquet_files_keeps_batches() {
+    use std::io::Write;
+
+    let batch = sample_batch();
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for name in ["part-1.parquet", "part-2.parquet"] {
+        writer
+            .start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(&parquet(&batch)).unwrap();
+    }
+    let body = writer.finish().unwrap().into_inner();
+
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Zip, Box::new(ParquetDecoder::new()));
+    let page = decoder.decode_bytes(&body).unwrap();
+    assert_eq!(page.record_count(), 4);
+    let batches = page.batches.unwrap();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[1].schema(), batch.schema());
+}
+
+// ============================================================================
+// GraphQL Decoder Tests
+// ============================================================================
+
+#[test]
+fn test_graphql_decoder_edges() {
+    let decoder = GraphqlDecoder::new("data.orders");
+    let body = r##"{"data": {"orders": {
+        "edges": [
+            {"cursor": "a", "node": {"id": "gid://1", "name": "#1001"}},
+            {"cursor": "b", "node": {"id": "gid://2", "name": "#1002"}}
+        ],
+        "pageInfo": {"hasNextPage": false, "endCursor": "b"}
+    }}}"##;
+
+    let records = decoder.decode(body).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["id"], "gid://1");
+    assert_eq!(records[1]["name"], "#1002");
+}
+
+#[test]
+fn test_graphql_decoder_nodes_and_missing() {
+    let decoder = GraphqlDecoder::new("data.repository.issues");
+    let body = r#"{"data": {"repository": {"issues": {"nodes": [{"number": 1}]}}}}"#;
+    assert_eq!(
+        decoder.decode(body).unwrap(),
+        vec![serde_json::json!({"number": 1})]
+    );
+
+    let body = r#"{"data": {"repository": null}}"#;
+    assert!(decoder.decode(body).unwrap().is_empty());
+}
+
+// ============================================================================
+// Stream Decoder Tests
+// ============================================================================
+
+#[test]
+fn test_stream_format_parse() {
+    assert_eq!(StreamFormat::parse("ndjson"), Some(StreamFormat::Ndjson));
+    assert_eq!(StreamFormat::parse("JSONL"), Some(StreamFormat::Ndjson));
+    assert_eq!(StreamFormat::parse("sse"), Some(StreamFormat::Sse));
+    assert_eq!(StreamFormat::parse("websocket"), None);
+}
+
+#[test]
+fn test_stream_ndjson_across_chunks() {
+    let decoder = StreamDecoder::new(StreamFormat::Ndjson);
+    let mut parser = decoder.stream_parser().unwrap();
+
+    // A record split mid-line is emitted once its line completes
+    assert_eq!(
+        parser.feed(b"{\"id\": 1}\n{\"id\"").unwrap(),
+        vec![serde_json::json!({"id": 1})]
+    );
+    assert_eq!(
+        parser.feed(b": 2}\r").unwrap(),
+        vec![serde_json::json!({"id": 2})]
+    );
+    // `\r\n` split across chunks is one line ending
+    assert!(parser.feed(b"\n\n").unwrap().is_empty());
+    assert!(parser.feed(b"{\"id\": 3}").unwrap().is_empty());
+    assert_eq!(parser.finish().unwrap(), vec![serde_json::json!({"id": 3})]);
+    assert!(parser.last_event_id().is_none());
+}
+
+#[test]
+fn test_stream_ndjson_error_line() {
+    let decoder = StreamDecoder::new(StreamFormat::Ndjson);
+    let err = decoder.decode("{\"id\": 1}\n\nnot json\n").unwrap_err();
+    assert!(err.to_string().contains("line 3"));
+}
+
+#[test]
+fn test_stream_sse_events() {
+    let decoder = StreamDecoder::new(StreamFormat::Sse);
+    let mut parser = decoder.stream_parser().unwrap();
+
+    let records = parser
+        .feed(b": keep-alive\n\nid: 1\ndata: {\"id\": \"a\"}\n\nid: 2\ndata: [{\"id\": \"b\"},")
+        .unwrap();
+    assert_eq!(records, vec![serde_json::json!({"id": "a"})]);
+    assert_eq!(parser.last_event_id(), Some("1"));
+
+    // Multi-line data is joined before parsing; an array yields several records
+    let records = parser.feed(b"\ndata: {\"id\": \"c\"}]\n\n").unwrap();
+    assert_eq!(
+        records,
+        vec![
+            serde_json::json!({"id": "b"}),
+            serde_json::json!({"id": "c"})
+        ]
+    );
+    assert_eq!(parser.last_event_id(), Some("2"));
+
+    // An unterminated event is discarded at the end of the stream
+    assert!(parser.feed(b"id: 3\ndata: {}").unwrap().is_empty());
+    assert!(parser.finish().unwrap().is_empty());
+    assert_eq!(parser.last_event_id(), Some("2"));
+}
+
+#[test]
+fn test_stream_sse_event_filter() {
+    let decoder = StreamDecoder::new(StreamFormat::Sse).with_events(["change"]);
+    let body = "event: change\r\nid: 7\r\ndata: {\"op\": \"insert\"}\r\n\r\n\
+                event: heartbeat\r\nid: 8\r\ndata: {}\r\n\r\n\
+                data: {\"op\": \"untyped\"}\r\n\r\n";
+    assert_eq!(
+        decoder.decode(body).unwrap(),
+        vec![serde_json::json!({"op": "insert"})]
+    );
+
+    // Events without `event:` are `message` events
+    let decoder = StreamDecoder::new(StreamFormat::Sse).with_events(["message"]);
+    assert_eq!(
+        decoder.decode(body).unwrap(),
+        vec![serde_json::json!({"op": "untyped"})]
+    );
+}
+
 // ============================================================================
 // Integration Tests
 // ============================================================================
@@ -2,7 +2,10 @@
 //!
 //! Defines the core decoder abstractions.
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use arrow::json::writer::JsonArray;
+use arrow::json::WriterBuilder;
+use arrow::record_batch::RecordBatch;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -21,6 +24,64 @@ pub enum DecoderFormat {
     Xml,
 }
 
+/// Type coercion for CSV cells and XML text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvCoercion {
+    /// Keep every value as a string (default)
+    #[default]
+    None,
+    /// Cast fields typed in the stream schema; other fields stay strings
+    Schema,
+    /// Infer numbers, booleans and nulls from each value
+    Infer,
+}
+
+impl CsvCoercion {
+    /// Parse a coercion name ("none", "schema" or "infer")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "schema" => Some(Self::Schema),
+            "infer" => Some(Self::Infer),
+            _ => None,
+        }
+    }
+}
+
+/// What a decoder does with a record it cannot read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodeErrorPolicy {
+    /// Fail the whole page (default)
+    #[default]
+    Fail,
+    /// Set the record aside as rejected and keep decoding
+    Quarantine,
+}
+
+impl DecodeErrorPolicy {
+    /// Parse a policy name ("fail" or "quarantine")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fail" => Some(Self::Fail),
+            "quarantine" => Some(Self::Quarantine),
+            _ => None,
+        }
+    }
+}
+
+/// A record a decoder could not read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRecord {
+    /// Raw text of the record
+    pub raw: String,
+    /// Why the record was rejected
+    pub error: String,
+    /// Line where the record starts (1-based)
+    pub line: u64,
+    /// File inside an archive holding the record
+    pub file: Option<String>,
+}
+
 /// Configuration for decoding responses
 #[derive(Debug, Clone, Default)]
 pub struct DecoderConfig {
@@ -99,6 +160,77 @@ impl DecoderConfig {
     }
 }
 
+/// A decoded response page
+///
+/// Holds the page's records and the structured view of the whole response
+/// that paginators and stop conditions read (e.g., a `next` cursor).
+/// Columnar decoders also return the page as Arrow batches in the source
+/// schema, which the engine emits without converting records back to Arrow;
+/// their JSON records and response are only built by [`Self::materialize`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodedPage {
+    /// Records extracted from the page
+    pub records: Vec<Value>,
+    /// Full response as JSON (pagination view)
+    pub response: Value,
+    /// Records as Arrow batches in the source schema (Parquet, Avro)
+    pub batches: Option<Vec<RecordBatch>>,
+    /// Records set aside under [`DecodeErrorPolicy::Quarantine`]
+    pub rejected: Vec<RejectedRecord>,
+}
+
+impl DecodedPage {
+    /// Build a page from Arrow batches
+    ///
+    /// Records and the response view are left empty until [`Self::materialize`].
+    pub fn from_batches(batches: Vec<RecordBatch>) -> Self {
+        Self {
+            records: Vec::new(),
+            response: Value::Null,
+            batches: Some(batches),
+            rejected: Vec::new(),
+        }
+    }
+
+    /// Number of records on the page (Arrow rows for columnar pages)
+    pub fn record_count(&self) -> usize {
+        match &self.batches {
+            Some(batches) => batches.iter().map(RecordBatch::num_rows).sum(),
+            None => self.records.len(),
+        }
+    }
+
+    /// Build the JSON records and response view of a columnar page
+    ///
+    /// Records are the batches' rows as JSON (timestamps and dates as ISO
+    /// strings); the response view is the array of records. Pages that
+    /// already have JSON records are left as they are.
+    pub fn materialize(&mut self) -> Result<()> {
+        let Some(batches) = &self.batches else {
+            return Ok(());
+        };
+        if !self.records.is_empty() || self.record_count() == 0 {
+            return Ok(());
+        }
+
+        let mut writer = WriterBuilder::new()
+            .with_explicit_nulls(true)
+            .build::<_, JsonArray>(Vec::new());
+        for batch in batches {
+            writer.write(batch).map_err(|e| Error::Decode {
+                message: format!("Failed to convert Arrow batch to JSON: {e}"),
+            })?;
+        }
+        writer.finish().map_err(|e| Error::Decode {
+            message: format!("Failed to convert Arrow batch to JSON: {e}"),
+        })?;
+
+        self.records = serde_json::from_slice(&writer.into_inner())?;
+        self.response = Value::Array(self.records.clone());
+        Ok(())
+    }
+}
+
 /// Trait for decoding response bodies into records
 pub trait RecordDecoder: Send + Sync {
     /// Decode the response body into a list of records
@@ -106,4 +238,61 @@ pub trait RecordDecoder: Send + Sync {
 
     /// Decode the response body into a single JSON value (full response)
     fn decode_raw(&self, body: &str) -> Result<Value>;
+
+    /// Decode a response page into its records and pagination view
+    ///
+    /// The default decodes the body twice; decoders override this to parse once.
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        Ok(DecodedPage {
+            records: self.decode(body)?,
+            response: self.decode_raw(body)?,
+            batches: None,
+            rejected: Vec::new(),
+        })
+    }
+
+    /// Decode a raw response body (e.g., a compressed download)
+    ///
+    /// The default reads the body as UTF-8 text, replacing invalid sequences.
+    /// Columnar pages come back without JSON records; call
+    /// [`DecodedPage::materialize`] before reading them.
+    fn decode_bytes(&self, body: &[u8]) -> Result<DecodedPage> {
+        self.decode_page(&String::from_utf8_lossy(body))
+    }
+
+    /// Incremental parser for streaming responses (NDJSON, Server-Sent Events)
+    ///
+    /// When a decoder returns a parser, the engine reads the response body as
+    /// it arrives instead of buffering it, and does not paginate.
+    fn stream_parser(&self) -> Option<Box<dyn StreamParser>> {
+        None
+    }
+
+    /// Incremental parser for paginated responses (CSV)
+    ///
+    /// When a decoder returns a parser, the engine feeds it each page's body
+    /// as it arrives instead of buffering the body, then paginates on the
+    /// parsed records as usual.
+    fn page_parser(&self) -> Option<Box<dyn StreamParser>> {
+        None
+    }
+}
+
+/// Incremental parser fed a streaming response body chunk by chunk
+pub trait StreamParser: Send {
+    /// Feed a chunk of the body, returning the records it completes
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Value>>;
+
+    /// Flush records left when the body ends
+    fn finish(&mut self) -> Result<Vec<Value>>;
+
+    /// ID of the last complete event (SSE `id:`), used to resume the stream
+    fn last_event_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Records set aside since the last call (quarantined rows)
+    fn take_rejected(&mut self) -> Vec<RejectedRecord> {
+        Vec::new()
+    }
 }
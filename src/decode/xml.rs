@@ -0,0 +1,473 @@
+//! XML decoder
+//!
+//! Converts XML documents to JSON and selects record elements.
+//!
+//! # XML to JSON mapping
+//!
+//! - An element with only text becomes a string; an empty element is `null`
+//! - Attributes become keys prefixed with `@` (`<item id="1">` → `"@id": "1"`)
+//! - Text next to attributes or child elements goes under `#text`
+//! - CDATA sections are text, kept as strings
+//! - Repeated child elements become arrays, in document order
+//! - Namespace prefixes are dropped (`<soap:Body>` → `"Body"`) unless kept,
+//!   and `xmlns` declarations are skipped
+//!
+//! The JSON view of a document is its root element's value, without the
+//! root element name.
+//!
+//! Text and attribute values stay strings unless a [`CsvCoercion`] is set:
+//! `Infer` parses numbers and booleans everywhere (zero-padded numbers stay
+//! strings), `Schema` casts the record fields typed in the stream schema.
+//!
+//! # Record selection
+//!
+//! Record paths are either JSONPaths relative to the root element
+//! (`items.item`) or XPath-like paths:
+//!
+//! - `/Envelope/Body/list/record` — absolute, starting at the root element
+//! - `//record` — `record` elements at any depth
+//! - `*` matches any element, `[@type='invoice']` or `[status='open']`
+//!   filters on an attribute or child value
+
+use super::decoders::{cast_csv_value, parse_text_value};
+use super::types::{CsvCoercion, DecodedPage, RecordDecoder};
+use crate::error::{Error, Result};
+use crate::jsonpath::JsonPath;
+use crate::schema::{JsonSchema, JsonType};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// XML decoder with record element selection
+#[derive(Debug, Clone, Default)]
+pub struct XmlDecoder {
+    /// Record path (dot path or XPath-like)
+    record_element: Option<String>,
+    /// Keep namespace prefixes in element and attribute names
+    keep_prefixes: bool,
+    /// Text and attribute type coercion
+    coercion: CsvCoercion,
+    /// Record field types from the stream schema (for `CsvCoercion::Schema`)
+    field_types: HashMap<String, JsonType>,
+}
+
+impl XmlDecoder {
+    /// Create a new XML decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an XML decoder with a record path (e.g., `items.item` or `//item`)
+    pub fn with_element(element: impl Into<String>) -> Self {
+        Self {
+            record_element: Some(element.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Keep namespace prefixes (`soap:Body`) instead of local names
+    #[must_use]
+    pub fn with_prefixes(mut self, keep: bool) -> Self {
+        self.keep_prefixes = keep;
+        self
+    }
+
+    /// Set text and attribute type coercion
+    #[must_use]
+    pub fn with_coercion(mut self, coercion: CsvCoercion) -> Self {
+        self.coercion = coercion;
+        self
+    }
+
+    /// Take record field types from a stream schema's properties
+    #[must_use]
+    pub fn with_schema(mut self, schema: &JsonSchema) -> Self {
+        self.field_types = schema
+            .properties
+            .iter()
+            .filter_map(|(name, prop)| Some((name.clone(), prop.json_type.primary_type()?.clone())))
+            .collect();
+        self
+    }
+
+    /// Parse a document into its root element name and value
+    fn parse(&self, body: &str) -> Result<(String, Value)> {
+        xml_to_json(
+            body,
+            self.keep_prefixes,
+            self.coercion == CsvCoercion::Infer,
+        )
+    }
+
+    /// Select records from a parsed document, casting schema-typed fields
+    fn records(&self, root_name: &str, root: &Value) -> Result<Vec<Value>> {
+        let mut records = self.extract_records(root_name, root)?;
+        if self.coercion == CsvCoercion::Schema {
+            for record in &mut records {
+                let Value::Object(fields) = record else {
+                    continue;
+                };
+                for (name, value) in fields.iter_mut() {
+                    if let (Value::String(text), Some(json_type)) =
+                        (&*value, self.field_types.get(name))
+                    {
+                        *value = cast_csv_value(text, json_type);
+                    }
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Select records from a parsed document
+    fn extract_records(&self, root_name: &str, root: &Value) -> Result<Vec<Value>> {
+        let Some(path) = &self.record_element else {
+            // Return entire parsed result
+            return Ok(match root {
+                Value::Array(arr) => arr.clone(),
+                v => vec![v.clone()],
+            });
+        };
+
+        if path.starts_with('/') {
+            let mut document = Map::new();
+            document.insert(root_name.to_string(), root.clone());
+            return select(&Value::Object(document), path);
+        }
+
+        Ok(JsonPath::parse(path)?.records(root))
+    }
+}
+
+impl RecordDecoder for XmlDecoder {
+    fn decode(&self, body: &str) -> Result<Vec<Value>> {
+        let (root_name, root) = self.parse(body)?;
+        self.records(&root_name, &root)
+    }
+
+    fn decode_raw(&self, body: &str) -> Result<Value> {
+        Ok(self.parse(body)?.1)
+    }
+
+    fn decode_page(&self, body: &str) -> Result<DecodedPage> {
+        let (root_name, response) = self.parse(body)?;
+        Ok(DecodedPage {
+            records: self.records(&root_name, &response)?,
+            response,
+            batches: None,
+            rejected: Vec::new(),
+        })
+    }
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+/// Element being built while its content is read
+struct Element {
+    /// Element name
+    name: String,
+    /// Attributes and child elements
+    fields: Map<String, Value>,
+    /// Text content
+    text: String,
+    /// Whether the text came from CDATA
+    cdata: bool,
+    /// Parse numbers and booleans in text and attributes
+    infer: bool,
+}
+
+impl Element {
+    /// Start an element, reading its attributes
+    fn start(start: &BytesStart<'_>, keep_prefixes: bool, infer: bool) -> Result<Self> {
+        let mut fields = Map::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(|e| xml_error(&e))?;
+            let key = attr.key;
+            if key.as_namespace_binding().is_some() {
+                continue;
+            }
+            let name = if keep_prefixes {
+                utf8(key.as_ref())?
+            } else {
+                utf8(key.local_name().as_ref())?
+            };
+            let value = attr.unescape_value().map_err(|e| xml_error(&e))?;
+            fields.insert(format!("@{name}"), text_value(value.trim(), infer));
+        }
+
+        let name = if keep_prefixes {
+            utf8(start.name().as_ref())?
+        } else {
+            utf8(start.local_name().as_ref())?
+        };
+        Ok(Self {
+            name,
+            fields,
+            text: String::new(),
+            cdata: false,
+            infer,
+        })
+    }
+
+    /// Finish the element into its JSON value
+    fn finish(mut self) -> (String, Value) {
+        let text = self.text.trim();
+        let text_value = text_value(text, self.infer && !self.cdata);
+
+        let value = if self.fields.is_empty() {
+            if text.is_empty() {
+                Value::Null
+            } else {
+                text_value
+            }
+        } else {
+            if !text.is_empty() {
+                self.fields.insert("#text".to_string(), text_value);
+            }
+            Value::Object(self.fields)
+        };
+        (self.name, value)
+    }
+
+    /// Add a child element (repeated children become an array)
+    fn add_child(&mut self, name: String, value: Value) {
+        match self.fields.get_mut(&name) {
+            Some(Value::Array(arr)) => arr.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                self.fields.insert(name, value);
+            }
+        }
+    }
+}
+
+/// Convert text or an attribute value to JSON, inferring its type if asked
+fn text_value(text: &str, infer: bool) -> Value {
+    if infer {
+        parse_text_value(text)
+    } else {
+        Value::String(text.to_string())
+    }
+}
+
+/// Convert an XML document to JSON, returning the root element name and value
+fn xml_to_json(xml: &str, keep_prefixes: bool, infer: bool) -> Result<(String, Value)> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| xml_error(&format!("{e} at position {}", reader.buffer_position())))?;
+        let finished = match event {
+            Event::Start(start) => {
+                stack.push(Element::start(&start, keep_prefixes, infer)?);
+                None
+            }
+            Event::Empty(start) => Some(Element::start(&start, keep_prefixes, infer)?.finish()),
+            Event::End(_) => stack.pop().map(Element::finish),
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|e| xml_error(&e))?;
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&text),
+                    None if !text.trim().is_empty() => {
+                        return Err(xml_error(&"Text outside of the root element"));
+                    }
+                    None => {}
+                }
+                None
+            }
+            Event::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&utf8(&data.into_inner())?);
+                    element.cdata = true;
+                }
+                None
+            }
+            Event::Eof => break,
+            _ => None,
+        };
+
+        if let Some((name, value)) = finished {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(name, value),
+                None if root.is_none() => root = Some((name, value)),
+                None => return Err(xml_error(&"Multiple root elements")),
+            }
+        }
+    }
+
+    if let Some(element) = stack.last() {
+        return Err(xml_error(&format!(
+            "Unexpected end of document: <{}> is not closed",
+            element.name
+        )));
+    }
+    root.ok_or_else(|| xml_error(&"Input does not appear to be XML"))
+}
+
+/// Decode UTF-8 bytes from the document
+fn utf8(bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| xml_error(&e))
+}
+
+/// Build an XML parse error
+fn xml_error(e: &dyn std::fmt::Display) -> Error {
+    Error::XmlParse {
+        message: e.to_string(),
+    }
+}
+
+// ============================================================================
+// Selection
+// ============================================================================
+
+/// One step of an XPath-like path
+#[derive(Debug, PartialEq)]
+struct Step {
+    /// Match at any depth (`//name`)
+    descendant: bool,
+    /// Element name (`*` matches any)
+    name: String,
+    /// Attribute (`@name`) or child compared with a value
+    predicate: Option<(String, String)>,
+}
+
+/// Parse an XPath-like path into steps
+fn parse_steps(path: &str) -> Result<Vec<Step>> {
+    let invalid = || xml_error(&format!("Invalid XML record path '{path}'"));
+    let mut steps = Vec::new();
+    let mut descendant = false;
+
+    for part in path.split('/').skip(1) {
+        if part.is_empty() {
+            descendant = true;
+            continue;
+        }
+        let (name, predicate) = match part.split_once('[') {
+            Some((name, rest)) => {
+                let condition = rest.strip_suffix(']').ok_or_else(invalid)?;
+                let (key, value) = condition.split_once('=').ok_or_else(invalid)?;
+                let value = value.trim().trim_matches(|c| c == '\'' || c == '"');
+                (name, Some((key.trim().to_string(), value.to_string())))
+            }
+            None => (part, None),
+        };
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        steps.push(Step {
+            descendant,
+            name: name.to_string(),
+            predicate,
+        });
+        descendant = false;
+    }
+
+    if descendant || steps.is_empty() {
+        return Err(invalid());
+    }
+    Ok(steps)
+}
+
+/// Select elements with an XPath-like path from a document (`{root: value}`)
+fn select(document: &Value, path: &str) -> Result<Vec<Value>> {
+    let mut nodes = vec![document];
+    for step in parse_steps(path)? {
+        let mut matched = Vec::new();
+        for node in nodes {
+            if step.descendant {
+                descendants(node, &step, &mut matched);
+            } else {
+                children(node, &step, &mut matched);
+            }
+        }
+        nodes = matched;
+    }
+    Ok(nodes.into_iter().cloned().collect())
+}
+
+/// Collect the child elements of a node matching a step
+fn children<'a>(node: &'a Value, step: &Step, out: &mut Vec<&'a Value>) {
+    let Value::Object(fields) = node else {
+        return;
+    };
+    for (name, value) in fields {
+        if name.starts_with('@') || name == "#text" || (step.name != "*" && *name != step.name) {
+            continue;
+        }
+        let elements = match value {
+            Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        out.extend(elements.into_iter().filter(|e| matches_predicate(e, step)));
+    }
+}
+
+/// Collect matching elements at any depth below a node
+fn descendants<'a>(node: &'a Value, step: &Step, out: &mut Vec<&'a Value>) {
+    let Value::Object(fields) = node else {
+        return;
+    };
+    children(node, step, out);
+    for (name, value) in fields {
+        if name.starts_with('@') {
+            continue;
+        }
+        match value {
+            Value::Array(items) => items.iter().for_each(|item| descendants(item, step, out)),
+            value => descendants(value, step, out),
+        }
+    }
+}
+
+/// Check an element against a step's predicate
+fn matches_predicate(element: &Value, step: &Step) -> bool {
+    let Some((key, expected)) = &step.predicate else {
+        return true;
+    };
+    match element.get(key) {
+        Some(Value::String(s)) => s == expected,
+        Some(Value::Object(obj)) => obj
+            .get("#text")
+            .is_some_and(|text| text_of(text) == *expected),
+        Some(value) => text_of(value) == *expected,
+        None => false,
+    }
+}
+
+/// Text form of a scalar value
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_steps() {
+        let steps = parse_steps("/a//b/*[@type='x']").unwrap();
+        assert_eq!(steps.len(), 3);
+        assert!(!steps[0].descendant);
+        assert!(steps[1].descendant);
+        assert_eq!(steps[2].name, "*");
+        assert_eq!(
+            steps[2].predicate,
+            Some(("@type".to_string(), "x".to_string()))
+        );
+
+        assert!(parse_steps("/a/").is_err());
+        assert!(parse_steps("/a[@x]").is_err());
+    }
+}
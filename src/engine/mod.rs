@@ -11,19 +11,31 @@
 
 mod types;
 
-pub use types::{LogLevel, Message, SyncConfig, SyncStats};
+pub use types::{LimitPolicy, LogLevel, Message, PaginationLimits, SyncConfig, SyncStats};
 
-use crate::decode::RecordDecoder;
+use crate::auth::Authenticator;
+use crate::decode::{DecodedPage, JsonDecoder, RecordDecoder, RejectedRecord, StreamParser};
 use crate::error::Result;
-use crate::http::{HttpClient, RequestConfig};
+use crate::http::{GraphqlRequest, HttpClient, RequestConfig};
 use crate::output::json_to_arrow;
-use crate::pagination::{NextPage, PaginationState, Paginator};
-use crate::partition::PartitionRouter;
-use crate::state::StateManager;
+use crate::pagination::{
+    extract_jsonpath_value, set_body_field, NextPage, NoPaginator, PaginationState, Paginator,
+};
+use crate::partition::{
+    AdaptiveWindows, AsyncJob, AsyncJobConfig, AsyncJobState, DateWindow, PartitionRouter,
+};
+use crate::secrets::redact;
+use crate::state::{DateSlice, StateManager};
 use crate::template::{self, TemplateContext};
-use reqwest::header::HeaderMap;
-use std::collections::HashMap;
+use crate::transform::TransformPipeline;
+use chrono::DateTime;
+use reqwest::Method;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::mpsc;
 
 /// Sync engine for orchestrating data extraction
 pub struct SyncEngine {
@@ -35,6 +47,29 @@ pub struct SyncEngine {
     config: SyncConfig,
     /// Statistics
     stats: SyncStats,
+    /// HTTP method for stream requests
+    method: Method,
+    /// Request body template for stream requests (JSON)
+    body: Option<String>,
+    /// GraphQL query for stream requests (replaces `body`)
+    graphql: Option<GraphqlRequest>,
+    /// Pagination safeguards for stream requests
+    limits: PaginationLimits,
+    /// Record transformations for stream requests
+    transforms: TransformPipeline,
+    /// Channel that receives streamed response messages as they are produced
+    output: Option<mpsc::Sender<Message>>,
+}
+
+/// A pagination safeguard triggered during a sync
+#[derive(Debug, Clone, Copy)]
+enum PaginationEvent {
+    /// The stream reached its page limit
+    MaxPages(usize),
+    /// The next request repeats an earlier one
+    RepeatedCursor,
+    /// The response repeats an earlier page
+    RepeatedPage,
 }
 
 impl SyncEngine {
@@ -45,6 +80,12 @@ impl SyncEngine {
             state,
             config: SyncConfig::default(),
             stats: SyncStats::default(),
+            method: Method::GET,
+            body: None,
+            graphql: None,
+            limits: PaginationLimits::default(),
+            transforms: TransformPipeline::default(),
+            output: None,
         }
     }
 
@@ -70,6 +111,48 @@ impl SyncEngine {
         &self.stats
     }
 
+    /// Set the authenticator used for subsequent requests (e.g., per stream auth profile)
+    pub fn set_authenticator(&mut self, authenticator: Option<Arc<Authenticator>>) {
+        self.client.set_shared_authenticator(authenticator);
+    }
+
+    /// Authenticator used for subsequent requests
+    pub fn authenticator(&self) -> Option<Arc<Authenticator>> {
+        self.client.authenticator().cloned()
+    }
+
+    /// Set the HTTP method and JSON body template used for subsequent stream requests
+    pub fn set_request(&mut self, method: Method, body: Option<String>) {
+        self.method = method;
+        self.body = body;
+    }
+
+    /// Set the GraphQL query used for subsequent stream requests
+    pub fn set_graphql(&mut self, graphql: Option<GraphqlRequest>) {
+        self.graphql = graphql;
+    }
+
+    /// Set the pagination safeguards used for subsequent stream requests
+    pub fn set_pagination_limits(&mut self, limits: PaginationLimits) {
+        self.limits = limits;
+    }
+
+    /// Set the record transformations applied to subsequently synced streams
+    pub fn set_transformations(&mut self, transforms: TransformPipeline) {
+        self.transforms = transforms;
+    }
+
+    /// Set the channel that receives streamed response messages
+    ///
+    /// Without one, streamed records are returned with the other messages
+    /// once the sync completes. With one, record batches and event ID
+    /// checkpoints are sent as they are produced; the receiver commits each
+    /// checkpoint (see [`Message::event_checkpoint`]) after writing the
+    /// records before it.
+    pub fn set_output(&mut self, output: Option<mpsc::Sender<Message>>) {
+        self.output = output;
+    }
+
     /// Sync a single stream without partitioning
     #[allow(clippy::too_many_arguments)]
     pub async fn sync_stream(
@@ -83,98 +166,367 @@ impl SyncEngine {
         paginator: &dyn Paginator,
         context: &TemplateContext,
         cursor_field: Option<&str>,
+    ) -> Result<Vec<Message>> {
+        self.sync_pages(
+            stream_name,
+            None,
+            url,
+            path,
+            query_params,
+            headers,
+            decoder,
+            paginator,
+            context,
+            cursor_field,
+            None,
+        )
+        .await
+    }
+
+    /// Fetch all pages of a stream and return its records (e.g., parent records)
+    ///
+    /// Records are not emitted or counted as synced, and `{{ state.cursor }}`
+    /// is left empty so the full stream is read.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn collect_records(
+        &mut self,
+        stream_name: &str,
+        url: &str,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        paginator: &dyn Paginator,
+        context: &TemplateContext,
+    ) -> Result<Vec<serde_json::Value>> {
+        let mut records = Vec::new();
+        self.sync_pages(
+            stream_name,
+            None,
+            url,
+            path,
+            query_params,
+            headers,
+            decoder,
+            paginator,
+            context,
+            None,
+            Some(&mut records),
+        )
+        .await?;
+        Ok(records)
+    }
+
+    /// Sync all pages of a stream or of one of its partitions
+    ///
+    /// The stored cursor (stream or partition level) is available to templates
+    /// as `{{ state.cursor }}`; the highest `cursor_field` value seen is saved
+    /// back when the sync completes. With a `sink`, records are collected
+    /// there instead of being emitted, and nothing is sent to the output
+    /// channel.
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_pages(
+        &mut self,
+        stream_name: &str,
+        partition_id: Option<&str>,
+        url: &str,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        paginator: &dyn Paginator,
+        context: &TemplateContext,
+        cursor_field: Option<&str>,
+        mut sink: Option<&mut Vec<serde_json::Value>>,
     ) -> Result<Vec<Message>> {
         let start = Instant::now();
         let mut messages = Vec::new();
 
+        let previous_cursor = match partition_id {
+            _ if sink.is_some() => None,
+            Some(id) => self.state.get_partition_cursor(stream_name, id).await,
+            None => self.state.get_cursor(stream_name).await,
+        };
+        let mut context = context.clone();
+        context.set_state(serde_json::json!({ "cursor": previous_cursor }));
+        let context = &context;
+        let mut max_cursor = previous_cursor;
+
         messages.push(Message::info(format!(
             "Starting sync for stream: {stream_name}"
         )));
 
         let mut all_records = Vec::new();
+        let mut columnar_rows = 0;
         let mut page_count = 0;
         let mut pagination_state = PaginationState::new();
 
+        let mut next_url: Option<String> = None;
+        let mut seen_requests = HashSet::new();
+        let mut seen_pages = HashSet::new();
+
+        // Streaming decoders read one long-lived response and resume after
+        // the last checkpointed event
+        let mut stream_parser = decoder.stream_parser();
+        let last_event_id = match stream_parser {
+            Some(_) if sink.is_none() => self.state.get_event_id(stream_name, partition_id).await,
+            _ => None,
+        };
+
         loop {
-            // Build URL with template
-            let rendered_path = template::render(path, context)?;
-            let full_url = format!("{}{}", url.trim_end_matches('/'), rendered_path);
+            if self.limits.max_pages > 0 && page_count >= self.limits.max_pages {
+                self.pagination_event(
+                    stream_name,
+                    PaginationEvent::MaxPages(self.limits.max_pages),
+                    &mut messages,
+                )?;
+                break;
+            }
+            if self.limits.detect_loops
+                && !seen_requests.insert(Self::request_fingerprint(
+                    paginator,
+                    &pagination_state,
+                    next_url.as_deref(),
+                ))
+            {
+                self.pagination_event(stream_name, PaginationEvent::RepeatedCursor, &mut messages)?;
+                break;
+            }
 
-            // Build request config with query params
             let mut req_config = RequestConfig::new();
 
-            // Add template-rendered params
-            for (key, value) in query_params {
-                let rendered = template::render(value, context)?;
-                if !rendered.is_empty() {
-                    req_config = req_config.query(key, &rendered);
+            let full_url = if let Some(next) = next_url.take() {
+                // Next page URL from the response already carries its query string
+                next
+            } else {
+                // Build URL with template
+                let rendered_path = template::render(path, context)?;
+
+                // Add template-rendered params
+                for (key, value) in query_params {
+                    let rendered = template::render(value, context)?;
+                    if !rendered.is_empty() {
+                        req_config = req_config.query(key, &rendered);
+                    }
                 }
-            }
 
-            // Add pagination params
-            let pagination_params = paginator.initial_params(&pagination_state);
-            for (key, value) in pagination_params {
-                req_config = req_config.query(&key, &value);
-            }
+                // Add pagination params
+                for (key, value) in paginator.initial_params(&pagination_state) {
+                    req_config = req_config.query(&key, &value);
+                }
+
+                format!("{}{}", url.trim_end_matches('/'), rendered_path)
+            };
 
             // Add headers
             for (key, value) in headers {
                 req_config = req_config.header(key, value);
             }
+            for (key, value) in paginator.request_headers(&pagination_state) {
+                req_config = req_config.header(key, value);
+            }
+            if let Some(event_id) = &last_event_id {
+                req_config = req_config.header("Last-Event-ID", event_id);
+            }
+
+            // Build JSON body from template and pagination fields
+            let body_fields = paginator.request_body_fields(&pagination_state);
+            let mut body = match (&self.graphql, &self.body) {
+                (Some(graphql), _) => Some(graphql.render_body(context)?),
+                (None, Some(body)) => {
+                    Some(serde_json::from_str(&template::render(body, context)?)?)
+                }
+                (None, None) if !body_fields.is_empty() => Some(serde_json::Value::Null),
+                (None, None) => None,
+            };
+            if let Some(body) = body.as_mut() {
+                for (key, value) in body_fields {
+                    set_body_field(body, &key, value);
+                }
+            }
+            if let Some(body) = body {
+                req_config = req_config.json(body);
+            }
 
             // Make request
-            let response = self.client.get_with_config(&full_url, req_config).await?;
+            let response = self
+                .client
+                .request(self.method.clone(), &full_url, req_config)
+                .await?;
 
             page_count += 1;
             self.stats.add_page();
 
-            // Get response body as JSON
-            let body_text = response.text().await.map_err(|e| {
-                crate::error::Error::decode(format!("Failed to read response body: {e}"))
-            })?;
-            let response_json: serde_json::Value = serde_json::from_str(&body_text)?;
+            // A streamed response is the whole stream; it is not paginated
+            if let Some(parser) = stream_parser.take() {
+                self.stream_response(
+                    stream_name,
+                    partition_id,
+                    response,
+                    parser,
+                    context,
+                    cursor_field,
+                    &mut max_cursor,
+                    sink.as_deref_mut(),
+                    &mut messages,
+                )
+                .await?;
+                break;
+            }
+
+            // Decode records and the response view used for pagination;
+            // columnar pages only convert to JSON when something reads it.
+            // Incremental decoders parse the body as it arrives.
+            let response_headers = response.headers().clone();
+            let mut hasher = DefaultHasher::new();
+            let mut page = if let Some(parser) = decoder.page_parser() {
+                Self::parse_page(response, parser, &mut hasher).await?
+            } else {
+                let body = response.bytes().await.map_err(|e| {
+                    crate::error::Error::decode(format!("Failed to read response body: {e}"))
+                })?;
+                body.hash(&mut hasher);
+                decoder.decode_bytes(&body)?
+            };
+            let repeated_page = self.limits.detect_loops && !seen_pages.insert(hasher.finish());
+            let record_count = page.record_count();
+            if sink.is_some()
+                || cursor_field.is_some()
+                || !self.transforms.is_empty()
+                || paginator.reads_response()
+            {
+                page.materialize()?;
+            }
+            let DecodedPage {
+                records,
+                response: response_json,
+                batches,
+                rejected,
+            } = page;
+
+            // A repeated page is not emitted again; the paginator still sees it
+            // first so its own failures (e.g., a non-advancing key) are reported
+            if repeated_page {
+                paginator.observe_records(&records, &mut pagination_state);
+                let next_page = paginator.process_response(
+                    &response_json,
+                    &response_headers,
+                    record_count,
+                    &mut pagination_state,
+                );
+                if let NextPage::Failed { reason } = next_page {
+                    return Err(crate::error::Error::pagination(stream_name, reason));
+                }
+                self.pagination_event(stream_name, PaginationEvent::RepeatedPage, &mut messages)?;
+                break;
+            }
 
-            // Decode records
-            let records = decoder.decode(&body_text)?;
-            let record_count = records.len();
-            self.stats.add_records(record_count);
+            if !rejected.is_empty() {
+                self.quarantine(
+                    stream_name,
+                    partition_id,
+                    page_count,
+                    rejected,
+                    sink.is_none(),
+                    &mut messages,
+                )
+                .await?;
+            }
+
+            if let Some(graphql) = &self.graphql {
+                GraphqlRequest::check_errors(&response_json)?;
+                if let Some(delay) = graphql.throttle_delay(&response_json) {
+                    self.client.rate_limiter().pause(delay);
+                }
+            }
+
+            // Transformations shape emitted records; pagination, cursors and
+            // parent records keep reading the decoded ones
+            let (transformed, batches) = if sink.is_some() || self.transforms.is_empty() {
+                (None, batches)
+            } else {
+                (Some(self.transforms.apply(&records, context)?), None)
+            };
+            if sink.is_none() {
+                self.stats
+                    .add_records(transformed.as_ref().map_or(record_count, Vec::len));
+            }
 
             messages.push(Message::debug(format!(
                 "Page {page_count}: fetched {record_count} records"
             )));
 
+            // Track the highest cursor value across pages (batches drain all_records)
+            if let Some(field) = cursor_field {
+                if let Some(page_max) = self.extract_max_cursor(&records, field) {
+                    if max_cursor.as_ref().is_none_or(|max| page_max > *max) {
+                        max_cursor = Some(page_max);
+                    }
+                }
+            }
+
             // Note: Don't call pagination_state.add_fetched here - process_response handles it
-            all_records.extend(records.clone());
+            if let Some(sink) = sink.as_deref_mut() {
+                sink.extend(records.iter().cloned());
+            } else if let Some(batches) = batches {
+                // Columnar pages are emitted as decoded, keeping the source schema
+                if !all_records.is_empty() {
+                    let batch = json_to_arrow(&std::mem::take(&mut all_records), None)?;
+                    messages.push(Message::record(stream_name, batch));
+                }
+                for batch in batches {
+                    let rows = if self.config.max_records > 0 {
+                        batch
+                            .num_rows()
+                            .min(self.config.max_records.saturating_sub(columnar_rows))
+                    } else {
+                        batch.num_rows()
+                    };
+                    if rows > 0 {
+                        columnar_rows += rows;
+                        messages.push(Message::record(stream_name, batch.slice(0, rows)));
+                    }
+                }
+            } else if let Some(transformed) = transformed {
+                all_records.extend(transformed);
+            } else {
+                all_records.extend(records.clone());
+            }
 
             // Check max records limit
-            if self.config.max_records > 0 && all_records.len() >= self.config.max_records {
-                all_records.truncate(self.config.max_records);
+            if self.config.max_records > 0
+                && all_records.len() + columnar_rows >= self.config.max_records
+            {
+                all_records.truncate(self.config.max_records.saturating_sub(columnar_rows));
                 // Correct the stats to reflect truncated count
                 let overcounted = self.stats.records_synced - self.config.max_records;
                 self.stats.records_synced -= overcounted;
                 break;
             }
 
-            // Process pagination (use empty HeaderMap for now)
-            let empty_headers = HeaderMap::new();
+            // Process pagination
+            paginator.observe_records(&records, &mut pagination_state);
             let next_page = paginator.process_response(
                 &response_json,
-                &empty_headers,
+                &response_headers,
                 record_count,
                 &mut pagination_state,
             );
 
             match next_page {
-                NextPage::Continue { .. } => {
-                    // Continue to next page
+                NextPage::Continue { url, .. } => {
+                    next_url = url;
                 }
                 NextPage::Done => {
                     break;
                 }
+                NextPage::Failed { reason } => {
+                    return Err(crate::error::Error::pagination(stream_name, reason));
+                }
             }
 
             // Emit state per page if configured
-            if self.config.emit_state_per_page {
+            if self.config.emit_state_per_page && sink.is_none() {
                 if let Some(cursor) = &pagination_state.cursor {
                     self.state.set_cursor(stream_name, cursor.clone()).await?;
                     messages.push(Message::state(
@@ -198,9 +550,17 @@ impl SyncEngine {
             messages.push(Message::record(stream_name, batch));
         }
 
-        // Extract and save cursor from records if cursor_field is specified
-        if let Some(field) = cursor_field {
-            if let Some(max_cursor) = self.extract_max_cursor(&all_records, field) {
+        // Save the cursor if cursor_field is specified
+        if let (Some(_), Some(max_cursor)) = (cursor_field, max_cursor) {
+            if let Some(id) = partition_id {
+                self.state
+                    .set_partition_cursor(stream_name, id, max_cursor.clone())
+                    .await?;
+                messages.push(Message::state(
+                    stream_name,
+                    serde_json::json!({ "partition": id, "cursor": max_cursor }),
+                ));
+            } else {
                 self.state
                     .set_cursor(stream_name, max_cursor.clone())
                     .await?;
@@ -211,7 +571,9 @@ impl SyncEngine {
             }
         }
 
-        self.stats.add_stream();
+        if sink.is_none() {
+            self.stats.add_stream();
+        }
         #[allow(clippy::cast_possible_truncation)]
         self.stats.set_duration(start.elapsed().as_millis() as u64);
 
@@ -223,23 +585,416 @@ impl SyncEngine {
         Ok(messages)
     }
 
+    /// Emit the records a decoder rejected and enforce the decode error budget
+    ///
+    /// Rejected records go to a `<stream>_rejected` stream with their raw
+    /// text, error and location, so they land beside the stream's output.
+    /// With `emit`, they are sent to the output channel when one is set, so
+    /// the rows are written even when they exhaust the budget and fail the
+    /// sync; otherwise they are only returned with the sync's messages.
+    async fn quarantine(
+        &mut self,
+        stream_name: &str,
+        partition_id: Option<&str>,
+        page: usize,
+        rejected: Vec<RejectedRecord>,
+        emit: bool,
+        messages: &mut Vec<Message>,
+    ) -> Result<()> {
+        self.stats.add_rejected(rejected.len());
+        let warning = Message::warn(format!(
+            "Page {page} of {stream_name}: quarantined {} records that failed to decode",
+            rejected.len()
+        ));
+        let rows: Vec<_> = rejected
+            .into_iter()
+            .map(|record| {
+                serde_json::json!({
+                    "stream": stream_name,
+                    "partition": partition_id,
+                    "page": page,
+                    "file": record.file,
+                    "line": record.line,
+                    "raw": record.raw,
+                    "error": record.error,
+                })
+            })
+            .collect();
+        let rows = Message::record(
+            format!("{stream_name}_rejected"),
+            json_to_arrow(&rows, None)?,
+        );
+        for message in [warning, rows] {
+            if emit {
+                self.emit(message, messages).await?;
+            } else {
+                messages.push(message);
+            }
+        }
+
+        if self.config.max_rejected > 0 && self.stats.records_rejected > self.config.max_rejected {
+            return Err(crate::error::Error::decode(format!(
+                "Decode error budget exceeded: {} records rejected (max {})",
+                self.stats.records_rejected, self.config.max_rejected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Decode a streaming response as its bytes arrive
+    ///
+    /// Records are emitted in batches of `batch_size` as lines are read, and
+    /// the last SSE event ID is checkpointed whenever every record read so far
+    /// has been emitted. Checkpoints are sent to the output channel for the
+    /// receiver to commit; without one, the last checkpoint is saved once the
+    /// stream completes.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_response(
+        &mut self,
+        stream_name: &str,
+        partition_id: Option<&str>,
+        mut response: reqwest::Response,
+        mut parser: Box<dyn StreamParser>,
+        context: &TemplateContext,
+        cursor_field: Option<&str>,
+        max_cursor: &mut Option<String>,
+        mut sink: Option<&mut Vec<serde_json::Value>>,
+        messages: &mut Vec<Message>,
+    ) -> Result<()> {
+        let mut pending = Vec::new();
+        let mut read = 0;
+        let mut emitted = 0;
+        let mut checkpoint: Option<String> = None;
+
+        loop {
+            let chunk = response.chunk().await.map_err(|e| {
+                crate::error::Error::decode(format!("Failed to read response stream: {e}"))
+            })?;
+            let done = chunk.is_none();
+            let records = match chunk {
+                Some(chunk) => parser.feed(&chunk)?,
+                None => parser.finish()?,
+            };
+            read += records.len();
+
+            if let Some(field) = cursor_field {
+                if let Some(chunk_max) = self.extract_max_cursor(&records, field) {
+                    if max_cursor.as_ref().is_none_or(|max| chunk_max > *max) {
+                        *max_cursor = Some(chunk_max);
+                    }
+                }
+            }
+
+            if let Some(sink) = sink.as_deref_mut() {
+                sink.extend(records);
+                if done {
+                    break;
+                }
+                continue;
+            }
+
+            if self.transforms.is_empty() {
+                pending.extend(records);
+            } else {
+                pending.extend(self.transforms.apply(&records, context)?);
+            }
+
+            let limit_reached =
+                self.config.max_records > 0 && emitted + pending.len() >= self.config.max_records;
+            if limit_reached {
+                pending.truncate(self.config.max_records - emitted);
+            }
+
+            // Emit full batches, and the remainder once the stream or limit ends
+            while pending.len() >= self.config.batch_size
+                || (!pending.is_empty() && (done || limit_reached))
+            {
+                let size = pending.len().min(self.config.batch_size);
+                let batch: Vec<_> = pending.drain(..size).collect();
+                emitted += batch.len();
+                self.stats.add_records(batch.len());
+                let message = Message::record(stream_name, json_to_arrow(&batch, None)?);
+                self.emit(message, messages).await?;
+            }
+
+            // Records past the limit were read but never emitted
+            if limit_reached {
+                break;
+            }
+
+            // Checkpoint the last event once nothing read is left pending
+            if pending.is_empty() {
+                if let Some(event_id) = parser.last_event_id() {
+                    if checkpoint.as_deref() != Some(event_id) {
+                        let event_id = event_id.to_string();
+                        let state = match partition_id {
+                            Some(id) => {
+                                serde_json::json!({ "partition": id, "event_id": event_id })
+                            }
+                            None => serde_json::json!({ "event_id": event_id }),
+                        };
+                        self.emit(Message::state(stream_name, state), messages)
+                            .await?;
+                        checkpoint = Some(event_id);
+                    }
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        // Buffered records are only written after the sync returns, so the
+        // checkpoint is saved once the whole stream has been read
+        if self.output.is_none() {
+            if let Some(event_id) = checkpoint {
+                self.state
+                    .set_event_id(stream_name, partition_id, event_id)
+                    .await?;
+            }
+        }
+
+        messages.push(Message::debug(format!(
+            "Streamed {read} records from the response"
+        )));
+        Ok(())
+    }
+
+    /// Parse a page with an incremental parser as its body arrives
+    ///
+    /// The body's bytes are hashed for repeated page detection.
+    async fn parse_page(
+        mut response: reqwest::Response,
+        mut parser: Box<dyn StreamParser>,
+        hasher: &mut DefaultHasher,
+    ) -> Result<DecodedPage> {
+        let mut records = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| {
+            crate::error::Error::decode(format!("Failed to read response body: {e}"))
+        })? {
+            hasher.write(&chunk);
+            records.extend(parser.feed(&chunk)?);
+        }
+        records.extend(parser.finish()?);
+        Ok(DecodedPage {
+            response: serde_json::Value::Array(records.clone()),
+            records,
+            batches: None,
+            rejected: parser.take_rejected(),
+        })
+    }
+
+    /// Transform, count and emit collected records in batches
+    ///
+    /// At most `max_records` records are emitted.
+    async fn emit_records(
+        &mut self,
+        stream_name: &str,
+        mut records: Vec<serde_json::Value>,
+        context: &TemplateContext,
+        messages: &mut Vec<Message>,
+    ) -> Result<()> {
+        if self.config.max_records > 0 {
+            records.truncate(self.config.max_records);
+        }
+        if !self.transforms.is_empty() {
+            records = self.transforms.apply(&records, context)?;
+        }
+        self.stats.add_records(records.len());
+        for batch in records.chunks(self.config.batch_size.max(1)) {
+            let message = Message::record(stream_name, json_to_arrow(batch, None)?);
+            self.emit(message, messages).await?;
+        }
+        Ok(())
+    }
+
+    /// Send a message to the output channel, or buffer it with the sync's messages
+    async fn emit(&self, message: Message, messages: &mut Vec<Message>) -> Result<()> {
+        if let Some(output) = &self.output {
+            return output.send(message).await.map_err(|_| {
+                crate::error::Error::decode("Output closed while streaming the response")
+            });
+        }
+        messages.push(message);
+        Ok(())
+    }
+
+    /// Fetch and decode records with a single request (e.g., partition lookups)
+    ///
+    /// Records the decoder rejects are quarantined under `stream_name` and
+    /// count toward the stream's decode error budget.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn fetch_records(
+        &mut self,
+        stream_name: &str,
+        url: &str,
+        method: Method,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        body: Option<&str>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        context: &TemplateContext,
+    ) -> Result<Vec<serde_json::Value>> {
+        let body = body
+            .map(|body| template::render(body, context))
+            .transpose()?
+            .map(|body| serde_json::from_str(&body))
+            .transpose()?;
+        let body = self
+            .fetch_body(url, method, path, query_params, body, headers, context)
+            .await?;
+        let mut page = decoder.decode_bytes(&body)?;
+        page.materialize()?;
+        if !page.rejected.is_empty() {
+            let rejected = std::mem::take(&mut page.rejected);
+            self.quarantine(stream_name, None, 1, rejected, true, &mut Vec::new())
+                .await?;
+        }
+        Ok(page.records)
+    }
+
+    /// Fetch and decode the first page of a stream request (e.g., a total-count probe)
+    async fn fetch_response(
+        &self,
+        url: &str,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        context: &TemplateContext,
+    ) -> Result<serde_json::Value> {
+        let body = match (&self.graphql, &self.body) {
+            (Some(graphql), _) => Some(graphql.render_body(context)?),
+            (None, Some(body)) => Some(serde_json::from_str(&template::render(body, context)?)?),
+            (None, None) => None,
+        };
+        let body = self
+            .fetch_body(
+                url,
+                self.method.clone(),
+                path,
+                query_params,
+                body,
+                headers,
+                context,
+            )
+            .await?;
+        let mut page = decoder.decode_bytes(&body)?;
+        page.materialize()?;
+        Ok(page.response)
+    }
+
+    /// Send a single templated request and read the response body
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_body(
+        &self,
+        url: &str,
+        method: Method,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        body: Option<serde_json::Value>,
+        headers: &HashMap<String, String>,
+        context: &TemplateContext,
+    ) -> Result<bytes::Bytes> {
+        let mut req_config = RequestConfig::new();
+        for (key, value) in query_params {
+            let rendered = template::render(value, context)?;
+            if !rendered.is_empty() {
+                req_config = req_config.query(key, &rendered);
+            }
+        }
+        for (key, value) in headers {
+            req_config = req_config.header(key, value);
+        }
+        if let Some(body) = body {
+            req_config = req_config.json(body);
+        }
+
+        let full_url = format!(
+            "{}{}",
+            url.trim_end_matches('/'),
+            template::render(path, context)?
+        );
+        let response = self.client.request(method, &full_url, req_config).await?;
+        response
+            .bytes()
+            .await
+            .map_err(|e| crate::error::Error::decode(format!("Failed to read response body: {e}")))
+    }
+
+    /// Identify the next request by its URL and pagination inputs
+    fn request_fingerprint(
+        paginator: &dyn Paginator,
+        state: &PaginationState,
+        next_url: Option<&str>,
+    ) -> String {
+        let params: BTreeMap<_, _> = paginator.initial_params(state).into_iter().collect();
+        let headers: BTreeMap<_, _> = paginator.request_headers(state).into_iter().collect();
+        let body: BTreeMap<_, _> = paginator
+            .request_body_fields(state)
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect();
+        format!("{next_url:?} {params:?} {headers:?} {body:?}")
+    }
+
+    /// Record a pagination safeguard and apply the configured policy
+    ///
+    /// Returns an error under the `fail` policy; otherwise pagination stops.
+    fn pagination_event(
+        &mut self,
+        stream_name: &str,
+        event: PaginationEvent,
+        messages: &mut Vec<Message>,
+    ) -> Result<()> {
+        let reason = match event {
+            PaginationEvent::MaxPages(max) => {
+                self.stats.add_max_pages_reached();
+                format!("reached max_pages ({max})")
+            }
+            PaginationEvent::RepeatedCursor => {
+                self.stats.add_repeated_cursor();
+                "next request repeats an earlier one (pagination loop)".to_string()
+            }
+            PaginationEvent::RepeatedPage => {
+                self.stats.add_repeated_page();
+                "response repeats an earlier page (pagination loop)".to_string()
+            }
+        };
+
+        match self.limits.policy {
+            LimitPolicy::Fail => Err(crate::error::Error::pagination(stream_name, reason)),
+            LimitPolicy::Warn => {
+                messages.push(Message::warn(format!(
+                    "Stopping pagination for {stream_name}: {reason}"
+                )));
+                Ok(())
+            }
+            LimitPolicy::Stop => {
+                messages.push(Message::info(format!(
+                    "Stopping pagination for {stream_name}: {reason}"
+                )));
+                Ok(())
+            }
+        }
+    }
+
     /// Extract the maximum cursor value from records
     fn extract_max_cursor(
         &self,
         records: &[serde_json::Value],
         cursor_field: &str,
     ) -> Option<String> {
+        // Nested fields use JSONPath (e.g., "data.timestamp")
+        let path = crate::jsonpath::JsonPath::parse(cursor_field).ok()?;
         records
             .iter()
             .filter_map(|record| {
-                // Support nested fields with dot notation (e.g., "data.timestamp")
-                let mut current = record;
-                for part in cursor_field.split('.') {
-                    current = current.get(part)?;
-                }
                 // Convert to string for comparison
-                match current {
-                    serde_json::Value::String(s) => Some(s.clone()),
+                match path.find_first(record)? {
+                    serde_json::Value::String(s) => Some(s),
                     serde_json::Value::Number(n) => Some(n.to_string()),
                     _ => None,
                 }
@@ -260,6 +1015,7 @@ impl SyncEngine {
         paginator: &dyn Paginator,
         router: &dyn PartitionRouter,
         base_context: &TemplateContext,
+        cursor_field: Option<&str>,
     ) -> Result<Vec<Message>> {
         let start = Instant::now();
         let mut messages = Vec::new();
@@ -275,8 +1031,19 @@ impl SyncEngine {
             partitions.len()
         )));
 
+        let mut all_completed = true;
         for partition in partitions {
-            // Check if partition is already completed
+            // In failed-only mode, skip partitions without a recorded failure
+            if self.config.failed_partitions_only
+                && !self
+                    .state
+                    .is_partition_failed(stream_name, &partition.id)
+                    .await
+            {
+                continue;
+            }
+
+            // Skip partitions completed by an interrupted earlier run
             if self
                 .state
                 .is_partition_completed(stream_name, &partition.id)
@@ -299,10 +1066,11 @@ impl SyncEngine {
             let partition_json = serde_json::to_value(&partition.values).unwrap_or_default();
             context.set_partition(partition_json);
 
-            // Sync this partition (no cursor tracking for partitioned streams yet)
+            // Sync this partition with its own cursor
             let partition_messages = self
-                .sync_stream(
+                .sync_partition(
                     stream_name,
+                    &partition.id,
                     url,
                     path,
                     query_params,
@@ -310,7 +1078,9 @@ impl SyncEngine {
                     decoder,
                     paginator,
                     &context,
-                    None, // TODO: Add cursor_field support for partitioned streams
+                    cursor_field,
+                    None,
+                    &mut messages,
                 )
                 .await;
 
@@ -323,11 +1093,15 @@ impl SyncEngine {
                     self.stats.add_partition();
                 }
                 Err(e) => {
-                    self.stats.add_error();
-                    messages.push(Message::error(format!(
-                        "Error in partition {}: {e}",
-                        partition.id
-                    )));
+                    all_completed = false;
+                    self.partition_failed(&partition.id, &e, &mut messages);
+                    self.state
+                        .mark_partition_failed(
+                            stream_name,
+                            &partition.id,
+                            &redact::redact_str(&e.to_string()),
+                        )
+                        .await?;
                     if self.config.fail_fast {
                         return Err(e);
                     }
@@ -335,6 +1109,14 @@ impl SyncEngine {
             }
         }
 
+        // Once every partition is done, re-visit those that resume from a
+        // cursor on the next run; cursorless ones stay done
+        if all_completed {
+            self.state
+                .clear_completed_partitions(stream_name, cursor_field.is_some())
+                .await?;
+        }
+
         #[allow(clippy::cast_possible_truncation)]
         self.stats.set_duration(start.elapsed().as_millis() as u64);
 
@@ -346,6 +1128,405 @@ impl SyncEngine {
         Ok(messages)
     }
 
+    /// Sync a stream exported by an async job (create, poll, download)
+    ///
+    /// The job ID is available to templates as `{{ job_id }}`. The download
+    /// is read as a single page through the stream's decoder, so gzipped or
+    /// zipped results are unpacked by an `archive` decoder, unless the job
+    /// sets a download `records_path`.
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+    pub async fn sync_async_job_stream(
+        &mut self,
+        stream_name: &str,
+        url: &str,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        job: &AsyncJobConfig,
+        context: &TemplateContext,
+        cursor_field: Option<&str>,
+    ) -> Result<Vec<Message>> {
+        let no_params = HashMap::new();
+        let method =
+            Method::from_bytes(job.create_method.to_uppercase().as_bytes()).map_err(|_| {
+                crate::error::Error::config(format!(
+                    "Invalid async job method '{}'",
+                    job.create_method
+                ))
+            })?;
+        let body = job
+            .create_body
+            .as_deref()
+            .map(|body| template::render(body, context))
+            .transpose()?
+            .map(|body| serde_json::from_str(&body))
+            .transpose()?;
+        let created = self
+            .fetch_body(
+                url,
+                method,
+                &job.create_path,
+                &no_params,
+                body,
+                headers,
+                context,
+            )
+            .await?;
+        let created: serde_json::Value = serde_json::from_slice(&created)?;
+        let job_id = match extract_jsonpath_value(&created, &job.job_id_path) {
+            Some(serde_json::Value::String(id)) => id,
+            Some(id) if !id.is_null() => id.to_string(),
+            _ => {
+                return Err(crate::error::Error::partition(
+                    stream_name,
+                    format!("Async job response has no '{}'", job.job_id_path),
+                ))
+            }
+        };
+
+        let mut context = context.clone();
+        let mut vars = context.vars.as_object().cloned().unwrap_or_default();
+        vars.insert("job_id".to_string(), serde_json::json!(job_id));
+        context.set_vars(serde_json::Value::Object(vars));
+
+        // Poll until the job finishes
+        let mut async_job = AsyncJob::new(job_id.clone(), created);
+        for attempt in 0..job.poll_max_attempts.max(1) {
+            if attempt > 0 {
+                tokio::time::sleep(std::time::Duration::from_secs(job.poll_interval_secs)).await;
+            }
+            let status = self
+                .fetch_body(
+                    url,
+                    Method::GET,
+                    &job.poll_path,
+                    &no_params,
+                    None,
+                    headers,
+                    &context,
+                )
+                .await?;
+            async_job.update_state(job, &serde_json::from_slice(&status)?);
+            if async_job.state.is_terminal() {
+                break;
+            }
+        }
+        match &async_job.state {
+            AsyncJobState::Completed => {}
+            AsyncJobState::Failed(status) => {
+                return Err(crate::error::Error::partition(
+                    stream_name,
+                    format!("Async job {job_id} failed with status '{status}'"),
+                ))
+            }
+            _ => {
+                return Err(crate::error::Error::partition(
+                    stream_name,
+                    format!(
+                        "Async job {job_id} did not finish after {} polls",
+                        job.poll_max_attempts
+                    ),
+                ))
+            }
+        }
+
+        // A download `records_path` reads JSON results; otherwise the stream's
+        // decoder (e.g. an archive) decodes them
+        let json_decoder = job
+            .records_path
+            .as_ref()
+            .map(|path| JsonDecoder::with_paths([path.as_str()]));
+        let decoder = json_decoder
+            .as_ref()
+            .map_or(decoder, |json| json as &dyn RecordDecoder);
+
+        // Download the results as a plain GET, whatever the stream request is
+        let method = std::mem::replace(&mut self.method, Method::GET);
+        let body = self.body.take();
+        let graphql = self.graphql.take();
+        let result = self
+            .sync_pages(
+                stream_name,
+                None,
+                url,
+                &job.download_path,
+                &no_params,
+                headers,
+                decoder,
+                &NoPaginator,
+                &context,
+                cursor_field,
+                None,
+            )
+            .await;
+        self.method = method;
+        self.body = body;
+        self.graphql = graphql;
+        result
+    }
+
+    /// Sync a stream over adaptive date windows
+    ///
+    /// Windows whose reported total or record count reaches the result cap
+    /// are split and synced again; sparse windows widen the following ones.
+    /// The chosen windows are saved as slices in state, so an interrupted run
+    /// resumes after its last completed window and later runs reuse the
+    /// same slicing.
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+    pub async fn sync_adaptive_stream(
+        &mut self,
+        stream_name: &str,
+        url: &str,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        paginator: &dyn Paginator,
+        windows: AdaptiveWindows,
+        base_context: &TemplateContext,
+    ) -> Result<Vec<Message>> {
+        let start = Instant::now();
+        let mut messages = Vec::new();
+
+        messages.push(Message::info(format!(
+            "Starting adaptive date-window sync for stream: {stream_name}"
+        )));
+
+        // Replay the windows chosen by earlier runs
+        let stored = self.state.get_slices(stream_name).await;
+        let completed: HashSet<String> = stored
+            .iter()
+            .filter(|slice| slice.completed)
+            .map(|slice| format!("{}_{}", slice.start, slice.end))
+            .collect();
+        let mut windows = windows.with_windows(
+            stored
+                .iter()
+                .filter_map(|slice| {
+                    Some(DateWindow::new(
+                        DateTime::parse_from_rfc3339(&slice.start).ok()?.into(),
+                        DateTime::parse_from_rfc3339(&slice.end).ok()?.into(),
+                    ))
+                })
+                .collect(),
+        );
+
+        let mut slices = Vec::new();
+        let mut all_completed = true;
+        while let Some(window) = windows.next_window() {
+            let id = window.id();
+            let (slice_start, slice_end) = id.split_once('_').unwrap_or_default();
+            let mut slice = DateSlice {
+                start: slice_start.to_string(),
+                end: slice_end.to_string(),
+                records: 0,
+                completed: false,
+                error: None,
+            };
+
+            // Skip windows completed by an interrupted earlier run
+            if completed.contains(&id) {
+                if let Some(done) = stored.iter().find(|s| s.start == slice.start) {
+                    slice.records = done.records;
+                }
+                slice.completed = true;
+                slices.push(slice);
+                messages.push(Message::debug(format!("Skipping completed window: {id}")));
+                continue;
+            }
+
+            let partition = windows.partition(&window);
+            let mut context = base_context.clone();
+            context.set_partition(serde_json::to_value(&partition.values).unwrap_or_default());
+
+            // Probe the reported total before paging through the window
+            if let Some(total_path) = windows.total_path() {
+                let total = match self
+                    .fetch_response(url, path, query_params, headers, decoder, &context)
+                    .await
+                {
+                    // XML totals are text unless coerced
+                    Ok(response) => extract_jsonpath_value(&response, total_path)
+                        .and_then(|total| total.as_u64().or_else(|| total.as_str()?.parse().ok())),
+                    Err(e) => {
+                        all_completed = false;
+                        self.partition_failed(&id, &e, &mut messages);
+                        slice.error = Some(redact::redact_str(&e.to_string()));
+                        slices.push(slice);
+                        self.state.set_slices(stream_name, slices.clone()).await?;
+                        if self.config.fail_fast {
+                            return Err(e);
+                        }
+                        continue;
+                    }
+                };
+                #[allow(clippy::cast_possible_truncation)]
+                if let Some(total) = total.map(|total| total as usize) {
+                    if windows.is_capped(total) && windows.split(&window) {
+                        messages.push(Message::debug(format!(
+                            "Splitting window {id}: {total} results reach the cap"
+                        )));
+                        continue;
+                    }
+                }
+            }
+
+            // Collect the window's decoded records; they are only emitted
+            // once the window is known to be under the cap
+            let rejected_before = self.stats.records_rejected;
+            let mut records = Vec::new();
+            let result = self
+                .sync_partition(
+                    stream_name,
+                    &id,
+                    url,
+                    path,
+                    query_params,
+                    headers,
+                    decoder,
+                    paginator,
+                    &context,
+                    None,
+                    Some(&mut records),
+                    &mut messages,
+                )
+                .await;
+
+            match result {
+                Ok(msgs) => {
+                    let count = records.len();
+                    if windows.is_capped(count) && windows.split(&window) {
+                        // Drop the capped results and sync both halves instead
+                        self.stats.records_rejected = rejected_before;
+                        messages.push(Message::debug(format!(
+                            "Splitting window {id}: {count} records reach the cap"
+                        )));
+                        continue;
+                    }
+                    if windows.is_capped(count) {
+                        messages.push(Message::warn(format!(
+                            "Window {id} returned {count} records at the minimum window size; \
+                             results may be truncated"
+                        )));
+                    }
+                    windows.observe(count);
+                    for message in msgs {
+                        self.emit(message, &mut messages).await?;
+                    }
+                    self.emit_records(stream_name, records, &context, &mut messages)
+                        .await?;
+                    slice.records = count;
+                    slice.completed = true;
+                    self.stats.add_partition();
+                }
+                Err(e) => {
+                    all_completed = false;
+                    self.partition_failed(&id, &e, &mut messages);
+                    slice.error = Some(redact::redact_str(&e.to_string()));
+                    if self.config.fail_fast {
+                        slices.push(slice);
+                        self.state.set_slices(stream_name, slices).await?;
+                        return Err(e);
+                    }
+                }
+            }
+
+            slices.push(slice);
+            self.state.set_slices(stream_name, slices.clone()).await?;
+        }
+
+        // Once every window is done, keep the slicing but re-visit them all
+        if all_completed {
+            for slice in &mut slices {
+                slice.completed = false;
+            }
+        }
+        self.state.set_slices(stream_name, slices).await?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        self.stats.set_duration(start.elapsed().as_millis() as u64);
+
+        messages.push(Message::info(format!(
+            "Completed adaptive date-window sync for {stream_name}: {} windows",
+            self.stats.partitions_synced
+        )));
+
+        Ok(messages)
+    }
+
+    /// Sync one partition, retrying it as a unit with exponential backoff
+    ///
+    /// Records counted or collected into `sink` by a failed attempt are
+    /// rolled back before the retry.
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_partition(
+        &mut self,
+        stream_name: &str,
+        partition_id: &str,
+        url: &str,
+        path: &str,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        decoder: &dyn RecordDecoder,
+        paginator: &dyn Paginator,
+        context: &TemplateContext,
+        cursor_field: Option<&str>,
+        mut sink: Option<&mut Vec<serde_json::Value>>,
+        messages: &mut Vec<Message>,
+    ) -> Result<Vec<Message>> {
+        let mut attempt = 0;
+        loop {
+            let records_before = self.stats.records_synced;
+            if let Some(sink) = sink.as_deref_mut() {
+                sink.clear();
+            }
+            let result = self
+                .sync_pages(
+                    stream_name,
+                    Some(partition_id),
+                    url,
+                    path,
+                    query_params,
+                    headers,
+                    decoder,
+                    paginator,
+                    context,
+                    cursor_field,
+                    sink.as_deref_mut(),
+                )
+                .await;
+
+            match result {
+                Err(e) if attempt < self.config.partition_retries => {
+                    let delay = self.config.partition_retry_delay(attempt);
+                    attempt += 1;
+                    self.stats.records_synced = records_before;
+                    self.stats.add_partition_retry();
+                    messages.push(Message::warn(format!(
+                        "Partition {partition_id} failed: {e}; retry {attempt}/{} in {}ms",
+                        self.config.partition_retries,
+                        delay.as_millis()
+                    )));
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Count and log a partition that failed after all retries
+    fn partition_failed(
+        &mut self,
+        partition_id: &str,
+        error: &crate::error::Error,
+        messages: &mut Vec<Message>,
+    ) {
+        self.stats.add_error();
+        self.stats.add_partition_failed();
+        messages.push(Message::error(format!(
+            "Error in partition {partition_id}: {error}"
+        )));
+    }
+
     /// Reset statistics
     pub fn reset_stats(&mut self) {
         self.stats = SyncStats::default();
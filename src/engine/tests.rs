@@ -1,12 +1,18 @@
 //! Tests for engine module
 
 use super::*;
-use crate::decode::JsonDecoder;
+use crate::decode::{
+    ArchiveDecoder, ArchiveFormat, CsvDecoder, DecodeErrorPolicy, GraphqlDecoder, JsonDecoder,
+    JsonlDecoder, ParquetDecoder, StreamDecoder, StreamFormat,
+};
 use crate::http::HttpClientConfig;
-use crate::pagination::{NoPaginator, OffsetPaginator, StopCondition};
-use crate::partition::ListRouter;
+use crate::pagination::{
+    CursorPaginator, CursorSource, KeysetPaginator, NoPaginator, OffsetPaginator, RelayPaginator,
+    RequestLocation, StopCondition,
+};
+use crate::partition::{AdaptiveWindows, CartesianRouter, DatetimeRouter, ListRouter};
 use serde_json::json;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{body_json, header, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ============================================================================
@@ -45,6 +51,19 @@ fn test_message_log() {
     assert!(msg.is_log());
 }
 
+#[test]
+fn test_message_log_redacts_secrets() {
+    crate::secrets::redact::register_secret("engine-log-secret");
+
+    let msg = Message::warn("GET https://api.example.com/v1?api_key=engine-log-secret failed");
+    match msg {
+        Message::Log { message, .. } => {
+            assert_eq!(message, "GET https://api.example.com/v1?api_key=*** failed");
+        }
+        _ => panic!("Expected log message"),
+    }
+}
+
 // ============================================================================
 // SyncConfig Tests
 // ============================================================================
@@ -72,6 +91,38 @@ fn test_sync_config_builder() {
     assert!(!config.fail_fast);
 }
 
+// ============================================================================
+// PaginationLimits Tests
+// ============================================================================
+
+#[test]
+fn test_pagination_limits_default() {
+    let limits = PaginationLimits::default();
+    assert_eq!(limits.max_pages, 0);
+    assert!(limits.detect_loops);
+    assert_eq!(limits.policy, LimitPolicy::Warn);
+}
+
+#[test]
+fn test_pagination_limits_builder() {
+    let limits = PaginationLimits::new()
+        .with_max_pages(10)
+        .with_loop_detection(false)
+        .with_policy(LimitPolicy::Fail);
+
+    assert_eq!(limits.max_pages, 10);
+    assert!(!limits.detect_loops);
+    assert_eq!(limits.policy, LimitPolicy::Fail);
+}
+
+#[test]
+fn test_limit_policy_parse() {
+    assert_eq!(LimitPolicy::parse("stop"), Some(LimitPolicy::Stop));
+    assert_eq!(LimitPolicy::parse("WARN"), Some(LimitPolicy::Warn));
+    assert_eq!(LimitPolicy::parse("fail"), Some(LimitPolicy::Fail));
+    assert_eq!(LimitPolicy::parse("ignore"), None);
+}
+
 // ============================================================================
 // SyncStats Tests
 // ============================================================================
@@ -108,6 +159,13 @@ fn test_sync_stats_mutations() {
     stats.add_error();
     assert_eq!(stats.errors, 1);
 
+    stats.add_max_pages_reached();
+    stats.add_repeated_cursor();
+    stats.add_repeated_page();
+    stats.add_repeated_page();
+    assert_eq!(stats.repeated_pages, 2);
+    assert_eq!(stats.pagination_events(), 4);
+
     stats.set_duration(1500);
     assert_eq!(stats.duration_ms, 1500);
 }
@@ -244,36 +302,54 @@ async fn test_sync_engine_with_pagination() {
 }
 
 #[tokio::test]
-async fn test_sync_engine_max_records() {
+async fn test_sync_engine_header_cursor_into_body() {
     let server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/api/items"))
+    // Page 2: cursor sent in the JSON body, merged with the body template
+    Mock::given(method("POST"))
+        .and(path("/api/search"))
+        .and(body_json(
+            json!({"filter": "open", "page": {"after": "cur_2"}}),
+        ))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "items": [{"id": 1}, {"id": 2}, {"id": 3}, {"id": 4}, {"id": 5}]
+            "items": [{"id": 3}]
         })))
         .mount(&server)
         .await;
 
-    let http_config = HttpClientConfig::builder()
+    // Page 1: cursor returned in a response header
+    Mock::given(method("POST"))
+        .and(path("/api/search"))
+        .and(body_json(json!({"filter": "open"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Next-Cursor", "cur_2")
+                .set_body_json(json!({"items": [{"id": 1}, {"id": 2}]})),
+        )
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
         .base_url(server.uri())
         .no_rate_limit()
         .build();
-    let client = HttpClient::with_config(http_config);
+    let client = HttpClient::with_config(config);
     let state = crate::state::StateManager::in_memory();
 
-    let config = SyncConfig::new().with_max_records(3);
-    let mut engine = SyncEngine::new(client, state).with_config(config);
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_request(Method::POST, Some(r#"{"filter": "open"}"#.to_string()));
 
     let decoder = JsonDecoder::with_path("items");
-    let paginator = NoPaginator;
+    let paginator = CursorPaginator::new("page.after", "", StopCondition::EmptyPage)
+        .with_source(CursorSource::Header("X-Next-Cursor".to_string()))
+        .with_location(RequestLocation::Body);
     let context = TemplateContext::new();
 
     engine
         .sync_stream(
             "items",
             &server.uri(),
-            "/api/items",
+            "/api/search",
             &std::collections::HashMap::new(),
             &std::collections::HashMap::new(),
             &decoder,
@@ -285,85 +361,174 @@ async fn test_sync_engine_max_records() {
         .unwrap();
 
     let stats = engine.stats();
-    assert_eq!(stats.records_synced, 3); // Limited to 3
+    assert_eq!(stats.records_synced, 3);
+    assert_eq!(stats.pages_fetched, 2);
 }
 
 #[tokio::test]
-async fn test_sync_engine_partitioned() {
+async fn test_sync_engine_keyset_pagination() {
     let server = MockServer::start().await;
 
-    // Region A
     Mock::given(method("GET"))
-        .and(path("/api/data"))
-        .and(query_param("region", "us-east"))
+        .and(path("/v1/customers"))
+        .and(query_param_is_missing("starting_after"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "data": [{"id": 1, "region": "us-east"}]
+            "data": [{"id": "cus_1"}, {"id": "cus_2"}],
+            "has_more": true
         })))
         .mount(&server)
         .await;
 
-    // Region B
     Mock::given(method("GET"))
-        .and(path("/api/data"))
-        .and(query_param("region", "us-west"))
+        .and(path("/v1/customers"))
+        .and(query_param("starting_after", "cus_2"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "data": [{"id": 2, "region": "us-west"}]
+            "data": [{"id": "cus_3"}],
+            "has_more": false
         })))
         .mount(&server)
         .await;
 
-    let http_config = HttpClientConfig::builder()
+    let config = HttpClientConfig::builder()
         .base_url(server.uri())
         .no_rate_limit()
         .build();
-    let client = HttpClient::with_config(http_config);
+    let client = HttpClient::with_config(config);
     let state = crate::state::StateManager::in_memory();
 
     let mut engine = SyncEngine::new(client, state);
 
-    let decoder = JsonDecoder::with_path("data");
-    let paginator = NoPaginator;
-    let router = ListRouter::new(vec!["us-east".to_string(), "us-west".to_string()], "region");
+    let paginator = KeysetPaginator::new(
+        "starting_after",
+        "id",
+        StopCondition::field("has_more", json!(false)),
+    );
+    engine
+        .sync_stream(
+            "customers",
+            &server.uri(),
+            "/v1/customers",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("data"),
+            &paginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
 
-    let mut query_params = std::collections::HashMap::new();
-    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+    let stats = engine.stats();
+    assert_eq!(stats.records_synced, 3);
+    assert_eq!(stats.pages_fetched, 2);
+}
 
-    let context = TemplateContext::new();
+#[tokio::test]
+async fn test_sync_engine_keyset_key_not_advancing() {
+    let server = MockServer::start().await;
+
+    // The API ignores since_id and keeps returning the first page
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "orders": [{"id": 1}, {"id": 2}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let err = engine
+        .sync_stream(
+            "orders",
+            &server.uri(),
+            "/orders",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("orders"),
+            &KeysetPaginator::new("since_id", "id", StopCondition::EmptyPage),
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, crate::error::Error::Pagination { .. }));
+    assert!(err.to_string().contains("did not advance"));
+    assert_eq!(engine.stats().pages_fetched, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_max_pages() {
+    let server = MockServer::start().await;
+
+    // Every page reports more data
+    for offset in 0..5 {
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param("offset", (offset * 2).to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [{"id": offset * 2}, {"id": offset * 2 + 1}]
+            })))
+            .mount(&server)
+            .await;
+    }
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_pagination_limits(PaginationLimits::new().with_max_pages(3));
 
     let messages = engine
-        .sync_partitioned_stream(
-            "regional_data",
+        .sync_stream(
+            "items",
             &server.uri(),
-            "/api/data",
-            &query_params,
+            "/items",
             &std::collections::HashMap::new(),
-            &decoder,
-            &paginator,
-            &router,
-            &context,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("items"),
+            &OffsetPaginator::new("offset", "limit", 2, StopCondition::EmptyPage),
+            &TemplateContext::new(),
+            None,
         )
         .await
         .unwrap();
 
-    let record_msgs: Vec<_> = messages.iter().filter(|m| m.is_record()).collect();
-    assert_eq!(record_msgs.len(), 2); // One per partition
-
     let stats = engine.stats();
-    assert_eq!(stats.partitions_synced, 2);
+    assert_eq!(stats.pages_fetched, 3);
+    assert_eq!(stats.records_synced, 6);
+    assert_eq!(stats.max_pages_reached, 1);
+    assert!(messages.iter().any(|m| matches!(
+        m,
+        Message::Log { level: LogLevel::Warn, message } if message.contains("max_pages (3)")
+    )));
 }
 
 #[tokio::test]
-async fn test_sync_engine_skips_completed_partitions() {
+async fn test_sync_engine_csv_pages() {
     let server = MockServer::start().await;
 
-    // Only region-b should be called
     Mock::given(method("GET"))
-        .and(path("/api/data"))
-        .and(query_param("region", "region-b"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "data": [{"id": 2}]
-        })))
-        .expect(1) // Should only be called once
+        .and(path("/results"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Id,Name\n1,Acme\n2,Globex\n"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/results"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Id,Name\n"))
         .mount(&server)
         .await;
 
@@ -373,38 +538,1858 @@ async fn test_sync_engine_skips_completed_partitions() {
         .build();
     let client = HttpClient::with_config(http_config);
     let state = crate::state::StateManager::in_memory();
-
-    // Mark region-a as completed
-    state
-        .mark_partition_completed("stream", "region-a")
+    let mut engine = SyncEngine::new(client, state);
+    let messages = engine
+        .sync_stream(
+            "accounts",
+            &server.uri(),
+            "/results",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &CsvDecoder::new(),
+            &OffsetPaginator::new("offset", "limit", 2, StopCondition::EmptyPage),
+            &TemplateContext::new(),
+            None,
+        )
         .await
         .unwrap();
 
-    let mut engine = SyncEngine::new(client, state);
+    // Non-JSON pages decode and paginate without a JSON parse
+    assert_eq!(engine.stats().pages_fetched, 2);
+    assert_eq!(engine.stats().records_synced, 2);
+    assert!(messages
+        .iter()
+        .any(|m| matches!(m, Message::Record { batch, .. } if batch.num_rows() == 2)));
+}
 
-    let decoder = JsonDecoder::with_path("data");
-    let paginator = NoPaginator;
-    let router = ListRouter::new(
-        vec!["region-a".to_string(), "region-b".to_string()],
-        "region",
-    );
+#[tokio::test]
+async fn test_sync_engine_gzip_pages() {
+    use std::io::Write;
 
-    let mut query_params = std::collections::HashMap::new();
-    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+    fn gzip(data: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
 
-    let context = TemplateContext::new();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/export"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(gzip("{\"id\": 1}\n{\"id\": 2}\n")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/export"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(gzip("")))
+        .mount(&server)
+        .await;
 
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Auto, Box::new(JsonlDecoder::new()));
     engine
-        .sync_partitioned_stream(
-            "stream",
+        .sync_stream(
+            "exports",
             &server.uri(),
-            "/api/data",
-            &query_params,
+            "/export",
+            &std::collections::HashMap::new(),
             &std::collections::HashMap::new(),
             &decoder,
-            &paginator,
-            &router,
-            &context,
+            &OffsetPaginator::new("offset", "limit", 2, StopCondition::EmptyPage),
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Binary bodies reach the decoder unmodified
+    assert_eq!(engine.stats().pages_fetched, 2);
+    assert_eq!(engine.stats().records_synced, 2);
+}
+
+#[tokio::test]
+async fn test_sync_async_job_downloads_archive() {
+    use crate::partition::AsyncJobConfig;
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(b"{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n")
+        .unwrap();
+    let results = encoder.finish().unwrap();
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/jobs"))
+        .and(body_json(json!({"object": "accounts"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "j1"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/j1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"state": "JobComplete"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/j1/results"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(results))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    let decoder = ArchiveDecoder::new(ArchiveFormat::Auto, Box::new(JsonlDecoder::new()));
+    let job = AsyncJobConfig::new()
+        .with_create("POST", "/jobs", Some(r#"{"object": "accounts"}"#))
+        .with_poll("/jobs/{{ job_id }}", 0, 3)
+        .with_status("state", "JobComplete", vec!["Failed"])
+        .with_download("/jobs/{{ job_id }}/results", None);
+    let messages = engine
+        .sync_async_job_stream(
+            "accounts",
+            &server.uri(),
+            &std::collections::HashMap::new(),
+            &decoder,
+            &job,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The gzipped download is unpacked by the stream's archive decoder
+    assert_eq!(engine.stats().records_synced, 3);
+    assert!(messages
+        .iter()
+        .any(|m| matches!(m, Message::Record { batch, .. } if batch.num_rows() == 3)));
+}
+
+#[tokio::test]
+async fn test_sync_engine_parquet_keeps_schema() {
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batch = arrow::record_batch::RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+        ],
+    )
+    .unwrap();
+    let mut writer =
+        parquet::arrow::ArrowWriter::try_new(Vec::new(), schema.clone(), None).unwrap();
+    writer.write(&batch).unwrap();
+    let body = writer.into_inner().unwrap();
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/unload"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_max_records(2));
+    let messages = engine
+        .sync_stream(
+            "unload",
+            &server.uri(),
+            "/unload",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &ParquetDecoder::new(),
+            &NoPaginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let batches: Vec<_> = messages
+        .iter()
+        .filter_map(|m| match m {
+            Message::Record { batch, .. } => Some(batch),
+            _ => None,
+        })
+        .collect();
+    // Emitted as decoded (Int32 is not re-inferred as Int64), capped at max_records
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].schema(), schema);
+    assert_eq!(batches[0].num_rows(), 2);
+    assert_eq!(engine.stats().records_synced, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_transformations() {
+    use crate::partition::CompareOp;
+    use crate::transform::{TransformPipeline, Transformation};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"id": 1, "status": "open", "email": "a@example.com"},
+                {"id": 2, "status": "deleted", "email": "b@example.com"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_transformations(TransformPipeline::new(vec![
+        Transformation::Filter {
+            path: "status".to_string(),
+            op: CompareOp::Ne,
+            value: json!("deleted"),
+        },
+        Transformation::RemoveFields {
+            paths: vec!["email".to_string()],
+        },
+        Transformation::AddField {
+            path: "account_id".to_string(),
+            value: json!("{{ partition.account_id }}"),
+        },
+    ]));
+    let mut context = TemplateContext::new();
+    context.set_partition(json!({"account_id": 7}));
+
+    let messages = engine
+        .sync_stream(
+            "items",
+            &server.uri(),
+            "/items",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("items"),
+            &OffsetPaginator::new("offset", "limit", 2, StopCondition::EmptyPage),
+            &context,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let batch = messages
+        .iter()
+        .find_map(|m| match m {
+            Message::Record { batch, .. } => Some(batch),
+            _ => None,
+        })
+        .unwrap();
+    // Pagination sees both decoded records; only the transformed one is emitted
+    assert_eq!(engine.stats().pages_fetched, 2);
+    assert_eq!(engine.stats().records_synced, 1);
+    assert_eq!(batch.num_rows(), 1);
+    assert!(batch.column_by_name("email").is_none());
+    assert!(batch.column_by_name("account_id").is_some());
+}
+
+#[tokio::test]
+async fn test_sync_engine_quarantines_rejected_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/export"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("{\"id\": 1}\n{\"id\": 2,\n{\"id\": 3}\n{broken\n"),
+        )
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    let messages = sync_export(&mut engine, &server).await.unwrap();
+    assert_eq!(engine.stats().records_synced, 2);
+    assert_eq!(engine.stats().records_rejected, 2);
+
+    // Rejected lines go to their own stream with their raw text and location
+    let rejected = messages
+        .iter()
+        .find_map(|m| match m {
+            Message::Record { stream, batch } if stream == "export_rejected" => Some(batch),
+            _ => None,
+        })
+        .unwrap();
+    let rows = crate::output::arrow_to_json(rejected).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["stream"], "export");
+    assert_eq!(rows[0]["page"], 1);
+    assert_eq!(rows[0]["line"], 2);
+    assert_eq!(rows[0]["raw"], "{\"id\": 2,");
+    assert_eq!(rows[1]["line"], 4);
+
+    // The run fails once the error budget is spent, after the rows that
+    // spent it are sent to the output
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_max_rejected(1));
+    let (output, mut streamed) = mpsc::channel(16);
+    engine.set_output(Some(output));
+    let err = sync_export(&mut engine, &server).await.unwrap_err();
+    engine.set_output(None);
+    assert!(
+        err.to_string().contains("Decode error budget exceeded"),
+        "{err}"
+    );
+    let mut quarantined = 0;
+    while let Some(message) = streamed.recv().await {
+        if let Message::Record { stream, batch } = message {
+            assert_eq!(stream, "export_rejected");
+            quarantined += batch.num_rows();
+        }
+    }
+    assert_eq!(quarantined, 2);
+}
+
+#[tokio::test]
+async fn test_fetch_records_quarantines_rejected_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/brands"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{\"id\": 1}\n{broken\n"))
+        .mount(&server)
+        .await;
+
+    // Lookup rejects count toward the budget of the stream they feed
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_max_rejected(1));
+    let decoder = JsonlDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine);
+    let brands = engine
+        .fetch_records(
+            "tickets",
+            &server.uri(),
+            Method::GET,
+            "/brands",
+            &std::collections::HashMap::new(),
+            None,
+            &std::collections::HashMap::new(),
+            &decoder,
+            &TemplateContext::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(brands, vec![json!({"id": 1})]);
+    assert_eq!(engine.stats().records_rejected, 1);
+
+    let err = engine
+        .fetch_records(
+            "tickets",
+            &server.uri(),
+            Method::GET,
+            "/brands",
+            &std::collections::HashMap::new(),
+            None,
+            &std::collections::HashMap::new(),
+            &decoder,
+            &TemplateContext::new(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Decode error budget exceeded"),
+        "{err}"
+    );
+}
+
+async fn sync_export(engine: &mut SyncEngine, server: &MockServer) -> Result<Vec<Message>> {
+    engine
+        .sync_stream(
+            "export",
+            &server.uri(),
+            "/export",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonlDecoder::new().with_error_policy(DecodeErrorPolicy::Quarantine),
+            &NoPaginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_sync_engine_event_stream() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(header("Last-Event-ID", "3"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("id: 4\ndata: {\"id\": 4}\n\n", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            ": connected\n\n\
+             id: 1\ndata: {\"id\": 1}\n\n\
+             id: 2\ndata: {\"id\": 2}\n\n\
+             id: 3\ndata: {\"id\": 3}\n\n",
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_batch_size(2));
+    let messages = sync_events(&mut engine, &server).await.unwrap();
+    let rows: Vec<_> = messages
+        .iter()
+        .filter_map(|m| match m {
+            Message::Record { batch, .. } => Some(batch.num_rows()),
+            _ => None,
+        })
+        .collect();
+    // One streamed response, emitted in batches of two
+    assert_eq!(rows, vec![2, 1]);
+    assert_eq!(engine.stats().pages_fetched, 1);
+    assert_eq!(engine.stats().records_synced, 3);
+    assert!(messages.iter().any(|m| matches!(
+        m,
+        Message::State { data, .. } if data["event_id"] == "3"
+    )));
+
+    // The next run resumes after the checkpointed event
+    engine.reset_stats();
+    sync_events(&mut engine, &server).await.unwrap();
+    assert_eq!(engine.stats().records_synced, 1);
+    assert_eq!(
+        engine.state().get_event_id("events", None).await,
+        Some("4".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_sync_engine_event_stream_output() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "id: 1\ndata: {\"id\": 1}\n\n\
+             id: 2\ndata: {\"id\": 2}\n\n",
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    // Batches and checkpoints go to the output channel as they are produced
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_batch_size(1));
+    let (output, mut streamed) = mpsc::channel(16);
+    engine.set_output(Some(output));
+    let messages = sync_events(&mut engine, &server).await.unwrap();
+    engine.set_output(None);
+    assert!(!messages.iter().any(Message::is_record));
+
+    let mut received = Vec::new();
+    while let Some(message) = streamed.recv().await {
+        received.push(message);
+    }
+    assert_eq!(received.iter().filter(|m| m.is_record()).count(), 2);
+    assert_eq!(
+        received
+            .iter()
+            .filter_map(Message::event_checkpoint)
+            .collect::<Vec<_>>(),
+        vec![("events", None, "2")]
+    );
+
+    // The receiver commits checkpoints once it has written the records before them
+    assert_eq!(engine.state().get_event_id("events", None).await, None);
+}
+
+async fn sync_events(engine: &mut SyncEngine, server: &MockServer) -> Result<Vec<Message>> {
+    engine
+        .sync_stream(
+            "events",
+            &server.uri(),
+            "/events",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &StreamDecoder::new(StreamFormat::Sse),
+            &NoPaginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_sync_engine_repeated_page() {
+    let server = MockServer::start().await;
+
+    // The API ignores the offset and keeps returning the same page
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"id": 1}, {"id": 2}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let paginator = OffsetPaginator::new("offset", "limit", 2, StopCondition::EmptyPage);
+
+    // Stop keeps the first page only
+    let mut engine = SyncEngine::new(
+        HttpClient::with_config(config.clone()),
+        crate::state::StateManager::in_memory(),
+    );
+    engine.set_pagination_limits(PaginationLimits::new().with_policy(LimitPolicy::Stop));
+    engine
+        .sync_stream(
+            "items",
+            &server.uri(),
+            "/items",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("items"),
+            &paginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(engine.stats().records_synced, 2);
+    assert_eq!(engine.stats().pages_fetched, 2);
+    assert_eq!(engine.stats().repeated_pages, 1);
+
+    // Fail surfaces a pagination error
+    let mut engine = SyncEngine::new(
+        HttpClient::with_config(config),
+        crate::state::StateManager::in_memory(),
+    );
+    engine.set_pagination_limits(PaginationLimits::new().with_policy(LimitPolicy::Fail));
+    let err = engine
+        .sync_stream(
+            "items",
+            &server.uri(),
+            "/items",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("items"),
+            &paginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, crate::error::Error::Pagination { .. }));
+    assert!(err.to_string().contains("repeats an earlier page"));
+}
+
+#[tokio::test]
+async fn test_sync_engine_repeated_cursor() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "events": [{"id": 1}],
+            "next": "abc"
+        })))
+        .mount(&server)
+        .await;
+
+    // The cursor never moves past "abc"
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(query_param("cursor", "abc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "events": [{"id": 2}],
+            "next": "abc"
+        })))
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let messages = engine
+        .sync_stream(
+            "events",
+            &server.uri(),
+            "/events",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("events"),
+            &CursorPaginator::new("cursor", "next", StopCondition::EmptyPage),
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stats = engine.stats();
+    assert_eq!(stats.pages_fetched, 2);
+    assert_eq!(stats.records_synced, 2);
+    assert_eq!(stats.repeated_cursors, 1);
+    assert!(messages.iter().any(|m| matches!(
+        m,
+        Message::Log { level: LogLevel::Warn, message } if message.contains("pagination loop")
+    )));
+}
+
+#[tokio::test]
+async fn test_sync_engine_graphql_relay() {
+    let server = MockServer::start().await;
+    let query = "query($first: Int, $after: String) { orders(first: $first, after: $after) { edges { node { id } } pageInfo { hasNextPage endCursor } } }";
+
+    // Page 2: end cursor injected into variables
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_json(
+            json!({"query": query, "variables": {"first": 2, "after": "c2"}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {"orders": {
+                "edges": [{"node": {"id": 3}}],
+                "pageInfo": {"hasNextPage": false, "endCursor": "c3"}
+            }}
+        })))
+        .mount(&server)
+        .await;
+
+    // Page 1
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_json(
+            json!({"query": query, "variables": {"first": 2}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {"orders": {
+                "edges": [{"node": {"id": 1}}, {"node": {"id": 2}}],
+                "pageInfo": {"hasNextPage": true, "endCursor": "c2"}
+            }},
+            "extensions": {"cost": {
+                "requestedQueryCost": 10,
+                "throttleStatus": {"currentlyAvailable": 990, "restoreRate": 50}
+            }}
+        })))
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_request(Method::POST, None);
+    engine.set_graphql(Some(
+        GraphqlRequest::new(query)
+            .with_variables(json!({"first": 2}))
+            .with_cost_path("extensions.cost"),
+    ));
+
+    let decoder = GraphqlDecoder::new("data.orders");
+    let paginator = RelayPaginator::new("data.orders.pageInfo", "after");
+    let context = TemplateContext::new();
+
+    engine
+        .sync_stream(
+            "orders",
+            &server.uri(),
+            "/graphql",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &decoder,
+            &paginator,
+            &context,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stats = engine.stats();
+    assert_eq!(stats.records_synced, 3);
+    assert_eq!(stats.pages_fetched, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_graphql_errors() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": null,
+            "errors": [{"message": "Field 'nope' doesn't exist on type 'Shop'"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_request(Method::POST, None);
+    engine.set_graphql(Some(GraphqlRequest::new("{ shop { nope } }")));
+
+    let err = engine
+        .sync_stream(
+            "shop",
+            &server.uri(),
+            "/graphql",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &GraphqlDecoder::new("data.shop"),
+            &NoPaginator,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("doesn't exist on type"));
+}
+
+#[tokio::test]
+async fn test_sync_engine_max_records() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"id": 1}, {"id": 2}, {"id": 3}, {"id": 4}, {"id": 5}]
+        })))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+
+    let config = SyncConfig::new().with_max_records(3);
+    let mut engine = SyncEngine::new(client, state).with_config(config);
+
+    let decoder = JsonDecoder::with_path("items");
+    let paginator = NoPaginator;
+    let context = TemplateContext::new();
+
+    engine
+        .sync_stream(
+            "items",
+            &server.uri(),
+            "/api/items",
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &decoder,
+            &paginator,
+            &context,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stats = engine.stats();
+    assert_eq!(stats.records_synced, 3); // Limited to 3
+}
+
+#[tokio::test]
+async fn test_sync_engine_partitioned() {
+    let server = MockServer::start().await;
+
+    // Region A
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "us-east"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": 1, "region": "us-east"}]
+        })))
+        .mount(&server)
+        .await;
+
+    // Region B
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "us-west"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": 2, "region": "us-west"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let decoder = JsonDecoder::with_path("data");
+    let paginator = NoPaginator;
+    let router = ListRouter::new(vec!["us-east".to_string(), "us-west".to_string()], "region");
+
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+
+    let context = TemplateContext::new();
+
+    let messages = engine
+        .sync_partitioned_stream(
+            "regional_data",
+            &server.uri(),
+            "/api/data",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &decoder,
+            &paginator,
+            &router,
+            &context,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let record_msgs: Vec<_> = messages.iter().filter(|m| m.is_record()).collect();
+    assert_eq!(record_msgs.len(), 2); // One per partition
+
+    let stats = engine.stats();
+    assert_eq!(stats.partitions_synced, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_lookup_partitions() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/brands"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "brands": [{"id": 1, "subdomain": "acme"}, {"id": 2, "subdomain": "globex"}]
+        })))
+        .mount(&server)
+        .await;
+
+    for brand in ["1", "2"] {
+        Mock::given(method("GET"))
+            .and(path("/tickets"))
+            .and(query_param("brand_id", brand))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tickets": [{"id": format!("t{brand}")}]
+            })))
+            .mount(&server)
+            .await;
+    }
+
+    let config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+    let context = TemplateContext::new();
+
+    let brands = engine
+        .fetch_records(
+            "tickets",
+            &server.uri(),
+            Method::GET,
+            "/brands",
+            &std::collections::HashMap::new(),
+            None,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("brands"),
+            &context,
+        )
+        .await
+        .unwrap();
+    assert_eq!(brands.len(), 2);
+
+    let router = ListRouter::from_values(brands, "").with_fields(
+        [("brand_id".to_string(), "id".to_string())]
+            .into_iter()
+            .collect(),
+    );
+    let mut params = std::collections::HashMap::new();
+    params.insert(
+        "brand_id".to_string(),
+        "{{ partition.brand_id }}".to_string(),
+    );
+
+    engine
+        .sync_partitioned_stream(
+            "tickets",
+            &server.uri(),
+            "/tickets",
+            &params,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("tickets"),
+            &NoPaginator,
+            &router,
+            &context,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stats = engine.stats();
+    assert_eq!(stats.partitions_synced, 2);
+    assert_eq!(stats.records_synced, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_cartesian_partitions_resume() {
+    let server = MockServer::start().await;
+
+    // acct_1|eu was completed by an earlier run
+    for (account, region) in [("acct_1", "us"), ("acct_2", "us"), ("acct_2", "eu")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/accounts/{account}/usage")))
+            .and(query_param("region", region))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"account": account, "region": region}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    state
+        .mark_partition_completed("usage", "acct_1|eu")
+        .await
+        .unwrap();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let router = CartesianRouter::new(vec![
+        Box::new(ListRouter::new(
+            vec!["acct_1".to_string(), "acct_2".to_string()],
+            "account",
+        )),
+        Box::new(ListRouter::new(
+            vec!["us".to_string(), "eu".to_string()],
+            "region",
+        )),
+    ]);
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+
+    engine
+        .sync_partitioned_stream(
+            "usage",
+            &server.uri(),
+            "/accounts/{{ partition.account }}/usage",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("data"),
+            &NoPaginator,
+            &router,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stats = engine.stats();
+    assert_eq!(stats.partitions_synced, 3);
+    assert_eq!(stats.records_synced, 3);
+}
+
+#[tokio::test]
+async fn test_sync_engine_partition_cursors() {
+    let server = MockServer::start().await;
+
+    // First run: no stored cursors
+    for (repo, updated) in [("api", "2024-01-05"), ("web", "2024-02-01")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{repo}/comments")))
+            .and(query_param_is_missing("since"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "comments": [{"id": 1, "updated_at": "2024-01-01"}, {"id": 2, "updated_at": updated}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    // Second run: each partition resumes from its own cursor
+    for (repo, since) in [("api", "2024-01-05"), ("web", "2024-02-01")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{repo}/comments")))
+            .and(query_param("since", since))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "comments": [] })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let router = ListRouter::new(vec!["api".to_string(), "web".to_string()], "repo");
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("since".to_string(), "{{ state.cursor }}".to_string());
+
+    for _ in 0..2 {
+        let messages = engine
+            .sync_partitioned_stream(
+                "comments",
+                &server.uri(),
+                "/repos/{{ partition.repo }}/comments",
+                &query_params,
+                &std::collections::HashMap::new(),
+                &JsonDecoder::with_path("comments"),
+                &NoPaginator,
+                &router,
+                &TemplateContext::new(),
+                Some("updated_at"),
+            )
+            .await
+            .unwrap();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::State { data, .. } if data["partition"] == "web"
+        )));
+    }
+
+    let state = engine.state();
+    assert_eq!(
+        state.get_partition_cursor("comments", "api").await,
+        Some("2024-01-05".to_string())
+    );
+    assert_eq!(
+        state.get_partition_cursor("comments", "web").await,
+        Some("2024-02-01".to_string())
+    );
+    // The stream-level cursor is untouched
+    assert!(state.get_cursor("comments").await.is_none());
+    // Completed passes are re-visited next time
+    assert!(!state.is_partition_completed("comments", "api").await);
+    assert_eq!(engine.stats().partitions_synced, 4);
+}
+
+#[tokio::test]
+async fn test_sync_engine_interrupted_partitions_resume() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-a"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": 1}]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+
+    let mut engine =
+        SyncEngine::new(client, state).with_config(SyncConfig::new().with_fail_fast(false));
+
+    let router = ListRouter::new(
+        vec!["region-a".to_string(), "region-b".to_string()],
+        "region",
+    );
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+
+    engine
+        .sync_partitioned_stream(
+            "stream",
+            &server.uri(),
+            "/api/data",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("data"),
+            &NoPaginator,
+            &router,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The pass did not finish, so region-a stays completed for the next run
+    assert_eq!(engine.stats().errors, 1);
+    assert!(
+        engine
+            .state()
+            .is_partition_completed("stream", "region-a")
+            .await
+    );
+    assert!(
+        !engine
+            .state()
+            .is_partition_completed("stream", "region-b")
+            .await
+    );
+}
+
+#[tokio::test]
+async fn test_sync_engine_cursorless_partitions_stay_completed() {
+    let server = MockServer::start().await;
+
+    // Each day is fetched once across both runs
+    Mock::given(method("GET"))
+        .and(path("/daily"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": 1}]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    let router = DatetimeRouter::from_strings(
+        "2024-01-01T00:00:00Z",
+        "2024-01-03T00:00:00Z",
+        "1d",
+        "%Y-%m-%d",
+        "start",
+        "end",
+    )
+    .unwrap();
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("start".to_string(), "{{ partition.start }}".to_string());
+
+    for _ in 0..2 {
+        engine
+            .sync_partitioned_stream(
+                "daily",
+                &server.uri(),
+                "/daily",
+                &query_params,
+                &std::collections::HashMap::new(),
+                &JsonDecoder::with_path("data"),
+                &NoPaginator,
+                &router,
+                &TemplateContext::new(),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Without a cursor the second run has nothing to resume from
+    assert_eq!(engine.stats().partitions_synced, 2);
+    assert!(
+        engine
+            .state()
+            .is_partition_completed("daily", "0_2024-01-01")
+            .await
+    );
+}
+
+#[tokio::test]
+async fn test_sync_engine_collect_records() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "repos": [{"id": 1, "owner": {"login": "acme"}}],
+            "next": "p2"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos"))
+        .and(query_param("cursor", "p2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "repos": [{"id": 2, "owner": {"login": "acme"}}],
+            "next": null
+        })))
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    engine
+        .state()
+        .set_cursor("repos", "stale".to_string())
+        .await
+        .unwrap();
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("since".to_string(), "{{ state.cursor }}".to_string());
+
+    let records = engine
+        .collect_records(
+            "repos",
+            &server.uri(),
+            "/repos",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("repos"),
+            &CursorPaginator::new("cursor", "next", StopCondition::EmptyPage),
+            &TemplateContext::new(),
+        )
+        .await
+        .unwrap();
+
+    // Nested values are kept, and the records are not counted as synced
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1]["owner"]["login"], "acme");
+    assert_eq!(engine.stats().records_synced, 0);
+    assert_eq!(engine.stats().streams_synced, 0);
+
+    // The full parent stream is read regardless of its own cursor
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests
+        .iter()
+        .all(|r| !r.url.query().unwrap_or("").contains("since")));
+}
+
+fn region_engine(server: &MockServer, config: SyncConfig) -> SyncEngine {
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    SyncEngine::new(
+        HttpClient::with_config(http_config),
+        crate::state::StateManager::in_memory(),
+    )
+    .with_config(config)
+}
+
+async fn sync_regions(engine: &mut SyncEngine, server: &MockServer) -> Result<Vec<Message>> {
+    let router = ListRouter::new(
+        vec!["region-a".to_string(), "region-b".to_string()],
+        "region",
+    );
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+
+    engine
+        .sync_partitioned_stream(
+            "stream",
+            &server.uri(),
+            "/api/data",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("data"),
+            &NoPaginator,
+            &router,
+            &TemplateContext::new(),
+            None,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_sync_engine_partition_retry_succeeds() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-a"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"id": 1}]})))
+        .mount(&server)
+        .await;
+    // region-b fails once, then succeeds
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"id": 2}]})))
+        .mount(&server)
+        .await;
+
+    let config = SyncConfig::new().with_partition_retries(2, std::time::Duration::from_millis(1));
+    let mut engine = region_engine(&server, config);
+    sync_regions(&mut engine, &server).await.unwrap();
+
+    assert_eq!(engine.stats().records_synced, 2);
+    assert_eq!(engine.stats().partitions_synced, 2);
+    assert_eq!(engine.stats().partition_retries, 1);
+    assert_eq!(engine.stats().partitions_failed, 0);
+    assert!(engine.state().failed_partitions("stream").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_engine_failed_partitions_recorded() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-a"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"id": 1}]})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(3) // First attempt + 2 retries
+        .mount(&server)
+        .await;
+
+    let config = SyncConfig::new()
+        .with_fail_fast(false)
+        .with_partition_retries(2, std::time::Duration::from_millis(1));
+    let mut engine = region_engine(&server, config);
+    sync_regions(&mut engine, &server).await.unwrap();
+
+    assert_eq!(engine.stats().partitions_failed, 1);
+    assert_eq!(engine.stats().partition_retries, 2);
+    let failed = engine.state().failed_partitions("stream").await;
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, "region-b");
+    assert!(failed[0].1.contains("404"));
+}
+
+#[tokio::test]
+async fn test_sync_engine_failed_partitions_only() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-a"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"id": 1}]})))
+        .expect(0) // Did not fail last time
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": [{"id": 2}]})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut engine = region_engine(&server, SyncConfig::new().with_failed_partitions_only(true));
+    engine
+        .state()
+        .mark_partition_failed("stream", "region-b", "HTTP 500")
+        .await
+        .unwrap();
+    sync_regions(&mut engine, &server).await.unwrap();
+
+    assert_eq!(engine.stats().records_synced, 1);
+    assert!(engine.state().failed_partitions("stream").await.is_empty());
+}
+
+/// Mount a response for one adaptive date window
+async fn mount_window(server: &MockServer, start: &str, end: &str, body: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/api/events"))
+        .and(query_param("start_date", start))
+        .and(query_param("end_date", end))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+fn adaptive_query_params() -> std::collections::HashMap<String, String> {
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert(
+        "start_date".to_string(),
+        "{{ partition.start_date }}".to_string(),
+    );
+    query_params.insert(
+        "end_date".to_string(),
+        "{{ partition.end_date }}".to_string(),
+    );
+    query_params
+}
+
+fn adaptive_engine(server: &MockServer) -> SyncEngine {
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    SyncEngine::new(
+        HttpClient::with_config(http_config),
+        crate::state::StateManager::in_memory(),
+    )
+}
+
+async fn sync_adaptive(
+    engine: &mut SyncEngine,
+    server: &MockServer,
+    windows: AdaptiveWindows,
+) -> Vec<Message> {
+    engine
+        .sync_adaptive_stream(
+            "events",
+            &server.uri(),
+            "/api/events",
+            &adaptive_query_params(),
+            &std::collections::HashMap::new(),
+            &JsonDecoder::with_path("data"),
+            &NoPaginator,
+            windows,
+            &TemplateContext::new(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_sync_engine_adaptive_splits_capped_window() {
+    let server = MockServer::start().await;
+
+    // The full window hits the cap of 2 records
+    mount_window(
+        &server,
+        "2024-01-01",
+        "2024-01-05",
+        json!({"data": [{"id": 1}, {"id": 2}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-01",
+        "2024-01-03",
+        json!({"data": [{"id": 1}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-03",
+        "2024-01-05",
+        json!({"data": [{"id": 2}, {"id": 3}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-03",
+        "2024-01-04",
+        json!({"data": [{"id": 2}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-04",
+        "2024-01-05",
+        json!({"data": [{"id": 3}]}),
+    )
+    .await;
+
+    let mut engine = adaptive_engine(&server);
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-05", "4d", 2).unwrap();
+    let messages = sync_adaptive(&mut engine, &server, windows).await;
+
+    // Capped results are dropped and re-synced from the halves
+    assert_eq!(engine.stats().records_synced, 3);
+    assert_eq!(engine.stats().partitions_synced, 3);
+    let records: usize = messages
+        .iter()
+        .filter_map(|m| match m {
+            Message::Record { batch, .. } => Some(batch.num_rows()),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(records, 3);
+
+    // The chosen slices are saved and re-visited next run
+    let slices = engine.state().get_slices("events").await;
+    let bounds: Vec<_> = slices
+        .iter()
+        .map(|s| (s.start.as_str(), s.end.as_str(), s.records))
+        .collect();
+    assert_eq!(
+        bounds,
+        vec![
+            ("2024-01-01T00:00:00Z", "2024-01-03T00:00:00Z", 1),
+            ("2024-01-03T00:00:00Z", "2024-01-04T00:00:00Z", 1),
+            ("2024-01-04T00:00:00Z", "2024-01-05T00:00:00Z", 1),
+        ]
+    );
+    assert!(slices.iter().all(|s| !s.completed));
+}
+
+#[tokio::test]
+async fn test_sync_engine_adaptive_streams_only_kept_windows() {
+    use crate::partition::CompareOp;
+    use crate::transform::{TransformPipeline, Transformation};
+
+    let server = MockServer::start().await;
+    let mount = |start: &str, end: &str, body: &str| {
+        Mock::given(method("GET"))
+            .and(path("/api/events"))
+            .and(query_param("start_date", start))
+            .and(query_param("end_date", end))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+    };
+
+    // The full window reaches the cap of 2 decoded records, though the
+    // filter keeps only one of them
+    mount(
+        "2024-01-01",
+        "2024-01-05",
+        "{\"id\": 1}\n{\"id\": 2, \"status\": \"deleted\"}\n",
+    )
+    .mount(&server)
+    .await;
+    mount("2024-01-01", "2024-01-03", "{\"id\": 1}\n")
+        .mount(&server)
+        .await;
+    mount(
+        "2024-01-03",
+        "2024-01-05",
+        "{\"id\": 2, \"status\": \"deleted\"}\n{\"id\": 3}\n",
+    )
+    .mount(&server)
+    .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+    let mut engine = SyncEngine::new(client, state);
+    engine.set_transformations(TransformPipeline::new(vec![Transformation::Filter {
+        path: "status".to_string(),
+        op: CompareOp::Ne,
+        value: json!("deleted"),
+    }]));
+    let (output, mut streamed) = mpsc::channel(16);
+    engine.set_output(Some(output));
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-05", "4d", 2)
+        .unwrap()
+        .with_step_bounds("2d", "4d")
+        .unwrap();
+    let messages = engine
+        .sync_adaptive_stream(
+            "events",
+            &server.uri(),
+            "/api/events",
+            &adaptive_query_params(),
+            &std::collections::HashMap::new(),
+            &StreamDecoder::new(StreamFormat::Ndjson),
+            &NoPaginator,
+            windows,
+            &TemplateContext::new(),
+        )
+        .await
+        .unwrap();
+    engine.set_output(None);
+
+    // Records of the split window never reach the output
+    let mut ids = Vec::new();
+    while let Some(message) = streamed.recv().await {
+        if let Message::Record { stream, batch } = message {
+            assert_eq!(stream, "events");
+            let rows = crate::output::arrow_to_json(&batch).unwrap();
+            ids.extend(rows.into_iter().map(|row| row["id"].clone()));
+        }
+    }
+    ids.extend(messages.iter().filter_map(|m| match m {
+        Message::Record { .. } => Some(json!("buffered")),
+        _ => None,
+    }));
+    assert_eq!(ids, vec![json!(1), json!(3)]);
+    assert_eq!(engine.stats().records_synced, 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_adaptive_splits_on_total() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/events"))
+        .and(query_param("start_date", "2024-01-01"))
+        .and(query_param("end_date", "2024-01-03"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "meta": {"total": 500},
+            "data": [{"id": 1}]
+        })))
+        .expect(1) // Probe only
+        .mount(&server)
+        .await;
+    mount_window(
+        &server,
+        "2024-01-01",
+        "2024-01-02",
+        json!({"meta": {"total": 1}, "data": [{"id": 1}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-02",
+        "2024-01-03",
+        json!({"meta": {"total": 1}, "data": [{"id": 2}]}),
+    )
+    .await;
+
+    let mut engine = adaptive_engine(&server);
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-03", "2d", 100)
+        .unwrap()
+        .with_total_path("meta.total");
+    sync_adaptive(&mut engine, &server, windows).await;
+
+    assert_eq!(engine.stats().records_synced, 2);
+    assert_eq!(engine.state().get_slices("events").await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_sync_engine_adaptive_widens_sparse_windows() {
+    let server = MockServer::start().await;
+
+    mount_window(
+        &server,
+        "2024-01-01",
+        "2024-01-02",
+        json!({"data": [{"id": 1}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-02",
+        "2024-01-04",
+        json!({"data": [{"id": 2}]}),
+    )
+    .await;
+    mount_window(
+        &server,
+        "2024-01-04",
+        "2024-01-08",
+        json!({"data": [{"id": 3}]}),
+    )
+    .await;
+
+    let mut engine = adaptive_engine(&server);
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-08", "1d", 100)
+        .unwrap()
+        .with_step_bounds("1d", "4d")
+        .unwrap();
+    sync_adaptive(&mut engine, &server, windows).await;
+
+    assert_eq!(engine.stats().records_synced, 3);
+    assert_eq!(engine.stats().partitions_synced, 3);
+}
+
+#[tokio::test]
+async fn test_sync_engine_adaptive_resumes_stored_slices() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/events"))
+        .and(query_param("start_date", "2024-01-01"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": []})))
+        .expect(0) // Completed by the interrupted run
+        .mount(&server)
+        .await;
+    mount_window(
+        &server,
+        "2024-01-02",
+        "2024-01-05",
+        json!({"data": [{"id": 2}]}),
+    )
+    .await;
+
+    let mut engine = adaptive_engine(&server);
+    let slice = |start: &str, end: &str, completed| crate::state::DateSlice {
+        start: start.to_string(),
+        end: end.to_string(),
+        records: 1,
+        completed,
+        error: None,
+    };
+    engine
+        .state()
+        .set_slices(
+            "events",
+            vec![
+                slice("2024-01-01T00:00:00Z", "2024-01-02T00:00:00Z", true),
+                slice("2024-01-02T00:00:00Z", "2024-01-05T00:00:00Z", false),
+            ],
+        )
+        .await
+        .unwrap();
+
+    // The stored slicing wins over the configured step
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-05", "1d", 100).unwrap();
+    sync_adaptive(&mut engine, &server, windows).await;
+
+    assert_eq!(engine.stats().records_synced, 1);
+    assert_eq!(engine.stats().partitions_synced, 1);
+    let slices = engine.state().get_slices("events").await;
+    assert_eq!(slices.len(), 2);
+    assert!(slices.iter().all(|s| !s.completed));
+}
+
+#[tokio::test]
+async fn test_sync_engine_skips_completed_partitions() {
+    let server = MockServer::start().await;
+
+    // Only region-b should be called
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .and(query_param("region", "region-b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": 2}]
+        })))
+        .expect(1) // Should only be called once
+        .mount(&server)
+        .await;
+
+    let http_config = HttpClientConfig::builder()
+        .base_url(server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(http_config);
+    let state = crate::state::StateManager::in_memory();
+
+    // Mark region-a as completed
+    state
+        .mark_partition_completed("stream", "region-a")
+        .await
+        .unwrap();
+
+    let mut engine = SyncEngine::new(client, state);
+
+    let decoder = JsonDecoder::with_path("data");
+    let paginator = NoPaginator;
+    let router = ListRouter::new(
+        vec!["region-a".to_string(), "region-b".to_string()],
+        "region",
+    );
+
+    let mut query_params = std::collections::HashMap::new();
+    query_params.insert("region".to_string(), "{{ partition.region }}".to_string());
+
+    let context = TemplateContext::new();
+
+    engine
+        .sync_partitioned_stream(
+            "stream",
+            &server.uri(),
+            "/api/data",
+            &query_params,
+            &std::collections::HashMap::new(),
+            &decoder,
+            &paginator,
+            &router,
+            &context,
+            None,
         )
         .await
         .unwrap();
@@ -4,6 +4,7 @@
 
 use arrow::record_batch::RecordBatch;
 use serde_json::Value;
+use std::time::Duration;
 
 /// A message emitted during sync
 #[derive(Debug, Clone)]
@@ -61,11 +62,11 @@ impl Message {
         }
     }
 
-    /// Create a log message
+    /// Create a log message (known secrets are redacted)
     pub fn log(level: LogLevel, message: impl Into<String>) -> Self {
         Self::Log {
             level,
-            message: message.into(),
+            message: crate::secrets::redact::redact_str(&message.into()),
         }
     }
 
@@ -103,6 +104,16 @@ impl Message {
     pub fn is_log(&self) -> bool {
         matches!(self, Self::Log { .. })
     }
+
+    /// Stream, partition and event ID of a streamed-response checkpoint
+    pub fn event_checkpoint(&self) -> Option<(&str, Option<&str>, &str)> {
+        let Self::State { stream, data } = self else {
+            return None;
+        };
+        let event_id = data.get("event_id")?.as_str()?;
+        let partition = data.get("partition").and_then(Value::as_str);
+        Some((stream, partition, event_id))
+    }
 }
 
 /// Configuration for sync operation
@@ -116,6 +127,14 @@ pub struct SyncConfig {
     pub max_records: usize,
     /// Whether to fail fast on errors
     pub fail_fast: bool,
+    /// Times a failed partition is retried before it is recorded as failed
+    pub partition_retries: u32,
+    /// Delay before the first partition retry (doubles on each retry)
+    pub partition_backoff: Duration,
+    /// Whether to sync only partitions that failed in an earlier sync
+    pub failed_partitions_only: bool,
+    /// Rejected records allowed in a run before it fails (0 = unlimited)
+    pub max_rejected: usize,
 }
 
 impl Default for SyncConfig {
@@ -125,6 +144,10 @@ impl Default for SyncConfig {
             emit_state_per_page: false,
             max_records: 0,
             fail_fast: true,
+            partition_retries: 0,
+            partition_backoff: Duration::from_secs(1),
+            failed_partitions_only: false,
+            max_rejected: 0,
         }
     }
 }
@@ -163,6 +186,111 @@ impl SyncConfig {
         self.fail_fast = fail_fast;
         self
     }
+
+    /// Retry failed partitions with exponential backoff
+    #[must_use]
+    pub fn with_partition_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.partition_retries = retries;
+        self.partition_backoff = backoff;
+        self
+    }
+
+    /// Sync only partitions that failed in an earlier sync
+    #[must_use]
+    pub fn with_failed_partitions_only(mut self, only: bool) -> Self {
+        self.failed_partitions_only = only;
+        self
+    }
+
+    /// Set the decode error budget: quarantined records allowed per run
+    #[must_use]
+    pub fn with_max_rejected(mut self, max: usize) -> Self {
+        self.max_rejected = max;
+        self
+    }
+
+    /// Delay before a partition retry (`attempt` starts at 0)
+    pub fn partition_retry_delay(&self, attempt: u32) -> Duration {
+        self.partition_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(Duration::from_mins(1))
+    }
+}
+
+/// Action taken when a pagination safeguard triggers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitPolicy {
+    /// Stop paginating and keep the records fetched so far
+    Stop,
+    /// Stop paginating and log a warning
+    #[default]
+    Warn,
+    /// Fail the stream with a pagination error
+    Fail,
+}
+
+impl LimitPolicy {
+    /// Parse a policy name ("stop", "warn" or "fail")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "stop" => Some(Self::Stop),
+            "warn" => Some(Self::Warn),
+            "fail" => Some(Self::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// Per-stream pagination safeguards
+///
+/// Guards against APIs that keep returning a next page forever.
+#[derive(Debug, Clone, Copy)]
+pub struct PaginationLimits {
+    /// Maximum pages to fetch (0 = unlimited)
+    pub max_pages: usize,
+    /// Whether to detect repeated requests and repeated pages
+    pub detect_loops: bool,
+    /// Action taken when a limit is hit or a loop is detected
+    pub policy: LimitPolicy,
+}
+
+impl Default for PaginationLimits {
+    fn default() -> Self {
+        Self {
+            max_pages: 0,
+            detect_loops: true,
+            policy: LimitPolicy::default(),
+        }
+    }
+}
+
+impl PaginationLimits {
+    /// Create default pagination limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set max pages
+    #[must_use]
+    pub fn with_max_pages(mut self, max: usize) -> Self {
+        self.max_pages = max;
+        self
+    }
+
+    /// Enable or disable loop detection
+    #[must_use]
+    pub fn with_loop_detection(mut self, detect: bool) -> Self {
+        self.detect_loops = detect;
+        self
+    }
+
+    /// Set the policy
+    #[must_use]
+    pub fn with_policy(mut self, policy: LimitPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
 }
 
 /// Statistics from a sync operation
@@ -170,14 +298,26 @@ impl SyncConfig {
 pub struct SyncStats {
     /// Total records synced
     pub records_synced: usize,
+    /// Records quarantined because they failed to decode
+    pub records_rejected: usize,
     /// Total pages fetched
     pub pages_fetched: usize,
     /// Total streams synced
     pub streams_synced: usize,
     /// Total partitions synced
     pub partitions_synced: usize,
+    /// Partitions that failed after all retries
+    pub partitions_failed: usize,
+    /// Partition retries performed
+    pub partition_retries: usize,
     /// Errors encountered
     pub errors: usize,
+    /// Times pagination stopped at `max_pages`
+    pub max_pages_reached: usize,
+    /// Repeated pagination requests detected
+    pub repeated_cursors: usize,
+    /// Repeated response pages detected
+    pub repeated_pages: usize,
     /// Duration in milliseconds
     pub duration_ms: u64,
 }
@@ -193,6 +333,11 @@ impl SyncStats {
         self.records_synced += count;
     }
 
+    /// Add quarantined records
+    pub fn add_rejected(&mut self, count: usize) {
+        self.records_rejected += count;
+    }
+
     /// Add a page
     pub fn add_page(&mut self) {
         self.pages_fetched += 1;
@@ -208,11 +353,41 @@ impl SyncStats {
         self.partitions_synced += 1;
     }
 
+    /// Record a partition that failed after all retries
+    pub fn add_partition_failed(&mut self) {
+        self.partitions_failed += 1;
+    }
+
+    /// Record a partition retry
+    pub fn add_partition_retry(&mut self) {
+        self.partition_retries += 1;
+    }
+
     /// Add an error
     pub fn add_error(&mut self) {
         self.errors += 1;
     }
 
+    /// Record that pagination reached `max_pages`
+    pub fn add_max_pages_reached(&mut self) {
+        self.max_pages_reached += 1;
+    }
+
+    /// Record a repeated pagination request
+    pub fn add_repeated_cursor(&mut self) {
+        self.repeated_cursors += 1;
+    }
+
+    /// Record a repeated response page
+    pub fn add_repeated_page(&mut self) {
+        self.repeated_pages += 1;
+    }
+
+    /// Total pagination safeguard events
+    pub fn pagination_events(&self) -> usize {
+        self.max_pages_reached + self.repeated_cursors + self.repeated_pages
+    }
+
     /// Set duration
     pub fn set_duration(&mut self, ms: u64) {
         self.duration_ms = ms;
@@ -80,6 +80,9 @@ pub enum Error {
     #[error("XML parsing error: {message}")]
     XmlParse { message: String },
 
+    #[error("Pagination error for stream '{stream}': {message}")]
+    Pagination { stream: String, message: String },
+
     // ============================================================================
     // Arrow/Parquet Errors
     // ============================================================================
@@ -125,6 +128,12 @@ pub enum Error {
     #[error("Undefined variable in template: {variable}")]
     UndefinedVariable { variable: String },
 
+    // ============================================================================
+    // Secret Errors
+    // ============================================================================
+    #[error("Secret resolution failed: {message}")]
+    Secret { message: String },
+
     // ============================================================================
     // I/O Errors
     // ============================================================================
@@ -188,6 +197,14 @@ impl Error {
         }
     }
 
+    /// Create a pagination error
+    pub fn pagination(stream: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Pagination {
+            stream: stream.into(),
+            message: message.into(),
+        }
+    }
+
     /// Create a template error
     pub fn template(message: impl Into<String>) -> Self {
         Self::Template {
@@ -202,6 +219,13 @@ impl Error {
         }
     }
 
+    /// Create a secret resolution error
+    pub fn secret(message: impl Into<String>) -> Self {
+        Self::Secret {
+            message: message.into(),
+        }
+    }
+
     /// Create a state error
     pub fn state(message: impl Into<String>) -> Self {
         Self::State {
@@ -288,6 +312,12 @@ mod tests {
 
         let err = Error::http_status(404, "Not found");
         assert_eq!(err.to_string(), "HTTP 404: Not found");
+
+        let err = Error::pagination("orders", "key did not advance");
+        assert_eq!(
+            err.to_string(),
+            "Pagination error for stream 'orders': key did not advance"
+        );
     }
 
     #[test]
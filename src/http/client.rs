@@ -9,11 +9,13 @@
 use super::rate_limit::{RateLimiter, RateLimiterConfig};
 use crate::auth::{AuthConfig, Authenticator};
 use crate::error::{Error, Result};
+use crate::secrets::redact;
 use crate::types::BackoffType;
 use reqwest::{Client, Method, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, warn};
 
@@ -187,8 +189,8 @@ impl RequestConfig {
 pub struct HttpClient {
     client: Client,
     config: HttpClientConfig,
-    authenticator: Option<Authenticator>,
-    rate_limiter: Option<RateLimiter>,
+    authenticator: Option<Arc<Authenticator>>,
+    rate_limiter: RateLimiter,
 }
 
 impl HttpClient {
@@ -205,7 +207,10 @@ impl HttpClient {
             .build()
             .expect("Failed to build HTTP client");
 
-        let rate_limiter = config.rate_limit.as_ref().map(RateLimiter::new);
+        let rate_limiter = config
+            .rate_limit
+            .as_ref()
+            .map_or_else(RateLimiter::unlimited, RateLimiter::new);
 
         Self {
             client,
@@ -218,16 +223,29 @@ impl HttpClient {
     /// Create a client with authentication
     pub fn with_auth(config: HttpClientConfig, auth_config: AuthConfig) -> Self {
         let mut client = Self::with_config(config);
-        client.authenticator = Some(Authenticator::with_client(
+        client.authenticator = Some(Arc::new(Authenticator::with_client(
             auth_config,
             client.client.clone(),
-        ));
+        )));
         client
     }
 
     /// Set the authenticator
     pub fn set_authenticator(&mut self, auth_config: AuthConfig) {
-        self.authenticator = Some(Authenticator::with_client(auth_config, self.client.clone()));
+        self.authenticator = Some(Arc::new(Authenticator::with_client(
+            auth_config,
+            self.client.clone(),
+        )));
+    }
+
+    /// Use a shared authenticator (e.g., one auth profile used by several streams)
+    pub fn set_shared_authenticator(&mut self, authenticator: Option<Arc<Authenticator>>) {
+        self.authenticator = authenticator;
+    }
+
+    /// Get the current authenticator
+    pub fn authenticator(&self) -> Option<&Arc<Authenticator>> {
+        self.authenticator.as_ref()
     }
 
     /// Get the underlying reqwest client
@@ -271,12 +289,12 @@ impl HttpClient {
 
         let mut last_error = None;
         let mut attempt = 0;
+        let mut reauthenticated = false;
+        let mut auth_generation = 0;
 
         while attempt <= max_retries {
-            // Wait for rate limiter
-            if let Some(ref limiter) = self.rate_limiter {
-                limiter.wait().await;
-            }
+            // Wait for rate limiter (including pauses for API-reported costs)
+            self.rate_limiter.wait().await;
 
             // Build request
             let mut req = self.client.request(method.clone(), &full_url);
@@ -306,7 +324,7 @@ impl HttpClient {
 
             // Apply authentication
             if let Some(ref auth) = self.authenticator {
-                req = auth.apply(req).await?;
+                (req, auth_generation) = auth.apply(req).await?;
             }
 
             // Send request
@@ -314,6 +332,21 @@ impl HttpClient {
                 Ok(response) => {
                     let status = response.status();
 
+                    // Keep session cookies current and re-authenticate once if rejected.
+                    // Concurrent requests rejected with the same credentials share one refresh.
+                    if let Some(ref auth) = self.authenticator {
+                        auth.observe_response(response.headers()).await;
+                        if !reauthenticated && auth.should_reauthenticate(status.as_u16()) {
+                            warn!(
+                                "Request rejected with {}, re-authenticating",
+                                status.as_u16()
+                            );
+                            auth.invalidate(auth_generation).await;
+                            reauthenticated = true;
+                            continue;
+                        }
+                    }
+
                     // Check for rate limiting
                     if status == StatusCode::TOO_MANY_REQUESTS {
                         let retry_after = extract_retry_after(&response);
@@ -354,7 +387,7 @@ impl HttpClient {
 
                     // Check for client errors (non-retryable)
                     if status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS {
-                        let body = response.text().await.unwrap_or_default();
+                        let body = redact::redact_str(&response.text().await.unwrap_or_default());
                         return Err(Error::HttpStatus {
                             status: status.as_u16(),
                             body,
@@ -363,14 +396,18 @@ impl HttpClient {
 
                     // Check for server errors (after retries exhausted)
                     if status.is_server_error() {
-                        let body = response.text().await.unwrap_or_default();
+                        let body = redact::redact_str(&response.text().await.unwrap_or_default());
                         return Err(Error::HttpStatus {
                             status: status.as_u16(),
                             body,
                         });
                     }
 
-                    debug!("Request succeeded: {} {}", method, full_url);
+                    debug!(
+                        "Request succeeded: {} {}",
+                        method,
+                        redact::redact_str(&full_url)
+                    );
                     return Ok(response);
                 }
                 Err(e) => {
@@ -451,7 +488,14 @@ impl HttpClient {
 
     /// Check if rate limiting is enabled
     pub fn has_rate_limiter(&self) -> bool {
-        self.rate_limiter.is_some()
+        self.rate_limiter.has_quota()
+    }
+
+    /// Rate limiter gating this client's requests
+    ///
+    /// API-reported costs are charged here (see [`RateLimiter::pause`]).
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
     }
 
     /// Build full URL from path
@@ -496,7 +540,7 @@ impl std::fmt::Debug for HttpClient {
         f.debug_struct("HttpClient")
             .field("config", &self.config)
             .field("has_authenticator", &self.authenticator.is_some())
-            .field("has_rate_limiter", &self.rate_limiter.is_some())
+            .field("has_rate_limiter", &self.rate_limiter.has_quota())
             .finish_non_exhaustive()
     }
 }
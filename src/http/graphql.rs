@@ -0,0 +1,127 @@
+//! GraphQL requests
+//!
+//! Builds GraphQL request bodies from templated queries and variables,
+//! surfaces GraphQL errors returned with a 200 status, and reads query cost
+//! reports (e.g., Shopify `extensions.cost`) to throttle the next request.
+
+use crate::error::{Error, Result};
+use crate::template::{self, TemplateContext};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::warn;
+
+/// A GraphQL query sent as a JSON POST body
+#[derive(Debug, Clone)]
+pub struct GraphqlRequest {
+    /// Query document (template)
+    pub query: String,
+    /// Query variables (string values are templates)
+    pub variables: Value,
+    /// JSONPath to the query cost report in responses (e.g., "extensions.cost")
+    pub cost_path: Option<String>,
+}
+
+impl GraphqlRequest {
+    /// Create a new GraphQL request
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            variables: Value::Object(serde_json::Map::new()),
+            cost_path: None,
+        }
+    }
+
+    /// Set the query variables
+    #[must_use]
+    pub fn with_variables(mut self, variables: Value) -> Self {
+        if !variables.is_null() {
+            self.variables = variables;
+        }
+        self
+    }
+
+    /// Set the path to the query cost report
+    #[must_use]
+    pub fn with_cost_path(mut self, path: impl Into<String>) -> Self {
+        self.cost_path = Some(path.into());
+        self
+    }
+
+    /// Render the query and variables into a request body
+    pub fn render_body(&self, ctx: &TemplateContext) -> Result<Value> {
+        Ok(json!({
+            "query": template::render(&self.query, ctx)?,
+            "variables": template::render_value(&self.variables, ctx)?,
+        }))
+    }
+
+    /// Check a response for GraphQL errors
+    ///
+    /// Fails when the response carries errors and no data; errors alongside
+    /// partial data are logged.
+    pub fn check_errors(response: &Value) -> Result<()> {
+        let Some(errors) = response.get("errors").and_then(Value::as_array) else {
+            return Ok(());
+        };
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        let messages = errors
+            .iter()
+            .map(|e| {
+                e.get("message")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| e.to_string(), ToString::to_string)
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if response.get("data").is_none_or(Value::is_null) {
+            return Err(Error::decode(format!("GraphQL errors: {messages}")));
+        }
+        warn!("GraphQL response returned partial data with errors: {messages}");
+        Ok(())
+    }
+
+    /// Delay to wait before the next request, based on the reported query cost
+    pub fn throttle_delay(&self, response: &Value) -> Option<Duration> {
+        let path = self.cost_path.as_deref()?;
+        let cost = crate::pagination::extract_jsonpath_value(response, path)?;
+        QueryCost::from_value(&cost).map(|c| c.delay())
+    }
+}
+
+/// Query cost reported by a GraphQL API
+///
+/// Follows the Shopify shape:
+/// `{ "requestedQueryCost": 52, "throttleStatus": { "currentlyAvailable": 10, "restoreRate": 50 } }`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryCost {
+    /// Cost requested by the last query
+    pub requested: f64,
+    /// Points currently available in the bucket
+    pub available: f64,
+    /// Points restored per second
+    pub restore_rate: f64,
+}
+
+impl QueryCost {
+    /// Parse a cost report
+    pub fn from_value(cost: &Value) -> Option<Self> {
+        let throttle = cost.get("throttleStatus")?;
+        Some(Self {
+            requested: cost.get("requestedQueryCost")?.as_f64()?,
+            available: throttle.get("currentlyAvailable")?.as_f64()?,
+            restore_rate: throttle.get("restoreRate")?.as_f64()?,
+        })
+    }
+
+    /// Time until the bucket can afford another query of the same cost
+    pub fn delay(&self) -> Duration {
+        if self.available >= self.requested || self.restore_rate <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64((self.requested - self.available) / self.restore_rate)
+    }
+}
@@ -8,11 +8,14 @@
 //! - **Rate Limiting**: Token bucket rate limiter using governor
 //! - **Backoff Strategies**: Constant, linear, and exponential backoff
 //! - **Authentication**: Integration with auth module
+//! - **GraphQL**: Templated query bodies and query cost throttling
 
 mod client;
+mod graphql;
 mod rate_limit;
 
 pub use client::{HttpClient, HttpClientConfig, RequestConfig};
+pub use graphql::{GraphqlRequest, QueryCost};
 pub use rate_limit::{RateLimiter, RateLimiterConfig};
 
 #[cfg(test)]
@@ -1,14 +1,18 @@
 //! Rate limiting implementation
 //!
-//! Uses the governor crate for token bucket rate limiting.
+//! Uses the governor crate for token bucket rate limiting. APIs that report
+//! their own budget (e.g., a GraphQL query cost) pause the limiter until the
+//! budget restores.
 
 use governor::clock::DefaultClock;
 use governor::middleware::NoOpMiddleware;
 use governor::state::{InMemoryState, NotKeyed};
 use governor::{Quota, RateLimiter as Governor};
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
 
 /// Configuration for rate limiting
 #[derive(Debug, Clone)]
@@ -55,9 +59,15 @@ impl RateLimiterConfig {
 }
 
 /// Token bucket rate limiter
+///
+/// Clones share the bucket and any pause, so every client holding the
+/// limiter sees a cost reported to one of them.
 #[derive(Clone)]
 pub struct RateLimiter {
-    limiter: Arc<Governor<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>,
+    /// Request quota (none for a limiter that only honors pauses)
+    limiter: Option<Arc<Governor<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>>,
+    /// Earliest time the next permit may be granted (set by `pause`)
+    paused_until: Arc<Mutex<Option<Instant>>>,
 }
 
 impl RateLimiter {
@@ -69,7 +79,18 @@ impl RateLimiter {
         .allow_burst(NonZeroU32::new(config.burst_size).unwrap_or(NonZeroU32::new(1).unwrap()));
 
         Self {
-            limiter: Arc::new(Governor::direct(quota)),
+            limiter: Some(Arc::new(Governor::direct(quota))),
+            paused_until: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Create a limiter without a request quota
+    ///
+    /// Permits are only held back while the limiter is paused.
+    pub fn unlimited() -> Self {
+        Self {
+            limiter: None,
+            paused_until: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -78,26 +99,60 @@ impl RateLimiter {
         Self::new(&RateLimiterConfig::default())
     }
 
+    /// Check if the limiter has a request quota
+    pub fn has_quota(&self) -> bool {
+        self.limiter.is_some()
+    }
+
+    /// Hold back every permit for `delay`
+    ///
+    /// Used when an API reports its own budget, such as a GraphQL query cost
+    /// bucket that needs time to restore. Pauses never shorten an earlier one.
+    pub fn pause(&self, delay: Duration) {
+        if delay.is_zero() {
+            return;
+        }
+        let until = Instant::now() + delay;
+        let mut paused_until = self.paused_until.lock().unwrap();
+        if paused_until.is_none_or(|current| current < until) {
+            *paused_until = Some(until);
+        }
+    }
+
+    /// End of the current pause, if one is in effect
+    fn remaining_pause(&self) -> Option<Instant> {
+        let until = (*self.paused_until.lock().unwrap())?;
+        (until > Instant::now()).then_some(until)
+    }
+
     /// Wait until a request can be made (blocks)
     pub async fn wait(&self) {
-        self.limiter.until_ready().await;
+        if let Some(until) = self.remaining_pause() {
+            debug!("Rate limiter paused for {:?}", until - Instant::now());
+            tokio::time::sleep_until(until).await;
+        }
+        if let Some(limiter) = &self.limiter {
+            limiter.until_ready().await;
+        }
     }
 
     /// Check if a request can be made immediately
     pub fn check(&self) -> bool {
-        self.limiter.check().is_ok()
+        self.try_acquire()
     }
 
     /// Try to acquire a permit, returning immediately
     pub fn try_acquire(&self) -> bool {
-        self.limiter.check().is_ok()
+        self.remaining_pause().is_none()
+            && self
+                .limiter
+                .as_ref()
+                .is_none_or(|limiter| limiter.check().is_ok())
     }
 
     /// Wait with a timeout
     pub async fn wait_with_timeout(&self, timeout: Duration) -> bool {
-        tokio::time::timeout(timeout, self.limiter.until_ready())
-            .await
-            .is_ok()
+        tokio::time::timeout(timeout, self.wait()).await.is_ok()
     }
 }
 
@@ -158,6 +213,25 @@ mod rate_limit_tests {
         limiter.wait().await;
     }
 
+    #[tokio::test]
+    async fn test_rate_limiter_pause_is_shared() {
+        let limiter = RateLimiter::unlimited();
+        let shared = limiter.clone();
+        assert!(!limiter.has_quota());
+        assert!(shared.try_acquire());
+
+        // A pause reported to one holder holds back the others
+        limiter.pause(Duration::from_millis(200));
+        // A shorter pause does not cut the earlier one short
+        limiter.pause(Duration::from_millis(10));
+        assert!(!shared.try_acquire());
+
+        let start = Instant::now();
+        shared.wait().await;
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(shared.try_acquire());
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_wait_with_timeout() {
         let limiter = RateLimiter::new(&RateLimiterConfig::new(100, 10));
@@ -475,3 +475,264 @@ fn test_http_client_default() {
     // Should have rate limiter by default
     assert!(client.has_rate_limiter());
 }
+
+#[tokio::test]
+async fn test_http_client_cookie_session_relogin() {
+    use crate::auth::{AuthConfig, Authenticator};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let mock_server = MockServer::start().await;
+
+    // First login hands out a session that the API has already expired
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200).append_header("Set-Cookie", "sid=stale"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200).append_header("Set-Cookie", "sid=fresh"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .and(header("Cookie", "sid=stale"))
+        .respond_with(ResponseTemplate::new(419))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .and(header("Cookie", "sid=fresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(mock_server.uri())
+        .no_rate_limit()
+        .build();
+    let mut client = HttpClient::with_config(config);
+    client.set_shared_authenticator(Some(Arc::new(Authenticator::new(
+        AuthConfig::CookieSession {
+            login_url: format!("{}/login", mock_server.uri()),
+            login_method: reqwest::Method::POST,
+            login_body: HashMap::new(),
+            login_form: false,
+            csrf: None,
+            relogin_statuses: vec![401, 419],
+        },
+    ))));
+
+    let response = client.get("/orders").await.unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_http_client_401_refreshes_token_once() {
+    use crate::auth::{AuthConfig, Authenticator};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let mock_server = MockServer::start().await;
+
+    // The first token is revoked server-side before it expires
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "revoked",
+            "expires_in": 3600
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "valid",
+            "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("Authorization", "Bearer revoked"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("Authorization", "Bearer valid"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(mock_server.uri())
+        .no_rate_limit()
+        .build();
+    let mut client = HttpClient::with_config(config);
+    let auth = Arc::new(Authenticator::new(AuthConfig::Oauth2ClientCredentials {
+        token_url: format!("{}/oauth/token", mock_server.uri()),
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        scopes: vec![],
+        token_body: HashMap::new(),
+    }));
+    // Fetch the (revoked) token up front so every worker starts with it
+    let request = auth
+        .apply(reqwest::Client::new().get(mock_server.uri()))
+        .await
+        .unwrap()
+        .0
+        .build()
+        .unwrap();
+    assert_eq!(request.headers()["Authorization"], "Bearer revoked");
+    client.set_shared_authenticator(Some(auth));
+
+    // Concurrent workers all get a 401 but share a single refresh
+    let responses = futures::future::join_all((0..4).map(|_| client.get("/items"))).await;
+    for response in responses {
+        assert_eq!(response.unwrap().status(), 200);
+    }
+}
+
+#[tokio::test]
+async fn test_http_client_static_auth_401_is_not_retried() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(mock_server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_auth(
+        config,
+        crate::auth::AuthConfig::Bearer {
+            token: "static-token".to_string(),
+        },
+    );
+
+    let err = client.get("/items").await.unwrap_err();
+    assert!(matches!(
+        err,
+        crate::error::Error::HttpStatus { status: 401, .. }
+    ));
+}
+
+// ============================================================================
+// GraphQL Tests
+// ============================================================================
+
+#[test]
+fn test_graphql_render_body() {
+    let request = GraphqlRequest::new("query($first: Int, $shop: String) { orders { id } }")
+        .with_variables(serde_json::json!({"first": 50, "shop": "{{ config.shop }}"}));
+    let ctx = crate::template::TemplateContext::with_config(serde_json::json!({"shop": "acme"}));
+
+    let body = request.render_body(&ctx).unwrap();
+    assert_eq!(
+        body["query"],
+        "query($first: Int, $shop: String) { orders { id } }"
+    );
+    assert_eq!(
+        body["variables"],
+        serde_json::json!({"first": 50, "shop": "acme"})
+    );
+
+    // Missing variables become an empty object
+    let body = GraphqlRequest::new("{ shop { id } }")
+        .render_body(&ctx)
+        .unwrap();
+    assert_eq!(body["variables"], serde_json::json!({}));
+}
+
+#[test]
+fn test_graphql_check_errors() {
+    assert!(GraphqlRequest::check_errors(&serde_json::json!({"data": {"shop": {}}})).is_ok());
+
+    let err = GraphqlRequest::check_errors(&serde_json::json!({
+        "data": null,
+        "errors": [{"message": "Throttled"}, {"message": "Field 'x' doesn't exist"}]
+    }))
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Throttled; Field 'x' doesn't exist"));
+
+    // Partial data is kept
+    assert!(GraphqlRequest::check_errors(&serde_json::json!({
+        "data": {"shop": {}},
+        "errors": [{"message": "partial"}]
+    }))
+    .is_ok());
+}
+
+#[test]
+fn test_query_cost_delay() {
+    let response = serde_json::json!({
+        "data": {},
+        "extensions": {"cost": {
+            "requestedQueryCost": 102,
+            "actualQueryCost": 52,
+            "throttleStatus": {"maximumAvailable": 1000, "currentlyAvailable": 2, "restoreRate": 50}
+        }}
+    });
+
+    let request = GraphqlRequest::new("{ shop { id } }").with_cost_path("extensions.cost");
+    assert_eq!(
+        request.throttle_delay(&response),
+        Some(Duration::from_secs(2))
+    );
+
+    // Enough budget left
+    let cost = QueryCost {
+        requested: 10.0,
+        available: 500.0,
+        restore_rate: 50.0,
+    };
+    assert_eq!(cost.delay(), Duration::ZERO);
+
+    // No cost path configured
+    assert!(GraphqlRequest::new("{ shop { id } }")
+        .throttle_delay(&response)
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_http_client_rate_limiter_pause() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = HttpClientConfig::builder()
+        .base_url(mock_server.uri())
+        .no_rate_limit()
+        .build();
+    let client = HttpClient::with_config(config);
+
+    // Costs are charged even without a request quota
+    assert!(!client.has_rate_limiter());
+    let start = std::time::Instant::now();
+    client.rate_limiter().pause(Duration::from_millis(300));
+
+    client.get("/items").await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
@@ -0,0 +1,25 @@
+//! JSONPath module
+//!
+//! Supports: Dot paths, Bracket keys, Indices (including negative), Wildcards,
+//! Slices, Unions, Filters, Recursive descent
+//!
+//! # Overview
+//!
+//! One JSONPath implementation is shared by record extraction, paginators,
+//! stop conditions, auth token extraction and partition routers, so a path
+//! means the same thing wherever it appears in a connector:
+//!
+//! - `data.items`, `$.data.items` and `$['data']['items']` are equivalent
+//! - `data[0]` and `data[-1]` index arrays (negative counts from the end)
+//! - `data[*]`, `$..id`, `data[0:2]` and `data[?(@.type == 'invoice')]`
+//!   select several values
+//!
+//! Definite paths (keys and indices only) are walked directly; other paths
+//! are evaluated with `jsonpath-rust`.
+
+mod path;
+
+pub use path::{select, select_first, JsonPath};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,216 @@
+//! JSONPath parsing and evaluation
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// A parsed JSONPath expression
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    /// Expression as written
+    path: String,
+    /// Parsed form
+    kind: PathKind,
+}
+
+#[derive(Debug, Clone)]
+enum PathKind {
+    /// Keys and indices only; selects at most one value
+    Definite(Vec<Segment>),
+    /// Wildcards, slices, unions, filters or recursive descent
+    Query(jsonpath_rust::JsonPath<Value>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Object key
+    Key(String),
+    /// Array index (negative counts from the end)
+    Index(i64),
+}
+
+impl JsonPath {
+    /// Parse a JSONPath expression
+    ///
+    /// The leading `$` is optional: `data.items` is read as `$.data.items`.
+    pub fn parse(path: &str) -> Result<Self> {
+        let kind = if let Some(segments) = parse_definite(path.trim()) {
+            PathKind::Definite(segments)
+        } else {
+            let query = jsonpath_rust::JsonPath::try_from(normalize(path.trim()).as_str())
+                .map_err(|e| Error::json_path(format!("Invalid JSONPath '{path}': {e}")))?;
+            PathKind::Query(query)
+        };
+        Ok(Self {
+            path: path.to_string(),
+            kind,
+        })
+    }
+
+    /// The expression as written
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether the path selects at most one value (keys and indices only)
+    pub fn is_definite(&self) -> bool {
+        matches!(self.kind, PathKind::Definite(_))
+    }
+
+    /// Find all values matching the path
+    pub fn find(&self, value: &Value) -> Vec<Value> {
+        match &self.kind {
+            PathKind::Definite(segments) => walk(value, segments).cloned().into_iter().collect(),
+            PathKind::Query(query) => query
+                .find_slice_ptr(value)
+                .into_iter()
+                .map(|found| match found {
+                    jsonpath_rust::JsonPtr::Slice(v) => v.clone(),
+                    jsonpath_rust::JsonPtr::NewValue(v) => v,
+                })
+                .collect(),
+        }
+    }
+
+    /// Find the first value matching the path
+    pub fn find_first(&self, value: &Value) -> Option<Value> {
+        match &self.kind {
+            PathKind::Definite(segments) => walk(value, segments).cloned(),
+            PathKind::Query(_) => self.find(value).into_iter().next(),
+        }
+    }
+
+    /// Select records
+    ///
+    /// A definite path pointing at an array yields the array's elements;
+    /// any other path yields each matching value as a record.
+    pub fn records(&self, value: &Value) -> Vec<Value> {
+        match &self.kind {
+            PathKind::Definite(segments) => match walk(value, segments) {
+                Some(Value::Array(arr)) => arr.clone(),
+                Some(v) => vec![v.clone()],
+                None => vec![],
+            },
+            PathKind::Query(_) => self.find(value),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+/// Find all values matching a path
+pub fn select(value: &Value, path: &str) -> Result<Vec<Value>> {
+    Ok(JsonPath::parse(path)?.find(value))
+}
+
+/// Find the first value matching a path (`None` for invalid paths)
+pub fn select_first(value: &Value, path: &str) -> Option<Value> {
+    JsonPath::parse(path).ok()?.find_first(value)
+}
+
+/// Parse a path made only of keys and indices
+///
+/// Returns `None` when the path needs the full JSONPath evaluator.
+fn parse_definite(path: &str) -> Option<Vec<Segment>> {
+    let (mut rest, mut leading_key) = match path.strip_prefix('$') {
+        Some(rest) => (rest, false),
+        None => (path, true),
+    };
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(bracket) = rest.strip_prefix('[') {
+            let end = bracket.find(']')?;
+            segments.push(parse_bracket(bracket[..end].trim())?);
+            rest = &bracket[end + 1..];
+        } else {
+            let dotted = match rest.strip_prefix('.') {
+                Some(dotted) => dotted,
+                None if leading_key => rest,
+                None => return None,
+            };
+            let end = dotted.find(['.', '[']).unwrap_or(dotted.len());
+            let key = &dotted[..end];
+            if key.is_empty() || key == "*" || key.contains(['(', ')', '@']) {
+                return None;
+            }
+            segments.push(Segment::Key(key.to_string()));
+            rest = &dotted[end..];
+        }
+        leading_key = false;
+    }
+    Some(segments)
+}
+
+/// Parse a bracket selector that addresses a single key or index
+fn parse_bracket(inner: &str) -> Option<Segment> {
+    for quote in ['\'', '"'] {
+        if let Some(key) = inner
+            .strip_prefix(quote)
+            .and_then(|key| key.strip_suffix(quote))
+        {
+            return (!key.contains(quote)).then(|| Segment::Key(key.to_string()));
+        }
+    }
+    inner.parse::<i64>().ok().map(Segment::Index)
+}
+
+/// Walk a definite path
+fn walk<'a>(value: &'a Value, segments: &[Segment]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| match segment {
+            Segment::Key(key) => current.as_object()?.get(key),
+            Segment::Index(index) => {
+                let arr = current.as_array()?;
+                let index = if *index < 0 {
+                    arr.len()
+                        .checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?
+                } else {
+                    usize::try_from(*index).ok()?
+                };
+                arr.get(index)
+            }
+        })
+}
+
+/// Root a path at `$` and rewrite negative indices as slices for `jsonpath-rust`
+fn normalize(path: &str) -> String {
+    let rooted = if path.starts_with('$') {
+        path.to_string()
+    } else if path.starts_with(['.', '[']) {
+        format!("${path}")
+    } else {
+        format!("$.{path}")
+    };
+
+    let mut normalized = String::with_capacity(rooted.len());
+    let mut rest = rooted.as_str();
+    while let Some(pos) = rest.find("[-") {
+        normalized.push_str(&rest[..pos]);
+        let after = &rest[pos + 2..];
+        let digits = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        match after[..digits].parse::<u64>() {
+            Ok(n) if n > 0 && after[digits..].starts_with(']') => {
+                if n == 1 {
+                    normalized.push_str("[-1:]");
+                } else {
+                    let _ = write!(normalized, "[-{n}:-{}]", n - 1);
+                }
+                rest = &after[digits + 1..];
+            }
+            _ => {
+                normalized.push_str("[-");
+                rest = after;
+            }
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
@@ -0,0 +1,144 @@
+//! Tests for jsonpath module
+
+use super::*;
+use serde_json::json;
+
+fn document() -> serde_json::Value {
+    json!({
+        "data": [
+            {"id": 1, "type": "invoice", "amount": 10},
+            {"id": 2, "type": "refund", "amount": 5},
+            {"id": 3, "type": "invoice", "amount": 30}
+        ],
+        "included": [{"id": "c1", "type": "customer"}],
+        "meta": {"next": {"cursor": "abc"}, "has-more": true}
+    })
+}
+
+// ============================================================================
+// Definite Paths
+// ============================================================================
+
+#[test]
+fn test_definite_path_forms_are_equivalent() {
+    let doc = document();
+    for path in [
+        "meta.next.cursor",
+        "$.meta.next.cursor",
+        "$['meta']['next']['cursor']",
+        "meta[\"next\"].cursor",
+    ] {
+        let parsed = JsonPath::parse(path).unwrap();
+        assert!(parsed.is_definite(), "{path}");
+        assert_eq!(parsed.find_first(&doc), Some(json!("abc")), "{path}");
+    }
+    assert_eq!(select_first(&doc, "meta.has-more"), Some(json!(true)));
+}
+
+#[test]
+fn test_definite_path_indices() {
+    let doc = document();
+    assert_eq!(select_first(&doc, "data[0].id"), Some(json!(1)));
+    assert_eq!(select_first(&doc, "data[-1].id"), Some(json!(3)));
+    assert_eq!(select_first(&doc, "$.data[-2].type"), Some(json!("refund")));
+    assert_eq!(select_first(&doc, "data[3]"), None);
+    assert_eq!(select_first(&doc, "data[-4]"), None);
+    assert_eq!(select_first(&doc, "meta[0]"), None);
+}
+
+#[test]
+fn test_definite_path_missing() {
+    let doc = document();
+    assert_eq!(select_first(&doc, "meta.previous.cursor"), None);
+    assert_eq!(select_first(&doc, "data.id"), None);
+    assert!(select(&doc, "meta.previous").unwrap().is_empty());
+}
+
+#[test]
+fn test_root_path() {
+    let doc = document();
+    assert_eq!(select_first(&doc, "$"), Some(doc.clone()));
+    assert_eq!(
+        JsonPath::parse("$").unwrap().records(&json!([1, 2])),
+        vec![json!(1), json!(2)]
+    );
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+#[test]
+fn test_wildcard() {
+    let doc = document();
+    let ids = select(&doc, "data[*].id").unwrap();
+    assert_eq!(ids, vec![json!(1), json!(2), json!(3)]);
+    assert_eq!(select(&doc, "$.data.*.id").unwrap().len(), 3);
+    assert!(!JsonPath::parse("data[*]").unwrap().is_definite());
+}
+
+#[test]
+fn test_filter() {
+    let doc = document();
+    let invoices = select(&doc, "$.data[?(@.type == 'invoice')]").unwrap();
+    assert_eq!(invoices.len(), 2);
+    assert_eq!(invoices[1]["id"], 3);
+
+    // Same result without the root and with a compound predicate
+    let large = select(&doc, "data[?(@.type == 'invoice' && @.amount > 20)].id").unwrap();
+    assert_eq!(large, vec![json!(3)]);
+}
+
+#[test]
+fn test_slice_union_and_descent() {
+    let doc = document();
+    assert_eq!(
+        select(&doc, "data[0:2].id").unwrap(),
+        vec![json!(1), json!(2)]
+    );
+    assert_eq!(
+        select(&doc, "data[0,2].id").unwrap(),
+        vec![json!(1), json!(3)]
+    );
+    assert_eq!(select(&doc, "$..cursor").unwrap(), vec![json!("abc")]);
+}
+
+#[test]
+fn test_negative_index_in_query() {
+    let doc = document();
+    assert_eq!(select(&doc, "data[-1].id").unwrap(), vec![json!(3)]);
+    assert_eq!(select(&doc, "$.data[?(@.amount > 0)].id").unwrap().len(), 3);
+    // Negative index rewritten inside a query
+    assert_eq!(select(&doc, "$..data[-2].id").unwrap(), vec![json!(2)]);
+}
+
+#[test]
+fn test_invalid_path() {
+    let err = JsonPath::parse("data[?(@.type ==").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Invalid JSONPath 'data[?(@.type =='"));
+    assert_eq!(select_first(&document(), "data[?(@.type =="), None);
+}
+
+// ============================================================================
+// Records
+// ============================================================================
+
+#[test]
+fn test_records() {
+    let doc = document();
+    // A definite path to an array yields its elements
+    assert_eq!(JsonPath::parse("data").unwrap().records(&doc).len(), 3);
+    // A definite path to an object yields it as one record
+    assert_eq!(
+        JsonPath::parse("meta.next").unwrap().records(&doc),
+        vec![json!({"cursor": "abc"})]
+    );
+    // A query yields each match
+    assert_eq!(
+        JsonPath::parse("included[*]").unwrap().records(&doc),
+        vec![json!({"id": "c1", "type": "customer"})]
+    );
+    assert!(JsonPath::parse("missing").unwrap().records(&doc).is_empty());
+}
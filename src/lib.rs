@@ -129,6 +129,15 @@ pub mod loader;
 /// Template interpolation
 pub mod template;
 
+/// JSONPath evaluation shared by decoders, paginators and routers
+pub mod jsonpath;
+
+/// Secret references (env, file, Vault, AWS Secrets Manager)
+pub mod secrets;
+
+/// Record transformations declared per stream
+pub mod transform;
+
 /// Command-line interface
 pub mod cli;
 
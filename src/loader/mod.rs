@@ -14,10 +14,13 @@ mod types;
 
 pub use parser::{load_connector, load_connector_from_str};
 pub use types::{
-    AsyncJobCreateDef, AsyncJobDownloadDef, AsyncJobPollDef, AuthDefinition, ConnectorDefinition,
-    DatabaseConnectionDef, DatabaseConnectorDefinition, DatabaseEngine, DatabaseStreamDefinition,
-    DecoderDefinition, HttpDefinition, PaginationDefinition, PartitionDefinition,
-    RequestDefinition, StopConditionDefinition, StreamDefinition, UnifiedConnectorDefinition,
+    AdaptiveWindowDefinition, AsyncJobCreateDef, AsyncJobDownloadDef, AsyncJobPollDef,
+    AuthDefinition, ConnectorDefinition, CsrfDefinition, DatabaseConnectionDef,
+    DatabaseConnectorDefinition, DatabaseEngine, DatabaseStreamDefinition, DecoderDefinition,
+    GraphqlDefinition, HttpDefinition, PaginationDefinition, PaginationLimitsDefinition,
+    ParentFilterDefinition, PartitionDefinition, PartitionLookupDefinition, RequestDefinition,
+    StopConditionDefinition, StreamDefinition, TransformationDefinition,
+    UnifiedConnectorDefinition,
 };
 
 #[cfg(test)]
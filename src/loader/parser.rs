@@ -4,8 +4,17 @@
 //! Supports both built-in connectors (by name) and custom YAML files (by path).
 
 use crate::connectors;
+use crate::decode::{ArchiveFormat, CsvCoercion, DecodeErrorPolicy, StreamFormat};
+use crate::engine::LimitPolicy;
 use crate::error::{Error, Result};
-use crate::loader::types::ConnectorDefinition;
+use crate::jsonpath::JsonPath;
+use crate::loader::types::{
+    AuthDefinition, ConnectorDefinition, DecoderDefinition, PaginationDefinition,
+    PartitionDefinition, TransformationDefinition,
+};
+use crate::pagination::{KeyOrder, RequestLocation};
+use crate::partition::CompareOp;
+use crate::transform::CastType;
 use std::fs;
 use std::path::Path;
 
@@ -92,13 +101,80 @@ fn validate_connector(def: &ConnectorDefinition) -> Result<()> {
         return Err(Error::config("Duplicate stream names found"));
     }
 
+    for auth in def.auth_definitions() {
+        validate_auth(auth)?;
+    }
+
     for stream in &def.streams {
         validate_stream(stream)?;
+
+        if let Some(partition) = &stream.partition {
+            validate_parent_streams(def, stream, partition)?;
+        }
+
+        if let Some(profile) = &stream.auth {
+            if !def.auth_profiles.contains_key(profile) {
+                return Err(Error::config(format!(
+                    "Stream '{}' references unknown auth profile '{}'",
+                    stream.name, profile
+                )));
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Validate that parent partitions reference an unpartitioned sibling stream
+fn validate_parent_streams(
+    def: &ConnectorDefinition,
+    stream: &crate::loader::types::StreamDefinition,
+    partition: &PartitionDefinition,
+) -> Result<()> {
+    match partition {
+        PartitionDefinition::Parent {
+            stream: parent_name,
+            ..
+        } => {
+            let Some(parent) = def.streams.iter().find(|s| &s.name == parent_name) else {
+                return Err(Error::config(format!(
+                    "Stream '{}' references unknown parent stream '{parent_name}'",
+                    stream.name
+                )));
+            };
+            if parent.name == stream.name || parent.partition.is_some() {
+                return Err(Error::config(format!(
+                    "Stream '{}' parent stream '{parent_name}' must be a different, unpartitioned stream",
+                    stream.name
+                )));
+            }
+            Ok(())
+        }
+        PartitionDefinition::Cartesian { routers } => routers
+            .iter()
+            .try_for_each(|router| validate_parent_streams(def, stream, router)),
+        _ => Ok(()),
+    }
+}
+
+/// Validate an auth definition
+fn validate_auth(auth: &AuthDefinition) -> Result<()> {
+    if let AuthDefinition::CookieSession {
+        content_type, csrf, ..
+    } = auth
+    {
+        if content_type != "json" && content_type != "form" {
+            return Err(Error::config(format!(
+                "Invalid cookie_session content_type '{content_type}' (expected json or form)"
+            )));
+        }
+        if let Some(csrf) = csrf {
+            csrf.to_config()?;
+        }
+    }
+    Ok(())
+}
+
 /// Validate a stream definition
 fn validate_stream(stream: &crate::loader::types::StreamDefinition) -> Result<()> {
     if stream.name.is_empty() {
@@ -121,5 +197,388 @@ fn validate_stream(stream: &crate::loader::types::StreamDefinition) -> Result<()
         )));
     }
 
+    if stream.request.graphql.is_some() && stream.request.body.is_some() {
+        return Err(Error::config(format!(
+            "Stream '{}' cannot set both a graphql query and a request body",
+            stream.name
+        )));
+    }
+
+    if let Some(pagination) = &stream.pagination {
+        validate_pagination(stream, pagination)?;
+    }
+
+    if let Some(partition) = &stream.partition {
+        validate_partition(stream, partition)?;
+    }
+
+    validate_decoder(stream, &stream.decoder)?;
+
+    for transformation in &stream.transformations {
+        validate_transformation(stream, transformation)?;
+    }
+
+    if LimitPolicy::parse(&stream.pagination_limits.policy).is_none() {
+        return Err(Error::config(format!(
+            "Stream '{}' has invalid pagination limit policy '{}' (expected stop, warn or fail)",
+            stream.name, stream.pagination_limits.policy
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a stream's partition definition
+fn validate_partition(
+    stream: &crate::loader::types::StreamDefinition,
+    partition: &PartitionDefinition,
+) -> Result<()> {
+    if let PartitionDefinition::List {
+        field,
+        fields,
+        values,
+        values_from,
+        lookup,
+    } = partition
+    {
+        let sources = usize::from(!values.is_empty())
+            + usize::from(values_from.is_some())
+            + usize::from(lookup.is_some());
+        if sources > 1 {
+            return Err(Error::config(format!(
+                "Stream '{}' list partition must use only one of values, values_from or lookup",
+                stream.name
+            )));
+        }
+        if field.is_empty() && fields.is_empty() {
+            return Err(Error::config(format!(
+                "Stream '{}' list partition requires a field or fields",
+                stream.name
+            )));
+        }
+        if let Some(lookup) = lookup {
+            if fields.is_empty() {
+                return Err(Error::config(format!(
+                    "Stream '{}' list partition lookup requires fields to map records",
+                    stream.name
+                )));
+            }
+            if lookup.request.graphql.is_some() {
+                return Err(Error::config(format!(
+                    "Stream '{}' list partition lookup does not support graphql",
+                    stream.name
+                )));
+            }
+            lookup.request.http_method()?;
+        }
+    }
+    validate_parent_partition(stream, partition)?;
+    if let PartitionDefinition::DateRange {
+        adaptive: Some(adaptive),
+        ..
+    } = partition
+    {
+        if adaptive.max_records == 0 {
+            return Err(Error::config(format!(
+                "Stream '{}' adaptive date range requires max_records above zero",
+                stream.name
+            )));
+        }
+    }
+    if let PartitionDefinition::Cartesian { routers } = partition {
+        if routers.len() < 2 {
+            return Err(Error::config(format!(
+                "Stream '{}' cartesian partition needs at least two routers",
+                stream.name
+            )));
+        }
+        for router in routers {
+            if matches!(router, PartitionDefinition::AsyncJob { .. }) {
+                return Err(Error::config(format!(
+                    "Stream '{}' cartesian partition cannot include an async job",
+                    stream.name
+                )));
+            }
+            if matches!(
+                router,
+                PartitionDefinition::DateRange {
+                    adaptive: Some(_),
+                    ..
+                }
+            ) {
+                return Err(Error::config(format!(
+                    "Stream '{}' cartesian partition cannot include an adaptive date range",
+                    stream.name
+                )));
+            }
+            // Child cursors are kept under the combined partition ID, which a
+            // parent component never sees
+            if let PartitionDefinition::Parent { filters, .. } = router {
+                if filters.iter().any(|filter| filter.child_cursor) {
+                    return Err(Error::config(format!(
+                        "Stream '{}' cartesian partition cannot use child_cursor parent filters",
+                        stream.name
+                    )));
+                }
+            }
+            validate_partition(stream, router)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate a parent partition's fields and filters
+fn validate_parent_partition(
+    stream: &crate::loader::types::StreamDefinition,
+    partition: &PartitionDefinition,
+) -> Result<()> {
+    if let PartitionDefinition::Parent {
+        parent_field,
+        partition_field,
+        fields,
+        filters,
+        ..
+    } = partition
+    {
+        if parent_field.is_empty() && fields.is_empty() {
+            return Err(Error::config(format!(
+                "Stream '{}' parent partition requires a parent_field or fields",
+                stream.name
+            )));
+        }
+        if !parent_field.is_empty() && partition_field.is_empty() {
+            return Err(Error::config(format!(
+                "Stream '{}' parent partition parent_field requires a partition_field",
+                stream.name
+            )));
+        }
+        for filter in filters {
+            if CompareOp::parse(&filter.op).is_none() {
+                return Err(Error::config(format!(
+                    "Stream '{}' parent filter has invalid op '{}' (expected eq, ne, gt, gte, lt or lte)",
+                    stream.name, filter.op
+                )));
+            }
+            if filter.value.is_some() == filter.child_cursor {
+                return Err(Error::config(format!(
+                    "Stream '{}' parent filter on '{}' needs either a value or child_cursor",
+                    stream.name, filter.field
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validate a stream's decoder definition
+fn validate_decoder(
+    stream: &crate::loader::types::StreamDefinition,
+    decoder: &DecoderDefinition,
+) -> Result<()> {
+    if let DecoderDefinition::Json {
+        records_path,
+        records_paths,
+        map_key_field,
+        unnest,
+    } = decoder
+    {
+        if records_path.is_some() && !records_paths.is_empty() {
+            return Err(Error::config(format!(
+                "Stream '{}' JSON decoder needs records_path or records_paths, not both",
+                stream.name
+            )));
+        }
+        for path in records_path.iter().chain(records_paths) {
+            if let Err(e) = JsonPath::parse(path) {
+                return Err(Error::config(format!(
+                    "Stream '{}' has invalid records path: {e}",
+                    stream.name
+                )));
+            }
+        }
+        if map_key_field
+            .as_ref()
+            .is_some_and(|field| field.trim().is_empty())
+        {
+            return Err(Error::config(format!(
+                "Stream '{}' JSON decoder map_key_field cannot be empty",
+                stream.name
+            )));
+        }
+        if let Some(path) = unnest {
+            if !JsonPath::parse(path).is_ok_and(|path| path.is_definite()) || path.is_empty() {
+                return Err(Error::config(format!(
+                    "Stream '{}' has invalid unnest path '{path}' (expected a field path like 'results' or 'usage.results')",
+                    stream.name
+                )));
+            }
+        }
+    }
+    if let DecoderDefinition::Archive { format, inner, .. } = decoder {
+        if ArchiveFormat::parse(format).is_none() {
+            return Err(Error::config(format!(
+                "Stream '{}' has invalid archive format '{format}' (expected auto, gzip, zstd, zip or tar)",
+                stream.name
+            )));
+        }
+        return validate_decoder(stream, inner);
+    }
+    if let DecoderDefinition::Stream { format, .. } = decoder {
+        if StreamFormat::parse(format).is_none() {
+            return Err(Error::config(format!(
+                "Stream '{}' has invalid stream format '{format}' (expected ndjson or sse)",
+                stream.name
+            )));
+        }
+    }
+    if let DecoderDefinition::Csv {
+        delimiter,
+        quote,
+        escape,
+        ..
+    } = decoder
+    {
+        if !delimiter.is_ascii() || !quote.is_ascii() || escape.is_some_and(|c| !c.is_ascii()) {
+            return Err(Error::config(format!(
+                "Stream '{}' CSV delimiter, quote and escape must be ASCII characters",
+                stream.name
+            )));
+        }
+    }
+    match decoder {
+        DecoderDefinition::Csv { coerce, .. } => validate_coerce(stream, "CSV", coerce)?,
+        DecoderDefinition::Xml { coerce, .. } => validate_coerce(stream, "XML", coerce)?,
+        _ => {}
+    }
+    if let DecoderDefinition::Jsonl { on_error } | DecoderDefinition::Csv { on_error, .. } = decoder
+    {
+        if DecodeErrorPolicy::parse(on_error).is_none() {
+            return Err(Error::config(format!(
+                "Stream '{}' has invalid decoder on_error '{on_error}' (expected fail or quarantine)",
+                stream.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a CSV or XML decoder's type coercion
+fn validate_coerce(
+    stream: &crate::loader::types::StreamDefinition,
+    format: &str,
+    coerce: &str,
+) -> Result<()> {
+    match CsvCoercion::parse(coerce) {
+        None => Err(Error::config(format!(
+            "Stream '{}' has invalid {format} coerce '{coerce}' (expected none, schema or infer)",
+            stream.name
+        ))),
+        Some(CsvCoercion::Schema) if stream.schema.is_none() => Err(Error::config(format!(
+            "Stream '{}' {format} coerce 'schema' requires a stream schema",
+            stream.name
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Validate a stream transformation
+fn validate_transformation(
+    stream: &crate::loader::types::StreamDefinition,
+    transformation: &TransformationDefinition,
+) -> Result<()> {
+    match transformation {
+        TransformationDefinition::Cast { field, to } if CastType::parse(to).is_none() => {
+            Err(Error::config(format!(
+                "Stream '{}' casts '{field}' to invalid type '{to}' (expected string, integer, number or boolean)",
+                stream.name
+            )))
+        }
+        TransformationDefinition::Filter { field, op, .. } if CompareOp::parse(op).is_none() => {
+            Err(Error::config(format!(
+                "Stream '{}' filters '{field}' with invalid op '{op}' (expected eq, ne, gt, gte, lt or lte)",
+                stream.name
+            )))
+        }
+        TransformationDefinition::RemoveFields { fields } if fields.is_empty() => {
+            Err(Error::config(format!(
+                "Stream '{}' remove_fields transformation needs at least one field",
+                stream.name
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validate a stream's pagination definition
+fn validate_pagination(
+    stream: &crate::loader::types::StreamDefinition,
+    pagination: &PaginationDefinition,
+) -> Result<()> {
+    if let PaginationDefinition::Cursor {
+        cursor_path,
+        cursor_header,
+        location,
+        ..
+    } = pagination
+    {
+        if cursor_path.is_some() == cursor_header.is_some() {
+            return Err(Error::config(format!(
+                "Stream '{}' cursor pagination needs exactly one of cursor_path or cursor_header",
+                stream.name
+            )));
+        }
+        match RequestLocation::parse(location) {
+            None => {
+                return Err(Error::config(format!(
+                    "Stream '{}' has invalid cursor location '{location}' (expected query, header or body)",
+                    stream.name
+                )));
+            }
+            Some(RequestLocation::Body)
+                if stream.request.http_method()? == reqwest::Method::GET =>
+            {
+                return Err(Error::config(format!(
+                    "Stream '{}' sends the cursor in the request body but uses GET",
+                    stream.name
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    if let PaginationDefinition::Keyset {
+        location, order, ..
+    } = pagination
+    {
+        match RequestLocation::parse(location) {
+            None => {
+                return Err(Error::config(format!(
+                    "Stream '{}' has invalid keyset location '{location}' (expected query, header or body)",
+                    stream.name
+                )));
+            }
+            Some(RequestLocation::Body)
+                if stream.request.http_method()? == reqwest::Method::GET =>
+            {
+                return Err(Error::config(format!(
+                    "Stream '{}' sends the keyset key in the request body but uses GET",
+                    stream.name
+                )));
+            }
+            Some(_) => {}
+        }
+        if KeyOrder::parse(order).is_none() {
+            return Err(Error::config(format!(
+                "Stream '{}' has invalid keyset order '{order}' (expected none, asc or desc)",
+                stream.name
+            )));
+        }
+    }
+    if matches!(pagination, PaginationDefinition::Relay { .. }) && stream.request.graphql.is_none()
+    {
+        return Err(Error::config(format!(
+            "Stream '{}' uses relay pagination without a graphql query",
+            stream.name
+        )));
+    }
     Ok(())
 }
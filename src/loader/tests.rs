@@ -327,13 +327,113 @@ streams:
 
     let def = load_connector_from_str(yaml).unwrap();
     match &def.streams[0].decoder {
-        DecoderDefinition::Json { records_path } => {
+        DecoderDefinition::Json { records_path, .. } => {
             assert_eq!(records_path, &Some("data.users".to_string()));
         }
         _ => panic!("Expected JSON decoder"),
     }
 }
 
+#[test]
+fn test_load_json_decoder_multiple_paths() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: articles
+    request:
+      path: /articles
+    decoder:
+      type: json
+      records_paths:
+        - data[*]
+        - included[?(@.type == 'people')]
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].decoder {
+        DecoderDefinition::Json {
+            records_path,
+            records_paths,
+            ..
+        } => {
+            assert!(records_path.is_none());
+            assert_eq!(records_paths.len(), 2);
+        }
+        _ => panic!("Expected JSON decoder"),
+    }
+}
+
+#[test]
+fn test_load_json_decoder_map_key_and_unnest() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: usage
+    request:
+      path: /usage
+    decoder:
+      type: json
+      records_path: result
+      map_key_field: date
+      unnest: models
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let decoder = def.streams[0].decoder.json_decoder().unwrap();
+    let records = decoder
+        .extract_records(&serde_json::json!({
+            "result": {"2024-01-01": {"models": [{"name": "a"}, {"name": "b"}]}}
+        }))
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1]["date"], "2024-01-01");
+    assert_eq!(records[1]["name"], "b");
+}
+
+#[test]
+fn test_invalid_json_decoder_paths() {
+    let connector = |decoder: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: articles
+    request:
+      path: /articles
+    decoder:
+      type: json
+{decoder}
+"#
+        )
+    };
+
+    let cases = [
+        (
+            "      records_path: data\n      records_paths: [included]",
+            "needs records_path or records_paths, not both",
+        ),
+        (
+            "      records_path: \"data[?(@.type ==\"",
+            "invalid records path",
+        ),
+        ("      map_key_field: \"\"", "map_key_field cannot be empty"),
+        (
+            "      unnest: \"items[*]\"",
+            "invalid unnest path 'items[*]'",
+        ),
+    ];
+    for (decoder, expected) in cases {
+        let err = load_connector_from_str(&connector(decoder)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
 #[test]
 fn test_load_jsonl_decoder() {
     let yaml = r#"
@@ -348,7 +448,34 @@ streams:
 "#;
 
     let def = load_connector_from_str(yaml).unwrap();
-    assert!(matches!(def.streams[0].decoder, DecoderDefinition::Jsonl));
+    assert!(matches!(
+        def.streams[0].decoder,
+        DecoderDefinition::Jsonl { .. }
+    ));
+}
+
+#[test]
+fn test_load_decoder_on_error() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: logs
+    request:
+      path: /logs
+    decoder:
+      type: jsonl
+      on_error: quarantine
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].decoder {
+        DecoderDefinition::Jsonl { on_error } => assert_eq!(on_error, "quarantine"),
+        _ => panic!("Expected JSONL decoder"),
+    }
+
+    let err = load_connector_from_str(&yaml.replace("quarantine", "skip")).unwrap_err();
+    assert!(err.to_string().contains("invalid decoder on_error 'skip'"));
 }
 
 #[test]
@@ -371,14 +498,357 @@ streams:
         DecoderDefinition::Csv {
             delimiter,
             has_header,
+            quote,
+            escape,
+            coerce,
+            on_error,
         } => {
             assert_eq!(*delimiter, ';');
             assert!(*has_header);
+            assert_eq!(*quote, '"');
+            assert!(escape.is_none());
+            assert_eq!(coerce, "none");
+            assert_eq!(on_error, "fail");
         }
         _ => panic!("Expected CSV decoder"),
     }
 }
 
+#[test]
+fn test_load_csv_decoder_with_schema_coercion() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: accounts
+    request:
+      path: /export
+    decoder:
+      type: csv
+      escape: "\\"
+      coerce: schema
+    schema:
+      type: object
+      properties:
+        Id:
+          type: string
+        NumberOfEmployees:
+          type: integer
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let stream = &def.streams[0];
+    assert!(matches!(
+        &stream.decoder,
+        DecoderDefinition::Csv { escape: Some('\\'), coerce, .. } if coerce == "schema"
+    ));
+    assert_eq!(stream.schema.as_ref().unwrap().properties.len(), 2);
+}
+
+#[test]
+fn test_invalid_csv_decoder() {
+    let connector = |decoder: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: accounts
+    request:
+      path: /export
+    decoder:
+      type: csv
+{decoder}
+"#
+        )
+    };
+
+    let cases = [
+        ("      coerce: guess", "invalid CSV coerce 'guess'"),
+        ("      coerce: schema", "requires a stream schema"),
+        ("      delimiter: \"§\"", "must be ASCII"),
+    ];
+    for (decoder, expected) in cases {
+        let err = load_connector_from_str(&connector(decoder)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
+#[test]
+fn test_xml_decoder_coerce() {
+    let connector = |coerce: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: accounts
+    request:
+      path: /export
+    decoder:
+      type: xml
+      records_path: //account
+{coerce}
+"#
+        )
+    };
+
+    // Text stays strings unless coercion is asked for
+    let def = load_connector_from_str(&connector("")).unwrap();
+    assert!(matches!(
+        &def.streams[0].decoder,
+        DecoderDefinition::Xml { coerce, .. } if coerce == "none"
+    ));
+
+    let cases = [
+        ("      coerce: guess", "invalid XML coerce 'guess'"),
+        ("      coerce: schema", "requires a stream schema"),
+    ];
+    for (coerce, expected) in cases {
+        let err = load_connector_from_str(&connector(coerce)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
+#[test]
+fn test_load_archive_decoder() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: accounts
+    request:
+      path: /jobs/123/results
+    decoder:
+      type: archive
+      format: zip
+      files: "*.csv"
+      inner:
+        type: csv
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].decoder {
+        DecoderDefinition::Archive {
+            format,
+            files,
+            max_unpacked_mb,
+            inner,
+        } => {
+            assert_eq!(format, "zip");
+            assert_eq!(files.as_deref(), Some("*.csv"));
+            assert_eq!(*max_unpacked_mb, 1024);
+            assert!(matches!(inner.as_ref(), DecoderDefinition::Csv { .. }));
+        }
+        _ => panic!("Expected archive decoder"),
+    }
+}
+
+#[test]
+fn test_load_columnar_decoders() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: events
+    request:
+      path: /unload/events.parquet
+    decoder:
+      type: parquet
+  - name: orders
+    request:
+      path: /exports/orders
+    decoder:
+      type: archive
+      files: "*.avro"
+      inner:
+        type: avro
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    assert!(matches!(def.streams[0].decoder, DecoderDefinition::Parquet));
+    match &def.streams[1].decoder {
+        DecoderDefinition::Archive { inner, .. } => {
+            assert!(matches!(inner.as_ref(), DecoderDefinition::Avro));
+        }
+        _ => panic!("Expected archive decoder"),
+    }
+}
+
+#[test]
+fn test_load_stream_decoder() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: changes
+    request:
+      path: /changes/stream
+    decoder:
+      type: stream
+      format: sse
+      events: [change, delete]
+  - name: export
+    request:
+      path: /export
+    decoder:
+      type: stream
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].decoder {
+        DecoderDefinition::Stream { format, events } => {
+            assert_eq!(format, "sse");
+            assert_eq!(events, &["change", "delete"]);
+        }
+        _ => panic!("Expected stream decoder"),
+    }
+    assert!(matches!(
+        &def.streams[1].decoder,
+        DecoderDefinition::Stream { format, events } if format == "ndjson" && events.is_empty()
+    ));
+
+    let err =
+        load_connector_from_str(&yaml.replace("format: sse", "format: websocket")).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("invalid stream format 'websocket'"));
+}
+
+#[test]
+fn test_invalid_archive_decoder() {
+    let connector = |decoder: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: accounts
+    request:
+      path: /export
+    decoder:
+      type: archive
+{decoder}
+"#
+        )
+    };
+
+    let cases = [
+        (
+            "      format: rar\n      inner:\n        type: jsonl",
+            "invalid archive format 'rar'",
+        ),
+        (
+            "      inner:\n        type: csv\n        coerce: guess",
+            "invalid CSV coerce 'guess'",
+        ),
+    ];
+    for (decoder, expected) in cases {
+        let err = load_connector_from_str(&connector(decoder)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
+// ============================================================================
+// Transformation Tests
+// ============================================================================
+
+#[test]
+fn test_load_transformations() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: customers
+    request:
+      path: /customers
+    transformations:
+      - type: add_field
+        field: account_id
+        value: "{{ partition.account_id }}"
+      - type: remove_fields
+        fields: [ssn]
+      - type: rename_field
+        from: createdAt
+        to: created_at
+      - type: flatten
+        path: billing.address
+      - type: cast
+        field: age
+        to: integer
+      - type: hash
+        field: email
+      - type: filter
+        field: status
+        op: ne
+        value: deleted
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let stream = &def.streams[0];
+    assert_eq!(stream.transformations.len(), 7);
+    assert!(matches!(
+        &stream.transformations[3],
+        TransformationDefinition::Flatten { separator, prefix: None, .. } if separator == "_"
+    ));
+
+    let pipeline = stream.transform_pipeline().unwrap();
+    assert!(!pipeline.is_empty());
+    assert!(def.streams[0]
+        .transformations
+        .iter()
+        .all(|t| t.to_transformation().is_ok()));
+}
+
+#[test]
+fn test_invalid_transformations() {
+    let connector = |transformation: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: customers
+    request:
+      path: /customers
+    transformations:
+{transformation}
+"#
+        )
+    };
+
+    let cases = [
+        (
+            "      - type: cast\n        field: age\n        to: date",
+            "casts 'age' to invalid type 'date'",
+        ),
+        (
+            "      - type: filter\n        field: status\n        op: like\n        value: open",
+            "filters 'status' with invalid op 'like'",
+        ),
+        (
+            "      - type: remove_fields\n        fields: []",
+            "needs at least one field",
+        ),
+    ];
+    for (transformation, expected) in cases {
+        let err = load_connector_from_str(&connector(transformation)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
 // ============================================================================
 // Pagination Tests
 // ============================================================================
@@ -450,44 +920,330 @@ streams:
             page_size,
             ..
         }) => {
-            assert_eq!(page_param, "page");
-            assert_eq!(*start_page, 1);
-            assert_eq!(page_size_param, &Some("per_page".to_string()));
-            assert_eq!(*page_size, Some(50));
+            assert_eq!(page_param, "page");
+            assert_eq!(*start_page, 1);
+            assert_eq!(page_size_param, &Some("per_page".to_string()));
+            assert_eq!(*page_size, Some(50));
+        }
+        _ => panic!("Expected page number pagination"),
+    }
+}
+
+#[test]
+fn test_load_cursor_pagination() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /items
+    pagination:
+      type: cursor
+      cursor_param: cursor
+      cursor_path: meta.next_cursor
+      location: query
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].pagination {
+        Some(PaginationDefinition::Cursor {
+            cursor_param,
+            cursor_path,
+            cursor_header,
+            location,
+            stop,
+        }) => {
+            assert_eq!(cursor_param, "cursor");
+            assert_eq!(cursor_path.as_deref(), Some("meta.next_cursor"));
+            assert!(cursor_header.is_none());
+            assert_eq!(location, "query");
+            assert!(matches!(stop, StopConditionDefinition::EmptyPage));
+        }
+        _ => panic!("Expected cursor pagination"),
+    }
+}
+
+#[test]
+fn test_load_cursor_pagination_from_header() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      method: POST
+      path: /items
+    pagination:
+      type: cursor
+      cursor_param: page.after
+      cursor_header: X-Next-Cursor
+      location: body
+      stop:
+        type: field
+        path: has_more
+        value: false
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].pagination {
+        Some(PaginationDefinition::Cursor {
+            cursor_path,
+            cursor_header,
+            location,
+            stop,
+            ..
+        }) => {
+            assert!(cursor_path.is_none());
+            assert_eq!(cursor_header.as_deref(), Some("X-Next-Cursor"));
+            assert_eq!(location, "body");
+            assert!(matches!(stop, StopConditionDefinition::Field { .. }));
+        }
+        _ => panic!("Expected cursor pagination"),
+    }
+}
+
+#[test]
+fn test_invalid_cursor_pagination() {
+    let stream = |pagination: &str, method: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      method: {method}
+      path: /items
+    pagination:
+      type: cursor
+      cursor_param: cursor
+{pagination}
+"#
+        )
+    };
+
+    // Neither or both cursor sources
+    assert!(load_connector_from_str(&stream("      location: query", "GET")).is_err());
+    assert!(load_connector_from_str(&stream(
+        "      cursor_path: next\n      cursor_header: X-Next",
+        "GET"
+    ))
+    .is_err());
+
+    // Unknown location
+    let err = load_connector_from_str(&stream(
+        "      cursor_path: next\n      location: cookie",
+        "GET",
+    ))
+    .unwrap_err();
+    assert!(err.to_string().contains("invalid cursor location"));
+
+    // Body cursor with GET
+    let err = load_connector_from_str(&stream(
+        "      cursor_path: next\n      location: body",
+        "GET",
+    ))
+    .unwrap_err();
+    assert!(err.to_string().contains("uses GET"));
+    assert!(load_connector_from_str(&stream(
+        "      cursor_path: next\n      location: body",
+        "POST"
+    ))
+    .is_ok());
+}
+
+#[test]
+fn test_load_keyset_pagination() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: orders
+    request:
+      path: /orders.json
+    pagination:
+      type: keyset
+      key_param: since_id
+      key_field: id
+      order: asc
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].pagination {
+        Some(PaginationDefinition::Keyset {
+            key_param,
+            key_field,
+            location,
+            order,
+            stop,
+        }) => {
+            assert_eq!(key_param, "since_id");
+            assert_eq!(key_field, "id");
+            assert_eq!(location, "query");
+            assert_eq!(order, "asc");
+            assert!(matches!(stop, StopConditionDefinition::EmptyPage));
+        }
+        _ => panic!("Expected keyset pagination"),
+    }
+}
+
+#[test]
+fn test_invalid_keyset_pagination() {
+    let stream = |options: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /items
+    pagination:
+      type: keyset
+      key_param: since_id
+      key_field: id
+{options}
+"#
+        )
+    };
+
+    let err = load_connector_from_str(&stream("      order: random")).unwrap_err();
+    assert!(err.to_string().contains("invalid keyset order"));
+
+    let err = load_connector_from_str(&stream("      location: cookie")).unwrap_err();
+    assert!(err.to_string().contains("invalid keyset location"));
+
+    let err = load_connector_from_str(&stream("      location: body")).unwrap_err();
+    assert!(err.to_string().contains("uses GET"));
+}
+
+#[test]
+fn test_load_pagination_limits() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /items
+    pagination_limits:
+      max_pages: 50
+      policy: fail
+  - name: defaults
+    request:
+      path: /defaults
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let limits = &def.streams[0].pagination_limits;
+    assert_eq!(limits.max_pages, Some(50));
+    assert!(limits.detect_loops);
+    assert_eq!(limits.policy, "fail");
+
+    let limits = def.streams[1].pagination_limits.to_limits();
+    assert_eq!(limits.max_pages, 0);
+    assert!(limits.detect_loops);
+    assert_eq!(limits.policy, crate::engine::LimitPolicy::Warn);
+
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /items
+    pagination_limits:
+      policy: retry
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("invalid pagination limit policy"));
+}
+
+#[test]
+fn test_load_graphql_stream() {
+    let yaml = r#"
+name: shopify
+base_url: https://shop.example.com
+streams:
+  - name: orders
+    request:
+      path: /admin/api/2024-01/graphql.json
+      graphql:
+        query: |
+          query($first: Int!, $after: String) {
+            orders(first: $first, after: $after) {
+              edges { node { id name } }
+              pageInfo { hasNextPage endCursor }
+            }
+          }
+        variables:
+          first: 50
+        cost_path: extensions.cost
+    decoder:
+      type: graphql
+      connection_path: data.orders
+    pagination:
+      type: relay
+      page_info_path: data.orders.pageInfo
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let stream = &def.streams[0];
+    let graphql = stream.request.graphql.as_ref().unwrap();
+    assert!(graphql.query.contains("orders(first: $first"));
+    assert_eq!(graphql.variables["first"], 50);
+    assert_eq!(graphql.cost_path.as_deref(), Some("extensions.cost"));
+    assert_eq!(stream.request.http_method().unwrap(), reqwest::Method::POST);
+    assert!(matches!(
+        &stream.decoder,
+        DecoderDefinition::Graphql { connection_path } if connection_path == "data.orders"
+    ));
+    match &stream.pagination {
+        Some(PaginationDefinition::Relay {
+            page_info_path,
+            cursor_variable,
+        }) => {
+            assert_eq!(page_info_path, "data.orders.pageInfo");
+            assert_eq!(cursor_variable, "after");
         }
-        _ => panic!("Expected page number pagination"),
+        _ => panic!("Expected relay pagination"),
     }
 }
 
 #[test]
-fn test_load_cursor_pagination() {
+fn test_invalid_graphql_stream() {
+    // Relay pagination needs a graphql query
     let yaml = r#"
 name: test
 base_url: https://api.example.com
 streams:
   - name: items
     request:
-      path: /items
+      path: /graphql
     pagination:
-      type: cursor
-      cursor_param: cursor
-      cursor_path: meta.next_cursor
-      location: query
+      type: relay
+      page_info_path: data.items.pageInfo
 "#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("without a graphql query"));
 
-    let def = load_connector_from_str(yaml).unwrap();
-    match &def.streams[0].pagination {
-        Some(PaginationDefinition::Cursor {
-            cursor_param,
-            cursor_path,
-            location,
-        }) => {
-            assert_eq!(cursor_param, "cursor");
-            assert_eq!(cursor_path, "meta.next_cursor");
-            assert_eq!(location, "query");
-        }
-        _ => panic!("Expected cursor pagination"),
-    }
+    // graphql and body are exclusive
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /graphql
+      body: '{"query": "{ items { id } }"}'
+      graphql:
+        query: "{ items { id } }"
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("both a graphql query and a request body"));
 }
 
 #[test]
@@ -539,7 +1295,7 @@ streams:
 
     let def = load_connector_from_str(yaml).unwrap();
     match &def.streams[0].partition {
-        Some(PartitionDefinition::List { field, values }) => {
+        Some(PartitionDefinition::List { field, values, .. }) => {
             assert_eq!(field, "region");
             assert_eq!(values, &["us-east", "us-west", "eu-west"]);
         }
@@ -547,6 +1303,173 @@ streams:
     }
 }
 
+#[test]
+fn test_load_dynamic_list_partition() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: shop_orders
+    request:
+      path: /orders
+    partition:
+      type: list
+      field: shop
+      values_from: "{{ config.shops }}"
+  - name: brand_tickets
+    request:
+      path: /tickets
+    partition:
+      type: list
+      fields:
+        brand_id: id
+        subdomain: subdomain
+      lookup:
+        request:
+          path: /brands.json
+        decoder:
+          type: json
+          records_path: brands
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].partition {
+        Some(PartitionDefinition::List {
+            field, values_from, ..
+        }) => {
+            assert_eq!(field, "shop");
+            assert_eq!(values_from.as_deref(), Some("{{ config.shops }}"));
+        }
+        _ => panic!("Expected list partition"),
+    }
+    match &def.streams[1].partition {
+        Some(PartitionDefinition::List { fields, lookup, .. }) => {
+            assert_eq!(fields.get("brand_id").map(String::as_str), Some("id"));
+            let lookup = lookup.as_ref().unwrap();
+            assert_eq!(lookup.request.path, "/brands.json");
+            assert!(matches!(lookup.decoder, DecoderDefinition::Json { .. }));
+        }
+        _ => panic!("Expected list partition"),
+    }
+}
+
+#[test]
+fn test_invalid_list_partition() {
+    let stream = |partition: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: items
+    request:
+      path: /items
+    partition:
+      type: list
+{partition}
+"#
+        )
+    };
+
+    let err = load_connector_from_str(&stream(
+        "      field: shop
+      values: [a]
+      values_from: \"{{ config.shops }}\"",
+    ))
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("only one of values, values_from or lookup"));
+
+    let err = load_connector_from_str(&stream("      values: [a]")).unwrap_err();
+    assert!(err.to_string().contains("requires a field or fields"));
+
+    let err = load_connector_from_str(&stream(
+        "      field: brand
+      lookup:
+        request:
+          path: /brands",
+    ))
+    .unwrap_err();
+    assert!(err.to_string().contains("lookup requires fields"));
+}
+
+#[test]
+fn test_load_cartesian_partition() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: usage
+    request:
+      path: "/accounts/{{ partition.account_id }}/usage"
+      params:
+        date: "{{ partition.start }}"
+    partition:
+      type: cartesian
+      routers:
+        - type: list
+          field: account_id
+          values_from: "{{ config.account_ids }}"
+        - type: date_range
+          start: "{{ config.start_date }}"
+          end: "{{ now }}"
+          step: 1d
+          start_field: start
+          end_field: end
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].partition {
+        Some(PartitionDefinition::Cartesian { routers }) => {
+            assert_eq!(routers.len(), 2);
+            assert!(matches!(routers[0], PartitionDefinition::List { .. }));
+            assert!(matches!(routers[1], PartitionDefinition::DateRange { .. }));
+        }
+        _ => panic!("Expected cartesian partition"),
+    }
+}
+
+#[test]
+fn test_invalid_cartesian_partition() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: usage
+    request:
+      path: /usage
+    partition:
+      type: cartesian
+      routers:
+        - type: list
+          field: account_id
+          values: [a]
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("at least two routers"));
+
+    // Components are validated too
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: usage
+    request:
+      path: /usage
+    partition:
+      type: cartesian
+      routers:
+        - type: list
+          values: [a]
+        - type: list
+          field: region
+          values: [us]
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("requires a field or fields"));
+}
+
 #[test]
 fn test_load_parent_partition() {
     let yaml = r#"
@@ -572,7 +1495,11 @@ streams:
             stream,
             parent_field,
             partition_field,
+            fields,
+            filters,
         }) => {
+            assert!(fields.is_empty());
+            assert!(filters.is_empty());
             assert_eq!(stream, "users");
             assert_eq!(parent_field, "id");
             assert_eq!(partition_field, "user_id");
@@ -581,6 +1508,116 @@ streams:
     }
 }
 
+#[test]
+fn test_load_parent_partition_fields_and_filters() {
+    let yaml = r#"
+name: test
+base_url: https://api.github.com
+streams:
+  - name: repos
+    request:
+      path: /user/repos
+  - name: commits
+    request:
+      path: "/repos/{{ partition.owner }}/{{ partition.repo }}/commits"
+    cursor_field: commit.author.date
+    partition:
+      type: parent
+      stream: repos
+      fields:
+        owner: owner.login
+        repo: name
+      filters:
+        - field: archived
+          op: ne
+          value: true
+        - field: pushed_at
+          op: gt
+          child_cursor: true
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[1].partition {
+        Some(PartitionDefinition::Parent {
+            parent_field,
+            fields,
+            filters,
+            ..
+        }) => {
+            assert!(parent_field.is_empty());
+            assert_eq!(fields.get("owner").map(String::as_str), Some("owner.login"));
+            assert_eq!(filters.len(), 2);
+            assert_eq!(filters[0].value, Some(serde_json::json!(true)));
+            assert_eq!(filters[1].op, "gt");
+            assert!(filters[1].child_cursor);
+        }
+        _ => panic!("Expected parent partition"),
+    }
+}
+
+#[test]
+fn test_invalid_parent_partition() {
+    let connector = |partition: &str| {
+        format!(
+            r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: users
+    request:
+      path: /users
+  - name: orders
+    request:
+      path: /orders
+    partition:
+{partition}
+"#
+        )
+    };
+
+    let cases = [
+        (
+            "      type: parent\n      stream: missing\n      parent_field: id\n      partition_field: user_id",
+            "unknown parent stream 'missing'",
+        ),
+        (
+            "      type: parent\n      stream: orders\n      parent_field: id\n      partition_field: user_id",
+            "must be a different, unpartitioned stream",
+        ),
+        (
+            "      type: parent\n      stream: users",
+            "requires a parent_field or fields",
+        ),
+        (
+            "      type: parent\n      stream: users\n      parent_field: id",
+            "requires a partition_field",
+        ),
+        (
+            "      type: parent\n      stream: users\n      parent_field: id\n      partition_field: user_id\n      filters:\n        - field: age\n          op: between\n          value: 3",
+            "invalid op 'between'",
+        ),
+        (
+            "      type: parent\n      stream: users\n      parent_field: id\n      partition_field: user_id\n      filters:\n        - field: updated_at\n          op: gt",
+            "needs either a value or child_cursor",
+        ),
+        (
+            "      type: cartesian\n      routers:\n        - type: parent\n          stream: missing\n          parent_field: id\n          partition_field: user_id\n        - type: list\n          field: region\n          values: [eu]",
+            "unknown parent stream 'missing'",
+        ),
+        (
+            "      type: cartesian\n      routers:\n        - type: parent\n          stream: users\n          parent_field: id\n          partition_field: user_id\n          filters:\n            - field: updated_at\n              op: gt\n              child_cursor: true\n        - type: list\n          field: region\n          values: [eu]",
+            "cannot use child_cursor parent filters",
+        ),
+    ];
+    for (partition, expected) in cases {
+        let err = load_connector_from_str(&connector(partition)).unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "expected '{expected}' in '{err}'"
+        );
+    }
+}
+
 #[test]
 fn test_load_date_range_partition() {
     let yaml = r#"
@@ -610,7 +1647,11 @@ streams:
             step,
             start_field,
             end_field,
+            format,
+            adaptive,
         }) => {
+            assert!(adaptive.is_none());
+            assert_eq!(format, "%Y-%m-%d");
             assert_eq!(start, "{{ config.start_date }}");
             assert_eq!(end, "{{ now() }}");
             assert_eq!(step, "1d");
@@ -621,6 +1662,93 @@ streams:
     }
 }
 
+#[test]
+fn test_load_adaptive_date_range_partition() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: events
+    request:
+      path: /events
+    partition:
+      type: date_range
+      start: "2024-01-01"
+      end: now
+      step: 7d
+      start_field: since
+      end_field: until
+      adaptive:
+        max_records: 10000
+        total_path: meta.total
+        min_step: 1h
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    match &def.streams[0].partition {
+        Some(PartitionDefinition::DateRange {
+            adaptive: Some(adaptive),
+            ..
+        }) => {
+            assert_eq!(adaptive.max_records, 10000);
+            assert_eq!(adaptive.total_path.as_deref(), Some("meta.total"));
+            assert_eq!(adaptive.min_step, "1h");
+            assert_eq!(adaptive.max_step, "30d");
+        }
+        _ => panic!("Expected adaptive date range partition"),
+    }
+}
+
+#[test]
+fn test_invalid_adaptive_date_range_partition() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: events
+    request:
+      path: /events
+    partition:
+      type: date_range
+      start: "2024-01-01"
+      end: now
+      step: 1d
+      start_field: since
+      end_field: until
+      adaptive:
+        max_records: 0
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("max_records above zero"));
+
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+streams:
+  - name: usage
+    request:
+      path: /usage
+    partition:
+      type: cartesian
+      routers:
+        - type: list
+          field: account_id
+          values: [a]
+        - type: date_range
+          start: "2024-01-01"
+          end: now
+          step: 1d
+          start_field: since
+          end_field: until
+          adaptive:
+            max_records: 100
+"#;
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot include an adaptive date range"));
+}
+
 // ============================================================================
 // Validation Tests
 // ============================================================================
@@ -830,3 +1958,168 @@ streams:
     ));
     assert_eq!(issues.cursor_field, Some("updated_at".to_string()));
 }
+
+// ============================================================================
+// Secret Field Tests
+// ============================================================================
+
+#[test]
+fn test_secret_config_fields() {
+    let yaml = r#"
+name: test
+base_url: https://api.example.com
+spec:
+  properties:
+    webhook_token:
+      type: string
+      secret: true
+    region:
+      type: string
+auth:
+  type: oauth2_client_credentials
+  token_url: https://auth.example.com/token
+  client_id: "{{ config.client_id }}"
+  client_secret: "{{ config.client_secret }}"
+streams:
+  - name: data
+    request:
+      path: /data
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let fields = def.secret_config_fields();
+    assert!(fields.contains(&"webhook_token".to_string()));
+    assert!(!fields.contains(&"client_id".to_string()));
+    assert!(fields.contains(&"client_secret".to_string()));
+    assert!(!fields.contains(&"region".to_string()));
+}
+
+#[test]
+fn test_auth_profiles() {
+    let yaml = r#"
+name: shop
+base_url: https://shop.example.com
+auth:
+  type: bearer
+  token: "{{ config.admin_token }}"
+auth_profiles:
+  storefront:
+    type: api_key
+    key: X-Storefront-Access-Token
+    value: "{{ config.storefront_token }}"
+streams:
+  - name: orders
+    request:
+      path: /admin/orders
+  - name: products
+    auth: storefront
+    request:
+      path: /storefront/products
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    assert_eq!(def.auth_profiles.len(), 1);
+    assert!(def.streams[0].auth.is_none());
+    assert_eq!(def.streams[1].auth.as_deref(), Some("storefront"));
+
+    assert!(matches!(
+        def.stream_auth(&def.streams[0]).unwrap(),
+        Some(AuthDefinition::Bearer { .. })
+    ));
+    assert!(matches!(
+        def.stream_auth(&def.streams[1]).unwrap(),
+        Some(AuthDefinition::ApiKey { .. })
+    ));
+    assert!(def.auth_profile(Some("missing")).is_err());
+
+    let fields = def.secret_config_fields();
+    assert!(fields.contains(&"admin_token".to_string()));
+    assert!(fields.contains(&"storefront_token".to_string()));
+}
+
+#[test]
+fn test_unknown_auth_profile() {
+    let yaml = r#"
+name: shop
+base_url: https://shop.example.com
+streams:
+  - name: products
+    auth: storefront
+    request:
+      path: /products
+"#;
+
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown auth profile 'storefront'"));
+}
+
+#[test]
+fn test_cookie_session_auth() {
+    let yaml = r#"
+name: legacy
+base_url: https://admin.example.com
+auth:
+  type: cookie_session
+  login_url: https://admin.example.com/login
+  content_type: form
+  body: '{"username": "{{ config.username }}", "password": "{{ config.password }}"}'
+  csrf:
+    source: cookie
+    name: XSRF-TOKEN
+    header_name: X-XSRF-TOKEN
+streams:
+  - name: users
+    request:
+      path: /api/users
+"#;
+
+    let def = load_connector_from_str(yaml).unwrap();
+    let Some(AuthDefinition::CookieSession {
+        login_method,
+        content_type,
+        csrf,
+        relogin_statuses,
+        ..
+    }) = &def.auth
+    else {
+        panic!("expected cookie_session auth");
+    };
+    assert_eq!(login_method, "POST");
+    assert_eq!(content_type, "form");
+    assert_eq!(relogin_statuses, &vec![401, 419]);
+
+    let csrf = csrf.as_ref().unwrap().to_config().unwrap();
+    assert_eq!(
+        csrf.source,
+        crate::auth::CsrfSource::Cookie("XSRF-TOKEN".to_string())
+    );
+    assert_eq!(csrf.header_name, "X-XSRF-TOKEN");
+
+    let fields = def.secret_config_fields();
+    assert!(fields.contains(&"password".to_string()));
+    assert!(!fields.contains(&"username".to_string()));
+}
+
+#[test]
+fn test_cookie_session_invalid_csrf_source() {
+    let yaml = r#"
+name: legacy
+base_url: https://admin.example.com
+auth:
+  type: cookie_session
+  login_url: https://admin.example.com/login
+  body: '{}'
+  csrf:
+    source: meta_tag
+    name: csrf-token
+streams:
+  - name: users
+    request:
+      path: /api/users
+"#;
+
+    let err = load_connector_from_str(yaml).unwrap_err();
+    assert!(err.to_string().contains("Invalid CSRF source 'meta_tag'"));
+}
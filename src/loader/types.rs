@@ -2,8 +2,11 @@
 //!
 //! Declarative connector definition types for YAML parsing.
 
+use crate::config::SpecConfig;
+use crate::error::{Error, Result};
+use crate::schema::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // Connector Definition
@@ -23,6 +26,9 @@ pub struct ConnectorDefinition {
     /// Authentication configuration
     #[serde(default)]
     pub auth: Option<AuthDefinition>,
+    /// Named auth profiles that streams can select with `auth: <name>`
+    #[serde(default)]
+    pub auth_profiles: HashMap<String, AuthDefinition>,
     /// HTTP client configuration
     #[serde(default)]
     pub http: HttpDefinition,
@@ -34,6 +40,61 @@ pub struct ConnectorDefinition {
     /// Global headers
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    /// Configuration specification (properties marked `secret: true`)
+    #[serde(default)]
+    pub spec: Option<SpecConfig>,
+}
+
+impl ConnectorDefinition {
+    /// Config fields that hold secrets
+    ///
+    /// Combines properties marked `secret` in the spec with config values
+    /// referenced by credential-bearing auth fields (e.g., `client_secret:
+    /// "{{ config.client_secret }}"`). Identifiers like `client_id` stay
+    /// visible.
+    pub fn secret_config_fields(&self) -> Vec<String> {
+        let mut fields = self
+            .spec
+            .as_ref()
+            .map(SpecConfig::secret_fields)
+            .unwrap_or_default();
+
+        for auth in self.auth_definitions() {
+            for template in auth.credential_templates() {
+                for var in crate::template::extract_variables(&template) {
+                    if let Some(field) = var.strip_prefix("config.") {
+                        if !fields.iter().any(|f| f == field) {
+                            fields.push(field.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        fields
+    }
+
+    /// Auth definition for a profile name (`None` selects the connector-level auth)
+    pub fn auth_profile(&self, profile: Option<&str>) -> Result<Option<&AuthDefinition>> {
+        match profile {
+            None => Ok(self.auth.as_ref()),
+            Some(name) => self
+                .auth_profiles
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| Error::config(format!("Unknown auth profile '{name}'"))),
+        }
+    }
+
+    /// Auth definition used by a stream
+    pub fn stream_auth(&self, stream: &StreamDefinition) -> Result<Option<&AuthDefinition>> {
+        self.auth_profile(stream.auth.as_deref())
+    }
+
+    /// All auth definitions (connector-level auth and named profiles)
+    pub fn auth_definitions(&self) -> impl Iterator<Item = &AuthDefinition> {
+        self.auth.iter().chain(self.auth_profiles.values())
+    }
 }
 
 /// Connection check configuration
@@ -124,15 +185,127 @@ pub enum AuthDefinition {
         #[serde(default)]
         header_prefix: String,
     },
+    /// Cookie session authentication (login sets session cookies)
+    #[serde(rename = "cookie_session")]
+    CookieSession {
+        /// Login URL
+        login_url: String,
+        /// Login HTTP method
+        #[serde(default = "default_login_method")]
+        login_method: String,
+        /// Login body (template, JSON object)
+        body: String,
+        /// Login body encoding: json or form
+        #[serde(default = "default_login_content_type")]
+        content_type: String,
+        /// CSRF token handling
+        #[serde(default)]
+        csrf: Option<CsrfDefinition>,
+        /// Response statuses that trigger a re-login
+        #[serde(default = "default_relogin_statuses")]
+        relogin_statuses: Vec<u16>,
+    },
     /// No authentication
     #[serde(rename = "none")]
     None,
 }
 
+/// Auth and login body fields that carry credentials
+const CREDENTIAL_FIELDS: &[&str] = &[
+    "client_secret",
+    "password",
+    "api_key",
+    "token",
+    "refresh_token",
+];
+
+impl AuthDefinition {
+    /// Templates of the fields that carry credentials
+    ///
+    /// Login bodies contribute the values of their credential keys
+    /// (e.g., `password`).
+    pub fn credential_templates(&self) -> Vec<String> {
+        match self {
+            Self::ApiKey { value, .. } => vec![value.clone()],
+            Self::Bearer { token } => vec![token.clone()],
+            Self::Basic { password, .. } => vec![password.clone()],
+            Self::OAuth2ClientCredentials { client_secret, .. } => vec![client_secret.clone()],
+            Self::OAuth2RefreshToken {
+                client_secret,
+                refresh_token,
+                ..
+            } => vec![client_secret.clone(), refresh_token.clone()],
+            Self::SessionToken { body, .. } | Self::CookieSession { body, .. } => {
+                let Ok(serde_json::Value::Object(map)) = serde_json::from_str(body) else {
+                    return Vec::new();
+                };
+                CREDENTIAL_FIELDS
+                    .iter()
+                    .filter_map(|field| map.get(*field))
+                    .map(|value| match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect()
+            }
+            Self::None => Vec::new(),
+        }
+    }
+}
+
 fn default_auth_location() -> String {
     "header".to_string()
 }
 
+fn default_login_method() -> String {
+    "POST".to_string()
+}
+
+fn default_login_content_type() -> String {
+    "json".to_string()
+}
+
+fn default_relogin_statuses() -> Vec<u16> {
+    vec![401, 419]
+}
+
+/// CSRF token definition for cookie sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CsrfDefinition {
+    /// Where the token comes from: header, cookie or body
+    pub source: String,
+    /// Header name, cookie name or JSON path (for body)
+    pub name: String,
+    /// Request header to send the token in (defaults to the source header
+    /// name, or X-CSRF-Token)
+    #[serde(default)]
+    pub header_name: Option<String>,
+}
+
+impl CsrfDefinition {
+    /// Convert to the runtime CSRF config
+    pub fn to_config(&self) -> Result<crate::auth::CsrfConfig> {
+        use crate::auth::{CsrfConfig, CsrfSource};
+
+        let source = match self.source.as_str() {
+            "header" => CsrfSource::Header(self.name.clone()),
+            "cookie" => CsrfSource::Cookie(self.name.clone()),
+            "body" => CsrfSource::Body(self.name.clone()),
+            other => {
+                return Err(Error::config(format!(
+                    "Invalid CSRF source '{other}' (expected header, cookie or body)"
+                )))
+            }
+        };
+        let header_name = self.header_name.clone().unwrap_or_else(|| match &source {
+            CsrfSource::Header(name) => name.clone(),
+            _ => "X-CSRF-Token".to_string(),
+        });
+        Ok(CsrfConfig::new(source, header_name))
+    }
+}
+
 // ============================================================================
 // HTTP Definition
 // ============================================================================
@@ -153,6 +326,9 @@ pub struct HttpDefinition {
     /// User agent
     #[serde(default)]
     pub user_agent: Option<String>,
+    /// Refresh OAuth2/session tokens this many seconds before they expire
+    #[serde(default = "default_token_refresh_margin")]
+    pub token_refresh_margin_secs: u64,
 }
 
 impl Default for HttpDefinition {
@@ -162,10 +338,15 @@ impl Default for HttpDefinition {
             max_retries: default_retries(),
             rate_limit_rps: None,
             user_agent: None,
+            token_refresh_margin_secs: default_token_refresh_margin(),
         }
     }
 }
 
+fn default_token_refresh_margin() -> u64 {
+    30
+}
+
 fn default_timeout() -> u64 {
     30
 }
@@ -192,6 +373,9 @@ pub struct StreamDefinition {
     /// Pagination configuration
     #[serde(default)]
     pub pagination: Option<PaginationDefinition>,
+    /// Pagination safeguards (page limit and loop detection)
+    #[serde(default)]
+    pub pagination_limits: PaginationLimitsDefinition,
     /// Partition router
     #[serde(default)]
     pub partition: Option<PartitionDefinition>,
@@ -204,6 +388,26 @@ pub struct StreamDefinition {
     /// Stream-specific headers
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    /// Auth profile name (defaults to the connector-level auth)
+    #[serde(default)]
+    pub auth: Option<String>,
+    /// Declared JSON schema (guides CSV type coercion)
+    #[serde(default)]
+    pub schema: Option<JsonSchema>,
+    /// Record transformations, applied in order before records are emitted
+    #[serde(default)]
+    pub transformations: Vec<TransformationDefinition>,
+}
+
+impl StreamDefinition {
+    /// Build the stream's record transformation pipeline
+    pub fn transform_pipeline(&self) -> Result<crate::transform::TransformPipeline> {
+        self.transformations
+            .iter()
+            .map(TransformationDefinition::to_transformation)
+            .collect::<Result<Vec<_>>>()
+            .map(crate::transform::TransformPipeline::new)
+    }
 }
 
 // ============================================================================
@@ -228,12 +432,176 @@ pub struct RequestDefinition {
     /// Content type
     #[serde(default)]
     pub content_type: Option<String>,
+    /// GraphQL query (sent as a POST body instead of `body`)
+    #[serde(default)]
+    pub graphql: Option<GraphqlDefinition>,
+}
+
+impl RequestDefinition {
+    /// Parse the HTTP method (GraphQL requests are always POST)
+    pub fn http_method(&self) -> Result<reqwest::Method> {
+        if self.graphql.is_some() {
+            return Ok(reqwest::Method::POST);
+        }
+        self.method
+            .to_uppercase()
+            .parse()
+            .map_err(|_| Error::config(format!("Invalid HTTP method: {}", self.method)))
+    }
 }
 
 fn default_method() -> String {
     "GET".to_string()
 }
 
+/// GraphQL query definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GraphqlDefinition {
+    /// Query document (template)
+    pub query: String,
+    /// Query variables (string values are templates)
+    #[serde(default)]
+    pub variables: serde_json::Value,
+    /// JSON path to the query cost report used for throttling
+    /// (e.g., "extensions.cost" for Shopify)
+    #[serde(default)]
+    pub cost_path: Option<String>,
+}
+
+impl GraphqlDefinition {
+    /// Convert to the runtime GraphQL request
+    pub fn to_request(&self) -> crate::http::GraphqlRequest {
+        let request =
+            crate::http::GraphqlRequest::new(&self.query).with_variables(self.variables.clone());
+        match &self.cost_path {
+            Some(path) => request.with_cost_path(path),
+            None => request,
+        }
+    }
+}
+
+// ============================================================================
+// Transformation Definition
+// ============================================================================
+
+/// Record transformation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformationDefinition {
+    /// Set a field to a value (string values are templates)
+    AddField {
+        /// Field to set (dot path)
+        field: String,
+        /// Value or template (e.g., "{{ partition.account_id }}")
+        value: serde_json::Value,
+    },
+    /// Remove fields
+    RemoveFields {
+        /// Fields to remove (dot paths)
+        fields: Vec<String>,
+    },
+    /// Rename a field
+    RenameField {
+        /// Current field (dot path)
+        from: String,
+        /// New field (dot path)
+        to: String,
+    },
+    /// Flatten a nested object into prefixed top-level fields
+    Flatten {
+        /// Object to flatten (dot path)
+        path: String,
+        /// Separator between key segments
+        #[serde(default = "default_flatten_separator")]
+        separator: String,
+        /// Key prefix (defaults to the path; empty for none)
+        #[serde(default)]
+        prefix: Option<String>,
+    },
+    /// Cast a field to another type
+    Cast {
+        /// Field to cast (dot path)
+        field: String,
+        /// Target type (string, integer, number or boolean)
+        to: String,
+    },
+    /// Replace a field with its SHA-256 hash
+    Hash {
+        /// Field to hash (dot path)
+        field: String,
+        /// Salt prepended before hashing (template)
+        #[serde(default)]
+        salt: Option<String>,
+    },
+    /// Keep only records matching a predicate
+    Filter {
+        /// Field to compare (dot path)
+        field: String,
+        /// Comparison operator (eq, ne, gt, gte, lt, lte)
+        #[serde(default = "default_filter_op")]
+        op: String,
+        /// Value to compare with (string values are templates)
+        #[serde(default)]
+        value: serde_json::Value,
+    },
+}
+
+impl TransformationDefinition {
+    /// Convert to the runtime transformation
+    pub fn to_transformation(&self) -> Result<crate::transform::Transformation> {
+        use crate::transform::{CastType, Transformation};
+
+        Ok(match self {
+            Self::AddField { field, value } => Transformation::AddField {
+                path: field.clone(),
+                value: value.clone(),
+            },
+            Self::RemoveFields { fields } => Transformation::RemoveFields {
+                paths: fields.clone(),
+            },
+            Self::RenameField { from, to } => Transformation::RenameField {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            Self::Flatten {
+                path,
+                separator,
+                prefix,
+            } => Transformation::Flatten {
+                path: path.clone(),
+                separator: separator.clone(),
+                prefix: prefix.clone(),
+            },
+            Self::Cast { field, to } => Transformation::Cast {
+                path: field.clone(),
+                to: CastType::parse(to).ok_or_else(|| {
+                    Error::config(format!(
+                        "invalid cast type '{to}' (expected string, integer, number or boolean)"
+                    ))
+                })?,
+            },
+            Self::Hash { field, salt } => Transformation::Hash {
+                path: field.clone(),
+                salt: salt.clone(),
+            },
+            Self::Filter { field, op, value } => Transformation::Filter {
+                path: field.clone(),
+                op: crate::partition::CompareOp::parse(op).ok_or_else(|| {
+                    Error::config(format!(
+                        "invalid filter op '{op}' (expected eq, ne, gt, gte, lt or lte)"
+                    ))
+                })?,
+                value: value.clone(),
+            },
+        })
+    }
+}
+
+fn default_flatten_separator() -> String {
+    "_".to_string()
+}
+
 // ============================================================================
 // Decoder Definition
 // ============================================================================
@@ -244,12 +612,27 @@ fn default_method() -> String {
 pub enum DecoderDefinition {
     /// JSON decoder
     Json {
-        /// JSON path to records array
+        /// JSONPath to records (an array, or a wildcard/filter selection)
         #[serde(default)]
         records_path: Option<String>,
+        /// Several JSONPaths whose records are concatenated in order
+        /// (e.g., `data[*]` and `included[*]`)
+        #[serde(default)]
+        records_paths: Vec<String>,
+        /// Turn object entries into records, injecting the key under this field
+        /// (for responses keyed by ID or date)
+        #[serde(default)]
+        map_key_field: Option<String>,
+        /// Nested array to unnest into one record per element (parent fields carried along)
+        #[serde(default)]
+        unnest: Option<String>,
     },
     /// JSONL (newline-delimited JSON) decoder
-    Jsonl,
+    Jsonl {
+        /// Invalid lines: fail the page or quarantine them (fail, quarantine)
+        #[serde(default = "default_on_error")]
+        on_error: String,
+    },
     /// CSV decoder
     Csv {
         /// Delimiter character
@@ -258,17 +641,97 @@ pub enum DecoderDefinition {
         /// Whether first row is header
         #[serde(default = "default_true")]
         has_header: bool,
+        /// Quote character
+        #[serde(default = "default_csv_quote")]
+        quote: char,
+        /// Escape character inside quoted fields (quotes are doubled otherwise)
+        #[serde(default)]
+        escape: Option<char>,
+        /// Cell type coercion (none, schema or infer)
+        #[serde(default = "default_csv_coerce")]
+        coerce: String,
+        /// Unreadable or ragged rows: fail the page or quarantine them (fail, quarantine)
+        #[serde(default = "default_on_error")]
+        on_error: String,
     },
     /// XML decoder
     Xml {
-        /// XPath to records
+        /// Path to record elements (`items.item`, `/root/items/item` or `//item`)
         records_path: String,
+        /// Keep namespace prefixes in element names (default: local names)
+        #[serde(default)]
+        keep_prefixes: bool,
+        /// Text and attribute type coercion (none, schema or infer)
+        #[serde(default = "default_csv_coerce")]
+        coerce: String,
+    },
+    /// GraphQL connection decoder (records from `edges[].node` or `nodes`)
+    Graphql {
+        /// JSON path to the connection (e.g., "data.orders")
+        connection_path: String,
+    },
+    /// Parquet file decoder (keeps the file's Arrow schema)
+    Parquet,
+    /// Avro object container file decoder (keeps the writer schema)
+    Avro,
+    /// Compressed or archived download (gzip, zstd, zip, tar)
+    Archive {
+        /// Container format (auto, gzip, zstd, zip or tar)
+        #[serde(default = "default_archive_format")]
+        format: String,
+        /// Glob selecting files inside zip/tar archives (e.g., "*.csv")
+        #[serde(default)]
+        files: Option<String>,
+        /// Maximum total unpacked size in MiB (guards against decompression bombs)
+        #[serde(default = "default_max_unpacked_mb")]
+        max_unpacked_mb: u64,
+        /// Decoder for the unpacked files
+        inner: Box<DecoderDefinition>,
+    },
+    /// Streaming endpoint decoded as bytes arrive (NDJSON or Server-Sent Events)
+    Stream {
+        /// Wire format (ndjson or sse)
+        #[serde(default = "default_stream_format")]
+        format: String,
+        /// SSE event types to keep (all when empty)
+        #[serde(default)]
+        events: Vec<String>,
     },
 }
 
 impl Default for DecoderDefinition {
     fn default() -> Self {
-        Self::Json { records_path: None }
+        Self::Json {
+            records_path: None,
+            records_paths: Vec::new(),
+            map_key_field: None,
+            unnest: None,
+        }
+    }
+}
+
+impl DecoderDefinition {
+    /// Build the JSON decoder of a `json` definition (`None` for other types)
+    pub fn json_decoder(&self) -> Option<crate::decode::JsonDecoder> {
+        let Self::Json {
+            records_path,
+            records_paths,
+            map_key_field,
+            unnest,
+        } = self
+        else {
+            return None;
+        };
+
+        let mut decoder =
+            crate::decode::JsonDecoder::with_paths(records_path.iter().chain(records_paths));
+        if let Some(key_field) = map_key_field {
+            decoder = decoder.with_map_key(key_field);
+        }
+        if let Some(path) = unnest {
+            decoder = decoder.with_unnest(path);
+        }
+        Some(decoder)
     }
 }
 
@@ -276,6 +739,30 @@ fn default_csv_delimiter() -> char {
     ','
 }
 
+fn default_csv_quote() -> char {
+    '"'
+}
+
+fn default_csv_coerce() -> String {
+    "none".to_string()
+}
+
+fn default_archive_format() -> String {
+    "auto".to_string()
+}
+
+fn default_max_unpacked_mb() -> u64 {
+    1024
+}
+
+fn default_on_error() -> String {
+    "fail".to_string()
+}
+
+fn default_stream_format() -> String {
+    "ndjson".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -321,13 +808,36 @@ pub enum PaginationDefinition {
     },
     /// Cursor-based pagination
     Cursor {
-        /// Cursor parameter name
+        /// Cursor parameter name (query param, header or body field)
         cursor_param: String,
-        /// Path to next cursor in response
-        cursor_path: String,
-        /// Location: query or header
+        /// Path to next cursor in response body
+        #[serde(default)]
+        cursor_path: Option<String>,
+        /// Response header holding the next cursor (instead of cursor_path)
+        #[serde(default)]
+        cursor_header: Option<String>,
+        /// Location to send the cursor: query, header or body
+        #[serde(default = "default_cursor_location")]
+        location: String,
+        /// Stop condition
+        #[serde(default)]
+        stop: StopConditionDefinition,
+    },
+    /// Keyset pagination (next key from the last record, e.g., `since_id`)
+    Keyset {
+        /// Key parameter name (query param, header or body field)
+        key_param: String,
+        /// Field of the last record holding the next key
+        key_field: String,
+        /// Location to send the key: query, header or body
         #[serde(default = "default_cursor_location")]
         location: String,
+        /// Expected key order across pages: none, asc or desc
+        #[serde(default = "default_key_order")]
+        order: String,
+        /// Stop condition
+        #[serde(default)]
+        stop: StopConditionDefinition,
     },
     /// Link header pagination (RFC 5988)
     LinkHeader {
@@ -340,6 +850,14 @@ pub enum PaginationDefinition {
         /// JSON path to next URL
         next_url_path: String,
     },
+    /// GraphQL Relay connection pagination (`pageInfo.endCursor` into variables)
+    Relay {
+        /// JSON path to the connection's pageInfo (e.g., "data.orders.pageInfo")
+        page_info_path: String,
+        /// Query variable that receives the cursor
+        #[serde(default = "default_cursor_variable")]
+        cursor_variable: String,
+    },
 }
 
 fn default_start_page() -> u32 {
@@ -350,6 +868,14 @@ fn default_cursor_location() -> String {
     "query".to_string()
 }
 
+fn default_key_order() -> String {
+    "none".to_string()
+}
+
+fn default_cursor_variable() -> String {
+    "after".to_string()
+}
+
 fn default_link_rel() -> String {
     "next".to_string()
 }
@@ -380,6 +906,44 @@ pub enum StopConditionDefinition {
     },
 }
 
+/// Pagination safeguards for a stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginationLimitsDefinition {
+    /// Maximum pages to fetch per sync (or per partition)
+    #[serde(default)]
+    pub max_pages: Option<usize>,
+    /// Detect repeated requests and repeated pages
+    #[serde(default = "default_true")]
+    pub detect_loops: bool,
+    /// Action when a limit is hit or a loop is detected: stop, warn or fail
+    #[serde(default = "default_limit_policy")]
+    pub policy: String,
+}
+
+impl Default for PaginationLimitsDefinition {
+    fn default() -> Self {
+        Self {
+            max_pages: None,
+            detect_loops: true,
+            policy: default_limit_policy(),
+        }
+    }
+}
+
+impl PaginationLimitsDefinition {
+    /// Convert to the runtime pagination limits
+    pub fn to_limits(&self) -> crate::engine::PaginationLimits {
+        crate::engine::PaginationLimits::new()
+            .with_max_pages(self.max_pages.unwrap_or(0))
+            .with_loop_detection(self.detect_loops)
+            .with_policy(crate::engine::LimitPolicy::parse(&self.policy).unwrap_or_default())
+    }
+}
+
+fn default_limit_policy() -> String {
+    "warn".to_string()
+}
+
 // ============================================================================
 // Partition Definition
 // ============================================================================
@@ -388,21 +952,41 @@ pub enum StopConditionDefinition {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum PartitionDefinition {
-    /// List of static values
+    /// List of values (static, from config or from a lookup request)
     List {
         /// Field name for partition value
+        #[serde(default)]
         field: String,
-        /// List of values
-        values: Vec<String>,
+        /// Partition fields mapped from object values (field -> path in value)
+        #[serde(default)]
+        fields: BTreeMap<String, String>,
+        /// List of static values
+        #[serde(default)]
+        values: Vec<serde_json::Value>,
+        /// Template rendering to a JSON array or comma-separated list
+        /// (e.g., "{{ config.shop_domains }}")
+        #[serde(default)]
+        values_from: Option<String>,
+        /// Request returning the values
+        #[serde(default)]
+        lookup: Option<PartitionLookupDefinition>,
     },
     /// Parent stream partition
     Parent {
         /// Parent stream name
         stream: String,
-        /// Field to extract from parent records
+        /// Field to extract from parent records (partition ID)
+        #[serde(default)]
         parent_field: String,
         /// Field name in partition context
+        #[serde(default)]
         partition_field: String,
+        /// Additional parent attributes (field -> path in parent record)
+        #[serde(default)]
+        fields: BTreeMap<String, String>,
+        /// Filters deciding which parents fan out
+        #[serde(default)]
+        filters: Vec<ParentFilterDefinition>,
     },
     /// Date/time range partition
     DateRange {
@@ -416,6 +1000,17 @@ pub enum PartitionDefinition {
         start_field: String,
         /// Field name for end date
         end_field: String,
+        /// Output format for dates (strftime)
+        #[serde(default = "default_date_format")]
+        format: String,
+        /// Split or widen windows based on the API's result cap
+        #[serde(default)]
+        adaptive: Option<AdaptiveWindowDefinition>,
+    },
+    /// Cross product of several partitions (e.g., account × day)
+    Cartesian {
+        /// Component partitions, outermost first
+        routers: Vec<PartitionDefinition>,
     },
     /// Async job-based partition (create → poll → download)
     AsyncJob {
@@ -428,6 +1023,64 @@ pub enum PartitionDefinition {
     },
 }
 
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+/// Filter on parent records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParentFilterDefinition {
+    /// Path to the compared field in the parent record
+    pub field: String,
+    /// Comparison operator (eq, ne, gt, gte, lt, lte)
+    #[serde(default = "default_filter_op")]
+    pub op: String,
+    /// Value to compare with (string values are templates)
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+    /// Compare with the child's cursor for the parent's partition instead
+    #[serde(default)]
+    pub child_cursor: bool,
+}
+
+fn default_filter_op() -> String {
+    "eq".to_string()
+}
+
+/// Adaptive window sizing for date range partitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveWindowDefinition {
+    /// Result cap of the API; windows reaching it are split
+    pub max_records: usize,
+    /// JSONPath to the total result count (probed before paging a window)
+    #[serde(default)]
+    pub total_path: Option<String>,
+    /// Smallest window size
+    #[serde(default = "default_min_step")]
+    pub min_step: String,
+    /// Largest window size
+    #[serde(default = "default_max_step")]
+    pub max_step: String,
+}
+
+fn default_min_step() -> String {
+    "1d".to_string()
+}
+
+fn default_max_step() -> String {
+    "30d".to_string()
+}
+
+/// Request returning list partition values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionLookupDefinition {
+    /// Lookup request
+    pub request: RequestDefinition,
+    /// Response decoder
+    #[serde(default)]
+    pub decoder: DecoderDefinition,
+}
+
 /// Async job creation definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -608,6 +1261,7 @@ fn default_batch_size() -> u32 {
 /// Unified connector that can be either REST API or Database
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum UnifiedConnectorDefinition {
     /// REST API connector (default, existing behavior)
     #[serde(rename = "api")]
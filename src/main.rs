@@ -20,11 +20,13 @@
 
 use clap::Parser;
 use solidafy_cdk::cli::{Cli, Runner};
+use solidafy_cdk::secrets::redact::{self, RedactingMakeWriter};
 
 #[tokio::main]
 async fn main() {
-    // Initialize logging
+    // Initialize logging (secrets are redacted from every event)
     tracing_subscriber::fmt()
+        .with_writer(RedactingMakeWriter::new(std::io::stdout))
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive(tracing::Level::INFO.into()),
@@ -35,7 +37,7 @@ async fn main() {
     let runner = Runner::new(cli);
 
     if let Err(e) = runner.run().await {
-        eprintln!("Error: {e}");
+        eprintln!("Error: {}", redact::redact_str(&e.to_string()));
         std::process::exit(1);
     }
 }
@@ -1,6 +1,7 @@
 //! Pagination module
 //!
-//! Supports: Cursor, Offset, Page Number, Link Header, Next URL, Response Body
+//! Supports: Cursor, Keyset, Offset, Page Number, Link Header, Next URL, Response Body,
+//! GraphQL Relay connections
 //!
 //! # Overview
 //!
@@ -12,11 +13,12 @@ mod strategies;
 mod types;
 
 pub use strategies::{
-    CursorPaginator, LinkHeaderPaginator, NextUrlPaginator, NoPaginator, OffsetPaginator,
-    PageNumberPaginator,
+    CursorPaginator, KeysetPaginator, LinkHeaderPaginator, NextUrlPaginator, NoPaginator,
+    OffsetPaginator, PageNumberPaginator, RelayPaginator,
 };
 pub use types::{
-    NextPage, PaginationConfig, PaginationState, Paginator, StopCondition, StopResult,
+    extract_jsonpath_value, set_body_field, CursorSource, KeyOrder, NextPage, PaginationConfig,
+    PaginationState, Paginator, RequestLocation, StopCondition, StopResult,
 };
 
 #[cfg(test)]
@@ -3,7 +3,8 @@
 //! Each strategy handles a specific pagination pattern.
 
 use super::types::{
-    check_stop_condition, NextPage, PaginationState, Paginator, StopCondition, StopResult,
+    check_stop_condition, extract_jsonpath_value, CursorSource, KeyOrder, NextPage,
+    PaginationState, Paginator, RequestLocation, StopCondition, StopResult,
 };
 use crate::auth::extract_jsonpath;
 use reqwest::header::HeaderMap;
@@ -14,24 +15,30 @@ use std::collections::HashMap;
 // Cursor Pagination
 // ============================================================================
 
-/// Cursor-based pagination (e.g., Stripe, Slack)
+/// Cursor-based pagination (e.g., Stripe, Slack, Salesforce)
 ///
 /// Uses a cursor value from the response to fetch the next page.
+/// The cursor is read from the response body or a response header and
+/// written as a query parameter, request header or JSON body field.
 /// Common patterns:
 /// - `?starting_after=obj_123`
 /// - `?cursor=abc123`
+/// - `X-Next-Cursor: abc123` response header
 #[derive(Debug, Clone)]
 pub struct CursorPaginator {
-    /// Query parameter name for cursor
+    /// Parameter name for cursor
     pub cursor_param: String,
-    /// JSONPath to extract cursor from response
-    pub cursor_path: String,
+    /// Where to read the cursor from the response
+    pub source: CursorSource,
+    /// Where to write the cursor into the next request
+    pub location: RequestLocation,
     /// Stop condition
     pub stop_condition: StopCondition,
 }
 
 impl CursorPaginator {
-    /// Create a new cursor paginator
+    /// Create a new cursor paginator reading the cursor from the body
+    /// and sending it as a query parameter
     pub fn new(
         cursor_param: impl Into<String>,
         cursor_path: impl Into<String>,
@@ -39,25 +46,76 @@ impl CursorPaginator {
     ) -> Self {
         Self {
             cursor_param: cursor_param.into(),
-            cursor_path: cursor_path.into(),
+            source: CursorSource::Body(cursor_path.into()),
+            location: RequestLocation::Query,
             stop_condition,
         }
     }
+
+    /// Set where the cursor is read from
+    #[must_use]
+    pub fn with_source(mut self, source: CursorSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set where the cursor is written to
+    #[must_use]
+    pub fn with_location(mut self, location: RequestLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Cursor entry for the given location, if a cursor is set
+    fn cursor_entry(
+        &self,
+        state: &PaginationState,
+        location: RequestLocation,
+    ) -> Option<(String, String)> {
+        located_cursor(&self.cursor_param, self.location, location, state)
+    }
+}
+
+/// Cursor as a `(param, value)` entry if it is sent to `location`
+fn located_cursor(
+    param: &str,
+    configured: RequestLocation,
+    location: RequestLocation,
+    state: &PaginationState,
+) -> Option<(String, String)> {
+    if configured != location {
+        return None;
+    }
+    state
+        .cursor
+        .as_ref()
+        .map(|cursor| (param.to_string(), cursor.clone()))
 }
 
 impl Paginator for CursorPaginator {
     fn initial_params(&self, state: &PaginationState) -> HashMap<String, String> {
-        let mut params = HashMap::new();
-        if let Some(cursor) = &state.cursor {
-            params.insert(self.cursor_param.clone(), cursor.clone());
-        }
-        params
+        self.cursor_entry(state, RequestLocation::Query)
+            .into_iter()
+            .collect()
+    }
+
+    fn request_headers(&self, state: &PaginationState) -> HashMap<String, String> {
+        self.cursor_entry(state, RequestLocation::Header)
+            .into_iter()
+            .collect()
+    }
+
+    fn request_body_fields(&self, state: &PaginationState) -> HashMap<String, Value> {
+        self.cursor_entry(state, RequestLocation::Body)
+            .map(|(key, cursor)| (key, Value::String(cursor)))
+            .into_iter()
+            .collect()
     }
 
     fn process_response(
         &self,
         body: &Value,
-        _headers: &HeaderMap,
+        headers: &HeaderMap,
         records_count: usize,
         state: &mut PaginationState,
     ) -> NextPage {
@@ -72,16 +130,166 @@ impl Paginator for CursorPaginator {
         }
 
         // Extract cursor for next page
-        if let Some(cursor) = extract_jsonpath(body, &self.cursor_path) {
-            if cursor.is_empty() {
+        match self.source.extract(body, headers) {
+            Some(cursor) if !cursor.is_empty() => {
+                state.set_cursor(cursor.clone());
+                if self.location == RequestLocation::Query {
+                    NextPage::with_param(&self.cursor_param, cursor)
+                } else {
+                    // Header and body cursors are applied via request_headers
+                    // and request_body_fields
+                    NextPage::with_params(HashMap::new())
+                }
+            }
+            _ => {
                 state.mark_done();
-                return NextPage::Done;
+                NextPage::Done
             }
-            state.set_cursor(cursor.clone());
-            NextPage::with_param(&self.cursor_param, cursor)
-        } else {
+        }
+    }
+}
+
+// ============================================================================
+// Keyset Pagination
+// ============================================================================
+
+/// Keyset ("since-id") pagination (e.g., Shopify, Stripe, Zendesk exports)
+///
+/// Derives the next key from a field of the last record on each page.
+/// Common patterns:
+/// - `?since_id=1234`
+/// - `?starting_after=cus_123`
+///
+/// Pagination fails instead of looping when a key does not advance, moves
+/// against the configured order, or repeats an earlier key.
+#[derive(Debug, Clone)]
+pub struct KeysetPaginator {
+    /// Parameter name for the key
+    pub key_param: String,
+    /// Field of the last record holding the key (dots address nested fields)
+    pub key_field: String,
+    /// Where to write the key into the next request
+    pub location: RequestLocation,
+    /// Expected order of keys across pages
+    pub order: KeyOrder,
+    /// Stop condition
+    pub stop_condition: StopCondition,
+}
+
+impl KeysetPaginator {
+    /// Create a new keyset paginator sending the key as a query parameter
+    pub fn new(
+        key_param: impl Into<String>,
+        key_field: impl Into<String>,
+        stop_condition: StopCondition,
+    ) -> Self {
+        Self {
+            key_param: key_param.into(),
+            key_field: key_field.into(),
+            location: RequestLocation::Query,
+            order: KeyOrder::Unordered,
+            stop_condition,
+        }
+    }
+
+    /// Set where the key is written to
+    #[must_use]
+    pub fn with_location(mut self, location: RequestLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Set the expected key order
+    #[must_use]
+    pub fn with_order(mut self, order: KeyOrder) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+impl Paginator for KeysetPaginator {
+    fn initial_params(&self, state: &PaginationState) -> HashMap<String, String> {
+        located_cursor(
+            &self.key_param,
+            self.location,
+            RequestLocation::Query,
+            state,
+        )
+        .into_iter()
+        .collect()
+    }
+
+    fn request_headers(&self, state: &PaginationState) -> HashMap<String, String> {
+        located_cursor(
+            &self.key_param,
+            self.location,
+            RequestLocation::Header,
+            state,
+        )
+        .into_iter()
+        .collect()
+    }
+
+    fn request_body_fields(&self, state: &PaginationState) -> HashMap<String, Value> {
+        located_cursor(&self.key_param, self.location, RequestLocation::Body, state)
+            .map(|(key, cursor)| (key, Value::String(cursor)))
+            .into_iter()
+            .collect()
+    }
+
+    fn observe_records(&self, records: &[Value], state: &mut PaginationState) {
+        state.last_key = records
+            .last()
+            .and_then(|record| extract_jsonpath(record, &self.key_field));
+    }
+
+    fn process_response(
+        &self,
+        body: &Value,
+        _headers: &HeaderMap,
+        records_count: usize,
+        state: &mut PaginationState,
+    ) -> NextPage {
+        state.add_fetched(records_count as u64);
+        let key = state.last_key.take();
+
+        // Check stop condition
+        if check_stop_condition(&self.stop_condition, body, records_count, state)
+            == StopResult::Stop
+        {
             state.mark_done();
-            NextPage::Done
+            return NextPage::Done;
+        }
+
+        let Some(key) = key.filter(|k| !k.is_empty()) else {
+            state.mark_done();
+            return NextPage::Done;
+        };
+
+        if let Some(previous) = &state.cursor {
+            if *previous == key {
+                return NextPage::failed(format!(
+                    "keyset key '{key}' did not advance past the previous page"
+                ));
+            }
+            if !self.order.advances(previous, &key) {
+                return NextPage::failed(format!(
+                    "keyset key went from '{previous}' to '{key}', against the expected {} order",
+                    self.order
+                ));
+            }
+        }
+        if !state.record_cursor(&key) {
+            return NextPage::failed(format!(
+                "keyset key '{key}' was already requested (pagination loop)"
+            ));
+        }
+
+        state.set_cursor(key.clone());
+        if self.location == RequestLocation::Query {
+            NextPage::with_param(&self.key_param, key)
+        } else {
+            NextPage::with_params(HashMap::new())
         }
     }
 }
@@ -405,6 +613,86 @@ impl Paginator for NextUrlPaginator {
     }
 }
 
+// ============================================================================
+// Relay (GraphQL) Pagination
+// ============================================================================
+
+/// GraphQL Relay connection pagination (e.g., Shopify, GitHub)
+///
+/// Follows `pageInfo { hasNextPage endCursor }` and injects the end cursor
+/// into the query variables of the next request.
+#[derive(Debug, Clone)]
+pub struct RelayPaginator {
+    /// JSONPath to the connection's `pageInfo` object
+    pub page_info_path: String,
+    /// Query variable that receives the cursor (e.g., "after")
+    pub cursor_variable: String,
+}
+
+impl RelayPaginator {
+    /// Create a new Relay paginator
+    pub fn new(page_info_path: impl Into<String>, cursor_variable: impl Into<String>) -> Self {
+        Self {
+            page_info_path: page_info_path.into(),
+            cursor_variable: cursor_variable.into(),
+        }
+    }
+}
+
+impl Paginator for RelayPaginator {
+    fn initial_params(&self, _state: &PaginationState) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn request_body_fields(&self, state: &PaginationState) -> HashMap<String, Value> {
+        state
+            .cursor
+            .as_ref()
+            .map(|cursor| {
+                (
+                    format!("variables.{}", self.cursor_variable),
+                    Value::String(cursor.clone()),
+                )
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn process_response(
+        &self,
+        body: &Value,
+        _headers: &HeaderMap,
+        records_count: usize,
+        state: &mut PaginationState,
+    ) -> NextPage {
+        state.add_fetched(records_count as u64);
+
+        let page_info = extract_jsonpath_value(body, &self.page_info_path);
+        let has_next = page_info
+            .as_ref()
+            .and_then(|info| info.get("hasNextPage"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let end_cursor = page_info
+            .as_ref()
+            .and_then(|info| info.get("endCursor"))
+            .and_then(Value::as_str)
+            .filter(|cursor| !cursor.is_empty());
+
+        match end_cursor {
+            Some(cursor) if has_next => {
+                state.set_cursor(cursor.to_string());
+                state.next_page();
+                NextPage::with_params(HashMap::new())
+            }
+            _ => {
+                state.mark_done();
+                NextPage::Done
+            }
+        }
+    }
+}
+
 // ============================================================================
 // No Pagination
 // ============================================================================
@@ -418,6 +706,10 @@ impl Paginator for NoPaginator {
         HashMap::new()
     }
 
+    fn reads_response(&self) -> bool {
+        false
+    }
+
     fn process_response(
         &self,
         _body: &Value,
@@ -2,7 +2,7 @@
 
 use super::*;
 use reqwest::header::{HeaderMap, HeaderValue};
-use serde_json::json;
+use serde_json::{json, Value};
 
 // ============================================================================
 // NextPage Tests
@@ -222,6 +222,263 @@ fn test_cursor_paginator_stops_on_field() {
     assert!(next.is_done());
 }
 
+#[test]
+fn test_cursor_paginator_header_source() {
+    let paginator = CursorPaginator::new("locator", "", StopCondition::EmptyPage)
+        .with_source(CursorSource::Header("Sforce-Locator".to_string()));
+
+    let body = json!({"records": [{"id": 1}]});
+    let mut headers = HeaderMap::new();
+    headers.insert("Sforce-Locator", HeaderValue::from_static("loc_2"));
+    let mut state = PaginationState::new();
+
+    let next = paginator.process_response(&body, &headers, 1, &mut state);
+    assert!(next.is_continue());
+    assert_eq!(state.cursor, Some("loc_2".to_string()));
+
+    // Salesforce sends "null" on the last page
+    headers.insert("Sforce-Locator", HeaderValue::from_static("null"));
+    let next = paginator.process_response(&body, &headers, 1, &mut state);
+    assert!(next.is_done());
+
+    // Missing header also stops
+    let mut state = PaginationState::new();
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_done());
+}
+
+#[test]
+fn test_cursor_paginator_header_location() {
+    let paginator = CursorPaginator::new("X-Page-Token", "next", StopCondition::EmptyPage)
+        .with_location(RequestLocation::Header);
+
+    let mut state = PaginationState::new();
+    assert!(paginator.request_headers(&state).is_empty());
+
+    let body = json!({"data": [{"id": 1}], "next": "tok_2"});
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_continue());
+
+    assert!(paginator.initial_params(&state).is_empty());
+    assert!(paginator.request_body_fields(&state).is_empty());
+    assert_eq!(
+        paginator.request_headers(&state).get("X-Page-Token"),
+        Some(&"tok_2".to_string())
+    );
+}
+
+#[test]
+fn test_cursor_paginator_body_location() {
+    let paginator = CursorPaginator::new("page.after", "meta.after", StopCondition::EmptyPage)
+        .with_location(RequestLocation::Body);
+
+    let mut state = PaginationState::new();
+    state.set_cursor("abc".to_string());
+
+    assert!(paginator.initial_params(&state).is_empty());
+    let fields = paginator.request_body_fields(&state);
+    assert_eq!(fields.get("page.after"), Some(&json!("abc")));
+
+    let mut body = json!({"filter": {"active": true}});
+    for (key, value) in fields {
+        set_body_field(&mut body, &key, value);
+    }
+    assert_eq!(
+        body,
+        json!({"filter": {"active": true}, "page": {"after": "abc"}})
+    );
+}
+
+#[test]
+fn test_request_location_parse() {
+    assert_eq!(
+        RequestLocation::parse("query"),
+        Some(RequestLocation::Query)
+    );
+    assert_eq!(
+        RequestLocation::parse("header"),
+        Some(RequestLocation::Header)
+    );
+    assert_eq!(RequestLocation::parse("body"), Some(RequestLocation::Body));
+    assert_eq!(RequestLocation::parse("cookie"), None);
+}
+
+#[test]
+fn test_set_body_field() {
+    let mut body = serde_json::Value::Null;
+    set_body_field(&mut body, "cursor", json!("a"));
+    assert_eq!(body, json!({"cursor": "a"}));
+
+    set_body_field(&mut body, "variables.after", json!("b"));
+    set_body_field(&mut body, "cursor", json!("c"));
+    assert_eq!(body, json!({"cursor": "c", "variables": {"after": "b"}}));
+}
+
+// ============================================================================
+// Keyset Paginator Tests
+// ============================================================================
+
+fn keyset_page(
+    paginator: &KeysetPaginator,
+    records: &[Value],
+    state: &mut PaginationState,
+) -> NextPage {
+    paginator.observe_records(records, state);
+    paginator.process_response(&json!({}), &HeaderMap::new(), records.len(), state)
+}
+
+#[test]
+fn test_keyset_paginator_advances() {
+    let paginator = KeysetPaginator::new("since_id", "id", StopCondition::EmptyPage);
+    let mut state = PaginationState::new();
+    assert!(paginator.initial_params(&state).is_empty());
+
+    let next = keyset_page(
+        &paginator,
+        &[json!({"id": 1}), json!({"id": 2})],
+        &mut state,
+    );
+    assert!(next.is_continue());
+    assert_eq!(
+        paginator.initial_params(&state).get("since_id"),
+        Some(&"2".to_string())
+    );
+
+    let next = keyset_page(&paginator, &[json!({"id": 3})], &mut state);
+    assert!(next.is_continue());
+    assert_eq!(state.cursor, Some("3".to_string()));
+
+    let next = keyset_page(&paginator, &[], &mut state);
+    assert!(next.is_done());
+    assert_eq!(state.total_fetched, 3);
+}
+
+#[test]
+fn test_keyset_paginator_nested_key_field() {
+    let paginator = KeysetPaginator::new("after", "meta.key", StopCondition::EmptyPage);
+    let mut state = PaginationState::new();
+
+    let next = keyset_page(&paginator, &[json!({"meta": {"key": "k1"}})], &mut state);
+    assert!(next.is_continue());
+    assert_eq!(state.cursor, Some("k1".to_string()));
+}
+
+#[test]
+fn test_keyset_paginator_missing_key_stops() {
+    let paginator = KeysetPaginator::new("since_id", "id", StopCondition::EmptyPage);
+    let mut state = PaginationState::new();
+
+    let next = keyset_page(&paginator, &[json!({"name": "no id"})], &mut state);
+    assert!(next.is_done());
+}
+
+#[test]
+fn test_keyset_paginator_stop_condition() {
+    let paginator = KeysetPaginator::new(
+        "starting_after",
+        "id",
+        StopCondition::field("has_more", json!(false)),
+    );
+    let mut state = PaginationState::new();
+
+    paginator.observe_records(&[json!({"id": "cus_1"})], &mut state);
+    let body = json!({"has_more": false});
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_done());
+}
+
+#[test]
+fn test_keyset_paginator_fails_when_key_does_not_advance() {
+    let paginator = KeysetPaginator::new("since_id", "id", StopCondition::EmptyPage);
+    let mut state = PaginationState::new();
+
+    keyset_page(&paginator, &[json!({"id": 5})], &mut state);
+    let next = keyset_page(&paginator, &[json!({"id": 5})], &mut state);
+    assert!(next.is_failed());
+    assert!(matches!(next, NextPage::Failed { reason } if reason.contains("did not advance")));
+}
+
+#[test]
+fn test_keyset_paginator_fails_on_order_violation() {
+    let paginator = KeysetPaginator::new("since_id", "id", StopCondition::EmptyPage)
+        .with_order(KeyOrder::Ascending);
+    let mut state = PaginationState::new();
+
+    // Numeric keys compare as numbers, not strings
+    assert!(keyset_page(&paginator, &[json!({"id": 9})], &mut state).is_continue());
+    assert!(keyset_page(&paginator, &[json!({"id": 10})], &mut state).is_continue());
+
+    let next = keyset_page(&paginator, &[json!({"id": 4})], &mut state);
+    assert!(matches!(next, NextPage::Failed { reason } if reason.contains("asc order")));
+}
+
+#[test]
+fn test_keyset_paginator_fails_on_loop() {
+    let paginator = KeysetPaginator::new("after", "id", StopCondition::EmptyPage);
+    let mut state = PaginationState::new();
+
+    keyset_page(&paginator, &[json!({"id": "a"})], &mut state);
+    keyset_page(&paginator, &[json!({"id": "b"})], &mut state);
+    let next = keyset_page(&paginator, &[json!({"id": "a"})], &mut state);
+    assert!(matches!(next, NextPage::Failed { reason } if reason.contains("pagination loop")));
+}
+
+#[test]
+fn test_keyset_paginator_header_and_body_location() {
+    let paginator = KeysetPaginator::new("X-Since-Id", "id", StopCondition::EmptyPage)
+        .with_location(RequestLocation::Header);
+    let mut state = PaginationState::new();
+    keyset_page(&paginator, &[json!({"id": 7})], &mut state);
+    assert!(paginator.initial_params(&state).is_empty());
+    assert_eq!(
+        paginator.request_headers(&state).get("X-Since-Id"),
+        Some(&"7".to_string())
+    );
+
+    let paginator = KeysetPaginator::new("filter.since_id", "id", StopCondition::EmptyPage)
+        .with_location(RequestLocation::Body);
+    let mut state = PaginationState::new();
+    keyset_page(&paginator, &[json!({"id": 7})], &mut state);
+    assert!(paginator.request_headers(&state).is_empty());
+    assert_eq!(
+        paginator.request_body_fields(&state).get("filter.since_id"),
+        Some(&json!("7"))
+    );
+}
+
+#[test]
+fn test_key_order_parse_and_advances() {
+    assert_eq!(KeyOrder::parse("none"), Some(KeyOrder::Unordered));
+    assert_eq!(KeyOrder::parse("asc"), Some(KeyOrder::Ascending));
+    assert_eq!(KeyOrder::parse("desc"), Some(KeyOrder::Descending));
+    assert_eq!(KeyOrder::parse("sideways"), None);
+
+    assert!(KeyOrder::Unordered.advances("b", "a"));
+    assert!(KeyOrder::Ascending.advances("2", "10"));
+    assert!(KeyOrder::Ascending.advances("a", "b"));
+    assert!(!KeyOrder::Ascending.advances("b", "a"));
+    assert!(KeyOrder::Descending.advances("10", "2"));
+    assert!(!KeyOrder::Descending.advances("2", "10"));
+}
+
+#[test]
+fn test_key_order_large_integer_keys() {
+    // 19-digit IDs one apart round to the same f64
+    let (previous, next) = ("1234567890123456789", "1234567890123456790");
+    assert!(KeyOrder::Ascending.advances(previous, next));
+    assert!(KeyOrder::Unordered.advances(previous, next));
+    assert!(!KeyOrder::Ascending.advances(next, previous));
+    assert!(!KeyOrder::Unordered.advances(previous, previous));
+
+    // Beyond i128, and non-integers
+    assert!(KeyOrder::Ascending.advances(
+        "300000000000000000000000000000000000000",
+        "300000000000000000000000000000000000001"
+    ));
+    assert!(KeyOrder::Ascending.advances("1.5", "2.25"));
+    assert!(KeyOrder::Descending.advances("-1", "-2.5"));
+}
+
 // ============================================================================
 // Offset Paginator Tests
 // ============================================================================
@@ -510,6 +767,53 @@ fn test_next_url_paginator_stops_empty() {
     assert!(next.is_done());
 }
 
+// ============================================================================
+// Relay Paginator Tests
+// ============================================================================
+
+#[test]
+fn test_relay_paginator() {
+    let paginator = RelayPaginator::new("data.orders.pageInfo", "after");
+    let mut state = PaginationState::new();
+    assert!(paginator.request_body_fields(&state).is_empty());
+
+    let body = json!({"data": {"orders": {
+        "edges": [{"node": {"id": 1}}],
+        "pageInfo": {"hasNextPage": true, "endCursor": "c1"}
+    }}});
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_continue());
+    assert!(paginator.initial_params(&state).is_empty());
+    assert_eq!(
+        paginator.request_body_fields(&state).get("variables.after"),
+        Some(&json!("c1"))
+    );
+
+    let body = json!({"data": {"orders": {
+        "edges": [{"node": {"id": 2}}],
+        "pageInfo": {"hasNextPage": false, "endCursor": "c2"}
+    }}});
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_done());
+    assert_eq!(state.total_fetched, 2);
+}
+
+#[test]
+fn test_relay_paginator_missing_page_info() {
+    let paginator = RelayPaginator::new("data.orders.pageInfo", "after");
+    let mut state = PaginationState::new();
+
+    let body = json!({"data": {"orders": {"edges": []}}});
+    let next = paginator.process_response(&body, &HeaderMap::new(), 0, &mut state);
+    assert!(next.is_done());
+
+    // hasNextPage without a cursor cannot advance
+    let body = json!({"data": {"orders": {"pageInfo": {"hasNextPage": true, "endCursor": null}}}});
+    let mut state = PaginationState::new();
+    let next = paginator.process_response(&body, &HeaderMap::new(), 1, &mut state);
+    assert!(next.is_done());
+}
+
 // ============================================================================
 // PaginationConfig Tests
 // ============================================================================
@@ -5,7 +5,8 @@
 use crate::auth::extract_jsonpath;
 use reqwest::header::HeaderMap;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 /// Result of the next page computation
 #[derive(Debug, Clone)]
@@ -19,6 +20,11 @@ pub enum NextPage {
     },
     /// No more pages
     Done,
+    /// Pagination cannot continue safely (e.g., a key that stopped advancing)
+    Failed {
+        /// Why pagination stopped
+        reason: String,
+    },
 }
 
 impl NextPage {
@@ -48,11 +54,23 @@ impl NextPage {
         }
     }
 
+    /// Create a failed result
+    pub fn failed(reason: impl Into<String>) -> Self {
+        Self::Failed {
+            reason: reason.into(),
+        }
+    }
+
     /// Check if this is a done result
     pub fn is_done(&self) -> bool {
         matches!(self, Self::Done)
     }
 
+    /// Check if this is a failed result
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed { .. })
+    }
+
     /// Check if this is a continue result
     pub fn is_continue(&self) -> bool {
         matches!(self, Self::Continue { .. })
@@ -68,10 +86,12 @@ pub enum PaginationConfig {
 
     /// Cursor-based pagination (e.g., Stripe)
     Cursor {
-        /// Query parameter name for cursor (e.g., "starting_after")
+        /// Parameter name for cursor (e.g., "starting_after")
         cursor_param: String,
-        /// JSONPath to extract cursor from response
-        cursor_path: String,
+        /// Where to read the cursor from the response
+        source: CursorSource,
+        /// Where to write the cursor into the next request
+        location: RequestLocation,
         /// Stop condition
         stop_condition: StopCondition,
     },
@@ -124,7 +144,8 @@ impl PaginationConfig {
     ) -> Self {
         Self::Cursor {
             cursor_param: cursor_param.into(),
-            cursor_path: cursor_path.into(),
+            source: CursorSource::Body(cursor_path.into()),
+            location: RequestLocation::Query,
             stop_condition,
         }
     }
@@ -166,6 +187,131 @@ impl PaginationConfig {
     }
 }
 
+/// Where a cursor is read from in a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorSource {
+    /// JSONPath into the response body (e.g., "meta.next_cursor")
+    Body(String),
+    /// Response header name (e.g., "X-Next-Cursor", "Sforce-Locator")
+    Header(String),
+}
+
+impl Default for CursorSource {
+    fn default() -> Self {
+        Self::Body(String::new())
+    }
+}
+
+impl CursorSource {
+    /// Extract the cursor value from a response
+    pub fn extract(&self, body: &Value, headers: &HeaderMap) -> Option<String> {
+        match self {
+            Self::Body(path) => extract_jsonpath(body, path),
+            Self::Header(name) => headers
+                .get(name.as_str())
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                // Salesforce sends the literal string "null" on the last page
+                .filter(|v| v != "null"),
+        }
+    }
+}
+
+/// Where a pagination value is written into a request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestLocation {
+    /// Query parameter
+    #[default]
+    Query,
+    /// Request header
+    Header,
+    /// Field of the JSON request body (dots address nested objects)
+    Body,
+}
+
+impl RequestLocation {
+    /// Parse a location name ("query", "header" or "body")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "query" => Some(Self::Query),
+            "header" => Some(Self::Header),
+            "body" => Some(Self::Body),
+            _ => None,
+        }
+    }
+}
+
+/// Expected ordering of keyset pagination keys across pages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// No ordering check (e.g., opaque IDs like Stripe's)
+    #[default]
+    Unordered,
+    /// Keys must increase (e.g., Shopify `since_id`)
+    Ascending,
+    /// Keys must decrease
+    Descending,
+}
+
+impl KeyOrder {
+    /// Parse an order name ("none", "asc" or "desc")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::Unordered),
+            "asc" => Some(Self::Ascending),
+            "desc" => Some(Self::Descending),
+            _ => None,
+        }
+    }
+
+    /// Check that `next` moves in this order from `previous`
+    ///
+    /// Keys that both parse as numbers compare numerically (integers exactly,
+    /// so 19-digit IDs are not rounded), others as strings (which orders
+    /// ISO 8601 timestamps correctly).
+    pub fn advances(self, previous: &str, next: &str) -> bool {
+        let ordering = if let (Ok(a), Ok(b)) = (previous.parse::<i128>(), next.parse::<i128>()) {
+            b.cmp(&a)
+        } else if let (Ok(a), Ok(b)) = (previous.parse::<u128>(), next.parse::<u128>()) {
+            b.cmp(&a)
+        } else if let (Ok(a), Ok(b)) = (previous.parse::<f64>(), next.parse::<f64>()) {
+            b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+        } else {
+            next.cmp(previous)
+        };
+        match self {
+            Self::Unordered => ordering != Ordering::Equal,
+            Self::Ascending => ordering == Ordering::Greater,
+            Self::Descending => ordering == Ordering::Less,
+        }
+    }
+}
+
+impl std::fmt::Display for KeyOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unordered => write!(f, "none"),
+            Self::Ascending => write!(f, "asc"),
+            Self::Descending => write!(f, "desc"),
+        }
+    }
+}
+
+/// Set a field in a JSON request body
+///
+/// Dots in `path` address nested objects, which are created as needed
+/// (e.g., "variables.after"). A non-object body is replaced by an object.
+pub fn set_body_field(body: &mut Value, path: &str, value: Value) {
+    let mut current = body;
+    for part in path.split('.') {
+        if !current.is_object() {
+            *current = Value::Object(serde_json::Map::new());
+        }
+        current = &mut current[part];
+    }
+    *current = value;
+}
+
 /// Stop conditions for pagination
 #[derive(Debug, Clone, Default)]
 pub enum StopCondition {
@@ -248,6 +394,10 @@ pub struct PaginationState {
     pub total_fetched: u64,
     /// Is pagination complete?
     pub done: bool,
+    /// Key of the last record on the current page (keyset pagination)
+    pub last_key: Option<String>,
+    /// Cursors and keys already requested
+    pub seen_cursors: HashSet<String>,
 }
 
 impl PaginationState {
@@ -288,6 +438,11 @@ impl PaginationState {
     pub fn add_fetched(&mut self, count: u64) {
         self.total_fetched += count;
     }
+
+    /// Remember a requested cursor, returning false if it was seen before
+    pub fn record_cursor(&mut self, cursor: &str) -> bool {
+        self.seen_cursors.insert(cursor.to_string())
+    }
 }
 
 /// Core trait for pagination strategies
@@ -295,6 +450,27 @@ pub trait Paginator: Send + Sync {
     /// Get initial query parameters for the first request
     fn initial_params(&self, state: &PaginationState) -> HashMap<String, String>;
 
+    /// Get headers to add to the request for the current state
+    fn request_headers(&self, _state: &PaginationState) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Get JSON body fields to set on the request for the current state
+    ///
+    /// Keys are passed to [`set_body_field`], so dots address nested objects.
+    fn request_body_fields(&self, _state: &PaginationState) -> HashMap<String, Value> {
+        HashMap::new()
+    }
+
+    /// Observe the decoded records of a page before `process_response`
+    fn observe_records(&self, _records: &[Value], _state: &mut PaginationState) {}
+
+    /// Whether `observe_records` and `process_response` read the page's
+    /// records and response (columnar pages skip building JSON otherwise)
+    fn reads_response(&self) -> bool {
+        true
+    }
+
     /// Process a response and determine if there's a next page
     fn process_response(
         &self,
@@ -359,19 +535,6 @@ pub fn check_stop_condition(
 }
 
 /// Extract a JSON value from a path (returns Value instead of String)
-fn extract_jsonpath_value(value: &Value, path: &str) -> Option<Value> {
-    let path = path.strip_prefix("$.").unwrap_or(path);
-    let parts: Vec<&str> = path.split('.').collect();
-
-    let mut current = value;
-    for part in parts {
-        match current {
-            Value::Object(map) => {
-                current = map.get(part)?;
-            }
-            _ => return None,
-        }
-    }
-
-    Some(current.clone())
+pub fn extract_jsonpath_value(value: &Value, path: &str) -> Option<Value> {
+    crate::jsonpath::select_first(value, path)
 }
@@ -1,24 +1,27 @@
 //! Partition routing module
 //!
-//! Supports: Parent stream, List, DateTime ranges, Async jobs
+//! Supports: Parent stream, List, DateTime ranges, Async jobs, Cartesian products
 //!
 //! # Overview
 //!
 //! Partitions allow splitting a stream into multiple parallel or sequential
 //! sub-queries. This is useful for:
 //! - Child resources that require a parent ID
-//! - Date range slicing for large datasets
+//! - Date range slicing for large datasets, adapting window size to result caps
 //! - Static list of values (e.g., regions, accounts)
 //! - Async job-based exports (e.g., Salesforce bulk API)
+//! - Combinations of the above (e.g., account × day)
 
 mod routers;
 mod types;
+mod windows;
 
 pub use routers::{
-    extract_json_path, AsyncJob, AsyncJobConfig, AsyncJobState, DatetimeRouter, ListRouter,
-    ParentRouter,
+    extract_json_path, AsyncJob, AsyncJobConfig, AsyncJobState, CartesianRouter, CompareOp,
+    DatetimeRouter, FilterValue, ListRouter, ParentFilter, ParentRouter,
 };
 pub use types::{Partition, PartitionConfig, PartitionRouter, PartitionValue};
+pub use windows::{AdaptiveWindows, DateWindow};
 
 #[cfg(test)]
 mod tests;
@@ -6,7 +6,8 @@ use super::types::{PartitionRouter, PartitionValue};
 use crate::error::{Error, Result};
 use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // List Router
@@ -14,38 +15,109 @@ use std::collections::HashMap;
 
 /// List-based partition router
 ///
-/// Creates partitions from a static list of values.
+/// Creates partitions from a list of values. Values are either scalars
+/// written to a single field, or objects mapped to several fields.
 #[derive(Debug, Clone)]
 pub struct ListRouter {
     /// List of partition values
-    values: Vec<String>,
+    values: Vec<Value>,
     /// Field name for partition
     partition_field: String,
+    /// Partition fields mapped from object values (field -> path in value)
+    fields: BTreeMap<String, String>,
 }
 
 impl ListRouter {
     /// Create a new list router
     pub fn new(values: Vec<String>, partition_field: impl Into<String>) -> Self {
+        Self::from_values(
+            values.into_iter().map(Value::String).collect(),
+            partition_field,
+        )
+    }
+
+    /// Create a list router from JSON values (e.g., from config or a lookup response)
+    pub fn from_values(values: Vec<Value>, partition_field: impl Into<String>) -> Self {
         Self {
             values,
             partition_field: partition_field.into(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Map each value to several partition fields (field -> path in value)
+    #[must_use]
+    pub fn with_fields(mut self, fields: BTreeMap<String, String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Parse values from a rendered template
+    ///
+    /// Accepts a JSON array (e.g., a rendered config list) or a comma-separated list.
+    pub fn parse_values(rendered: &str) -> Vec<Value> {
+        if let Ok(Value::Array(values)) = serde_json::from_str(rendered) {
+            return values;
+        }
+        rendered
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| Value::String(v.to_string()))
+            .collect()
+    }
+
+    /// Build a partition from a single value
+    fn partition(&self, value: &Value) -> Option<PartitionValue> {
+        if self.fields.is_empty() {
+            let id = partition_id(value)?;
+            return Some(PartitionValue::new(id).with_value(&self.partition_field, value.clone()));
+        }
+
+        let mut partition = PartitionValue::new(String::new());
+        let mut id_parts = Vec::with_capacity(self.fields.len());
+        for (field, path) in &self.fields {
+            let field_value = extract_json_path(value, path).filter(|v| !v.is_null())?;
+            id_parts.push(format!("{field}={}", partition_id(&field_value)?));
+            partition = partition.with_value(field, field_value);
         }
+        partition.id = id_parts.join(",");
+        Some(partition)
     }
 }
 
 impl PartitionRouter for ListRouter {
     fn partitions(&self) -> Result<Vec<PartitionValue>> {
-        Ok(self
-            .values
-            .iter()
-            .map(|v| {
-                PartitionValue::new(v.clone()).with_string(self.partition_field.clone(), v.clone())
-            })
-            .collect())
+        let mut partitions = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+
+        for value in &self.values {
+            if let Some(partition) = self.partition(value) {
+                // Deduplicate
+                if seen.insert(partition.id.clone()) {
+                    partitions.push(partition);
+                }
+            }
+        }
+
+        Ok(partitions)
     }
 
     fn partition_field(&self) -> &str {
-        &self.partition_field
+        match self.fields.keys().next() {
+            Some(field) if self.partition_field.is_empty() => field,
+            _ => &self.partition_field,
+        }
+    }
+}
+
+/// Partition ID for a scalar value
+fn partition_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
     }
 }
 
@@ -55,7 +127,9 @@ impl PartitionRouter for ListRouter {
 
 /// Parent stream-based partition router
 ///
-/// Creates partitions from records in a parent stream.
+/// Creates partitions from records in a parent stream. Besides the parent
+/// key, several parent attributes (nested paths) can be mapped to partition
+/// fields, and filters decide which parents fan out.
 #[derive(Debug, Clone)]
 pub struct ParentRouter {
     /// Records from parent stream
@@ -64,6 +138,12 @@ pub struct ParentRouter {
     parent_key: String,
     /// Field name for partition
     partition_field: String,
+    /// Additional partition fields mapped from parent records (field -> path)
+    fields: BTreeMap<String, String>,
+    /// Filters a parent must pass to create a partition
+    filters: Vec<ParentFilter>,
+    /// Child cursors by partition ID (for child cursor filters)
+    cursors: HashMap<String, String>,
 }
 
 impl ParentRouter {
@@ -77,16 +157,15 @@ impl ParentRouter {
             parent_records,
             parent_key: parent_key.into(),
             partition_field: partition_field.into(),
+            fields: BTreeMap::new(),
+            filters: Vec::new(),
+            cursors: HashMap::new(),
         }
     }
 
     /// Create an empty parent router (for deferred loading)
     pub fn empty(parent_key: impl Into<String>, partition_field: impl Into<String>) -> Self {
-        Self {
-            parent_records: Vec::new(),
-            parent_key: parent_key.into(),
-            partition_field: partition_field.into(),
-        }
+        Self::new(Vec::new(), parent_key, partition_field)
     }
 
     /// Set parent records
@@ -94,21 +173,53 @@ impl ParentRouter {
         self.parent_records = records;
     }
 
-    /// Extract value from a record using the parent key
-    fn extract_key(&self, record: &Value) -> Option<String> {
-        // Handle nested keys like "id" or "data.id"
-        let parts: Vec<&str> = self.parent_key.split('.').collect();
-        let mut current = record;
+    /// Map parent attributes to partition fields (field -> path in parent record)
+    ///
+    /// Without a parent key, the partition ID combines the mapped values
+    /// (e.g., `owner=acme,repo=api`).
+    #[must_use]
+    pub fn with_fields(mut self, fields: BTreeMap<String, String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Only create partitions for parents passing every filter
+    #[must_use]
+    pub fn with_filters(mut self, filters: Vec<ParentFilter>) -> Self {
+        self.filters = filters;
+        self
+    }
 
-        for part in parts {
-            current = current.get(part)?;
+    /// Set the child's cursors by partition ID (for child cursor filters)
+    #[must_use]
+    pub fn with_cursors(mut self, cursors: HashMap<String, String>) -> Self {
+        self.cursors = cursors;
+        self
+    }
+
+    /// Build a partition from a parent record
+    fn partition(&self, record: &Value) -> Option<PartitionValue> {
+        let mut partition = PartitionValue::new(String::new());
+        let mut id_parts = Vec::with_capacity(self.fields.len());
+
+        if !self.parent_key.is_empty() {
+            let key = extract_json_path(record, &self.parent_key)
+                .as_ref()
+                .and_then(partition_id)?;
+            partition = partition.with_string(&self.partition_field, &key);
+            id_parts.push(key);
         }
 
-        match current {
-            Value::String(s) => Some(s.clone()),
-            Value::Number(n) => Some(n.to_string()),
-            _ => None,
+        for (field, path) in &self.fields {
+            let value = extract_json_path(record, path).unwrap_or(Value::Null);
+            if self.parent_key.is_empty() {
+                id_parts.push(format!("{field}={}", partition_id(&value)?));
+            }
+            partition = partition.with_value(field, value);
         }
+
+        partition.id = id_parts.join(",");
+        Some(partition)
     }
 }
 
@@ -118,14 +229,16 @@ impl PartitionRouter for ParentRouter {
         let mut seen = std::collections::HashSet::new();
 
         for record in &self.parent_records {
-            if let Some(key_value) = self.extract_key(record) {
-                // Deduplicate
-                if seen.insert(key_value.clone()) {
-                    partitions.push(
-                        PartitionValue::new(&key_value)
-                            .with_string(self.partition_field.clone(), &key_value),
-                    );
-                }
+            let Some(partition) = self.partition(record) else {
+                continue;
+            };
+            let cursor = self.cursors.get(&partition.id).map(String::as_str);
+            if !self.filters.iter().all(|f| f.matches(record, cursor)) {
+                continue;
+            }
+            // Deduplicate
+            if seen.insert(partition.id.clone()) {
+                partitions.push(partition);
             }
         }
 
@@ -133,7 +246,135 @@ impl PartitionRouter for ParentRouter {
     }
 
     fn partition_field(&self) -> &str {
-        &self.partition_field
+        match self.fields.keys().next() {
+            Some(field) if self.partition_field.is_empty() => field,
+            _ => &self.partition_field,
+        }
+    }
+}
+
+/// Comparison operator for parent filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// Equal
+    Eq,
+    /// Not equal
+    Ne,
+    /// Greater than
+    Gt,
+    /// Greater than or equal
+    Gte,
+    /// Less than
+    Lt,
+    /// Less than or equal
+    Lte,
+}
+
+impl CompareOp {
+    /// Parse an operator name ("eq", "ne", "gt", "gte", "lt" or "lte")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "eq" => Some(Self::Eq),
+            "ne" => Some(Self::Ne),
+            "gt" => Some(Self::Gt),
+            "gte" => Some(Self::Gte),
+            "lt" => Some(Self::Lt),
+            "lte" => Some(Self::Lte),
+            _ => None,
+        }
+    }
+
+    /// Compare two JSON values
+    ///
+    /// Numbers (and numeric strings compared with numbers) compare
+    /// numerically, strings lexicographically (ISO timestamps sort
+    /// correctly). Ordering against other values never matches.
+    pub fn compare(self, left: &Value, right: &Value) -> bool {
+        let ordering = match (left, right) {
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            (Value::Number(_) | Value::String(_), Value::Number(_) | Value::String(_)) => {
+                as_number(left)
+                    .zip(as_number(right))
+                    .and_then(|(l, r)| l.partial_cmp(&r))
+            }
+            _ => None,
+        };
+
+        match self {
+            Self::Eq => ordering.map_or(left == right, Ordering::is_eq),
+            Self::Ne => ordering.map_or(left != right, Ordering::is_ne),
+            Self::Gt => ordering.is_some_and(Ordering::is_gt),
+            Self::Gte => ordering.is_some_and(Ordering::is_ge),
+            Self::Lt => ordering.is_some_and(Ordering::is_lt),
+            Self::Lte => ordering.is_some_and(Ordering::is_le),
+        }
+    }
+}
+
+/// Value a parent filter compares against
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    /// A fixed value
+    Value(Value),
+    /// The child's stored cursor for the parent's partition
+    ChildCursor,
+}
+
+/// Filter deciding which parent records fan out into partitions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentFilter {
+    /// Path to the compared field in the parent record
+    pub path: String,
+    /// Comparison operator
+    pub op: CompareOp,
+    /// Value compared against
+    pub value: FilterValue,
+}
+
+impl ParentFilter {
+    /// Create a filter comparing a parent field with a fixed value
+    pub fn new(path: impl Into<String>, op: CompareOp, value: Value) -> Self {
+        Self {
+            path: path.into(),
+            op,
+            value: FilterValue::Value(value),
+        }
+    }
+
+    /// Create a filter comparing a parent field with the child's cursor
+    ///
+    /// E.g., `updated_at gt` skips parents unchanged since the child last
+    /// synced them. Parents without a child cursor or without the field
+    /// always pass.
+    pub fn child_cursor(path: impl Into<String>, op: CompareOp) -> Self {
+        Self {
+            path: path.into(),
+            op,
+            value: FilterValue::ChildCursor,
+        }
+    }
+
+    /// Check a parent record (missing fields compare as null)
+    pub fn matches(&self, record: &Value, cursor: Option<&str>) -> bool {
+        let field = extract_json_path(record, &self.path);
+        match &self.value {
+            FilterValue::Value(value) => self.op.compare(&field.unwrap_or(Value::Null), value),
+            FilterValue::ChildCursor => match (field, cursor) {
+                (Some(field), Some(cursor)) if !field.is_null() => {
+                    self.op.compare(&field, &Value::String(cursor.to_string()))
+                }
+                _ => true,
+            },
+        }
+    }
+}
+
+/// Numeric value of a number or numeric string
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
     }
 }
 
@@ -190,7 +431,7 @@ impl DatetimeRouter {
         end_param: impl Into<String>,
     ) -> Result<Self> {
         let start_dt = parse_datetime(start)?;
-        let end_dt = if end == "now" || end == "{{ now }}" {
+        let end_dt = if matches!(end.trim(), "now" | "{{ now }}" | "{{ now() }}") {
             Utc::now()
         } else {
             parse_datetime(end)?
@@ -248,12 +489,70 @@ impl PartitionRouter for DatetimeRouter {
     }
 }
 
+// ============================================================================
+// Cartesian Router
+// ============================================================================
+
+/// Cross product of several partition routers
+///
+/// Each partition combines one partition from every component router
+/// (e.g., account × day). IDs join the component IDs with `|`, so they stay
+/// stable across runs as long as the component IDs do. Values are merged,
+/// with later components winning on conflicting keys.
+pub struct CartesianRouter {
+    /// Component routers, outermost first
+    routers: Vec<Box<dyn PartitionRouter>>,
+}
+
+impl CartesianRouter {
+    /// Create a new cartesian router
+    pub fn new(routers: Vec<Box<dyn PartitionRouter>>) -> Self {
+        Self { routers }
+    }
+}
+
+impl PartitionRouter for CartesianRouter {
+    fn partitions(&self) -> Result<Vec<PartitionValue>> {
+        if self.routers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut combined = vec![PartitionValue::new(String::new())];
+        for router in &self.routers {
+            let component = router.partitions()?;
+            combined = combined
+                .iter()
+                .flat_map(|outer| {
+                    component.iter().map(move |inner| {
+                        let mut values = outer.values.clone();
+                        values.extend(inner.values.clone());
+                        let id = if outer.id.is_empty() {
+                            inner.id.clone()
+                        } else {
+                            format!("{}|{}", outer.id, inner.id)
+                        };
+                        PartitionValue { id, values }
+                    })
+                })
+                .collect();
+        }
+
+        Ok(combined)
+    }
+
+    fn partition_field(&self) -> &str {
+        self.routers
+            .first()
+            .map_or("", |router| router.partition_field())
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Parse a datetime string into UTC DateTime
-fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
+pub(super) fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
     // Try RFC 3339 first
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         return Ok(dt.with_timezone(&Utc));
@@ -281,7 +580,7 @@ fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
 }
 
 /// Parse a duration string like "1d", "2h", "30m"
-fn parse_duration(s: &str) -> Result<Duration> {
+pub(super) fn parse_duration(s: &str) -> Result<Duration> {
     let s = s.trim();
 
     // Try to parse as number with suffix using strip_suffix
@@ -497,29 +796,7 @@ impl AsyncJob {
     }
 }
 
-/// Extract a value from JSON using a simple path (e.g., "data.id", "state")
+/// Extract a value from JSON using a JSONPath (e.g., "data.id", "records[0].state")
 pub fn extract_json_path(value: &Value, path: &str) -> Option<Value> {
-    let path = path.strip_prefix("$.").unwrap_or(path);
-    let parts: Vec<&str> = path.split('.').collect();
-
-    let mut current = value;
-    for part in parts {
-        // Handle array index like "records[0]"
-        if let Some(bracket_pos) = part.find('[') {
-            let key = &part[..bracket_pos];
-            let idx_str = &part[bracket_pos + 1..part.len() - 1];
-
-            current = current.get(key)?;
-
-            if let Ok(idx) = idx_str.parse::<usize>() {
-                current = current.get(idx)?;
-            } else {
-                return None;
-            }
-        } else {
-            current = current.get(part)?;
-        }
-    }
-
-    Some(current.clone())
+    crate::jsonpath::select_first(value, path)
 }
@@ -160,6 +160,57 @@ fn test_list_router_partition_field() {
     assert_eq!(router.partition_field(), "my_field");
 }
 
+#[test]
+fn test_list_router_json_values() {
+    let router = ListRouter::from_values(vec![json!(101), json!("acme"), json!(101)], "account");
+
+    let partitions = router.partitions().unwrap();
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions[0].id, "101");
+    assert_eq!(partitions[0].get("account"), Some(&json!(101)));
+    assert_eq!(partitions[1].get_string("account"), Some("acme"));
+}
+
+#[test]
+fn test_list_router_multi_field() {
+    let fields = [
+        ("brand_id".to_string(), "id".to_string()),
+        ("subdomain".to_string(), "host.subdomain".to_string()),
+    ]
+    .into_iter()
+    .collect();
+    let router = ListRouter::from_values(
+        vec![
+            json!({"id": 1, "host": {"subdomain": "acme"}}),
+            json!({"id": 2, "host": {"subdomain": "globex"}}),
+            json!({"id": 3}),
+        ],
+        "",
+    )
+    .with_fields(fields);
+
+    let partitions = router.partitions().unwrap();
+    // Values missing a mapped field are skipped
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions[0].id, "brand_id=1,subdomain=acme");
+    assert_eq!(partitions[0].get("brand_id"), Some(&json!(1)));
+    assert_eq!(partitions[1].get_string("subdomain"), Some("globex"));
+    assert_eq!(router.partition_field(), "brand_id");
+}
+
+#[test]
+fn test_list_router_parse_values() {
+    assert_eq!(
+        ListRouter::parse_values(r#"["shop-a.myshopify.com", "shop-b.myshopify.com"]"#),
+        vec![json!("shop-a.myshopify.com"), json!("shop-b.myshopify.com")]
+    );
+    assert_eq!(
+        ListRouter::parse_values("101, 102,,103"),
+        vec![json!("101"), json!("102"), json!("103")]
+    );
+    assert!(ListRouter::parse_values("").is_empty());
+}
+
 // ============================================================================
 // ParentRouter Tests
 // ============================================================================
@@ -245,6 +296,120 @@ fn test_parent_router_set_records() {
     assert_eq!(partitions.len(), 1);
 }
 
+#[test]
+fn test_parent_router_multiple_fields() {
+    let records = vec![
+        json!({"id": 1, "name": "api", "owner": {"login": "acme"}}),
+        json!({"id": 2, "name": "web", "owner": {"login": "acme"}}),
+    ];
+    let fields = [
+        ("owner".to_string(), "owner.login".to_string()),
+        ("repo".to_string(), "$.name".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let router = ParentRouter::new(records, "id", "repo_id").with_fields(fields);
+    let partitions = router.partitions().unwrap();
+
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions[0].id, "1");
+    assert_eq!(partitions[0].get_string("repo_id"), Some("1"));
+    assert_eq!(partitions[0].get_string("owner"), Some("acme"));
+    assert_eq!(partitions[1].get_string("repo"), Some("web"));
+}
+
+#[test]
+fn test_parent_router_fields_without_key() {
+    let records = vec![
+        json!({"name": "api", "owner": {"login": "acme"}}),
+        json!({"name": "api", "owner": {"login": "acme"}}), // Duplicate
+        json!({"name": "orphan"}),                          // No owner
+    ];
+    let fields = [
+        ("owner".to_string(), "owner.login".to_string()),
+        ("repo".to_string(), "name".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let router = ParentRouter::new(records, "", "").with_fields(fields);
+    let partitions = router.partitions().unwrap();
+
+    assert_eq!(partitions.len(), 1);
+    assert_eq!(partitions[0].id, "owner=acme,repo=api");
+    assert_eq!(router.partition_field(), "owner");
+}
+
+#[test]
+fn test_parent_router_value_filters() {
+    let records = vec![
+        json!({"id": "a", "archived": false, "stars": 10}),
+        json!({"id": "b", "archived": true, "stars": 50}),
+        json!({"id": "c", "stars": 3}),
+    ];
+
+    let router = ParentRouter::new(records, "id", "repo_id").with_filters(vec![
+        ParentFilter::new("archived", CompareOp::Ne, json!(true)),
+        ParentFilter::new("stars", CompareOp::Gte, json!("5")),
+    ]);
+    let ids: Vec<_> = router
+        .partitions()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+
+    assert_eq!(ids, vec!["a"]);
+}
+
+#[test]
+fn test_parent_router_child_cursor_filter() {
+    let records = vec![
+        json!({"id": "a", "updated_at": "2024-01-05"}),
+        json!({"id": "b", "updated_at": "2024-01-01"}),
+        json!({"id": "c", "updated_at": "2024-01-01"}),
+        json!({"id": "d"}),
+    ];
+    let cursors = [
+        ("a".to_string(), "2024-01-02".to_string()),
+        ("b".to_string(), "2024-01-02".to_string()),
+        ("d".to_string(), "2024-01-02".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let router = ParentRouter::new(records, "id", "repo_id")
+        .with_filters(vec![ParentFilter::child_cursor(
+            "updated_at",
+            CompareOp::Gt,
+        )])
+        .with_cursors(cursors);
+    let ids: Vec<_> = router
+        .partitions()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+
+    // b is unchanged since the child's cursor; c has no cursor; d has no updated_at
+    assert_eq!(ids, vec!["a", "c", "d"]);
+}
+
+#[test]
+fn test_compare_op() {
+    assert_eq!(CompareOp::parse("GTE"), Some(CompareOp::Gte));
+    assert_eq!(CompareOp::parse("between"), None);
+
+    assert!(CompareOp::Gt.compare(&json!(10), &json!(9)));
+    assert!(CompareOp::Gt.compare(&json!(10), &json!("9")));
+    assert!(CompareOp::Lt.compare(&json!("2024-01-01"), &json!("2024-02-01")));
+    assert!(CompareOp::Eq.compare(&json!(5), &json!("5")));
+    assert!(CompareOp::Eq.compare(&json!(true), &json!(true)));
+    assert!(CompareOp::Ne.compare(&json!(null), &json!(true)));
+    assert!(!CompareOp::Gt.compare(&json!(null), &json!(1)));
+}
+
 // ============================================================================
 // DatetimeRouter Tests
 // ============================================================================
@@ -341,6 +506,228 @@ fn test_datetime_router_partition_field() {
     assert_eq!(router.partition_field(), "my_start");
 }
 
+// ============================================================================
+// CartesianRouter Tests
+// ============================================================================
+
+fn account_day_router() -> CartesianRouter {
+    CartesianRouter::new(vec![
+        Box::new(ListRouter::new(
+            vec!["acct_1".to_string(), "acct_2".to_string()],
+            "account",
+        )),
+        Box::new(
+            DatetimeRouter::from_strings(
+                "2024-01-01",
+                "2024-01-03",
+                "1d",
+                "%Y-%m-%d",
+                "start",
+                "end",
+            )
+            .unwrap(),
+        ),
+    ])
+}
+
+#[test]
+fn test_cartesian_router_product() {
+    let router = account_day_router();
+
+    let partitions = router.partitions().unwrap();
+    assert_eq!(partitions.len(), 4);
+    assert_eq!(partitions[0].id, "acct_1|0_2024-01-01");
+    assert_eq!(partitions[1].id, "acct_1|1_2024-01-02");
+    assert_eq!(partitions[2].id, "acct_2|0_2024-01-01");
+
+    // Values from every component are merged
+    assert_eq!(partitions[3].get_string("account"), Some("acct_2"));
+    assert_eq!(partitions[3].get_string("start"), Some("2024-01-02"));
+    assert_eq!(partitions[3].get_string("end"), Some("2024-01-03"));
+    assert_eq!(router.partition_field(), "account");
+}
+
+#[test]
+fn test_cartesian_router_ids_are_stable() {
+    let first: Vec<_> = account_day_router()
+        .partitions()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+    let second: Vec<_> = account_day_router()
+        .partitions()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_cartesian_router_parent_and_nested() {
+    let parents = ParentRouter::new(
+        vec![json!({"id": "org_1"}), json!({"id": "org_2"})],
+        "id",
+        "org_id",
+    );
+    let nested = CartesianRouter::new(vec![
+        Box::new(ListRouter::new(vec!["open".to_string()], "status")),
+        Box::new(ListRouter::new(
+            vec!["bug".to_string(), "feature".to_string()],
+            "label",
+        )),
+    ]);
+    let router = CartesianRouter::new(vec![Box::new(parents), Box::new(nested)]);
+
+    let partitions = router.partitions().unwrap();
+    assert_eq!(partitions.len(), 4);
+    assert_eq!(partitions[0].id, "org_1|open|bug");
+    assert_eq!(partitions[3].id, "org_2|open|feature");
+    assert_eq!(partitions[3].get_string("org_id"), Some("org_2"));
+    assert_eq!(partitions[3].get_string("status"), Some("open"));
+}
+
+#[test]
+fn test_cartesian_router_empty_component() {
+    let router = CartesianRouter::new(vec![
+        Box::new(ListRouter::new(vec!["a".to_string()], "x")),
+        Box::new(ListRouter::new(vec![], "y")),
+    ]);
+    assert!(router.partitions().unwrap().is_empty());
+    assert!(CartesianRouter::new(vec![])
+        .partitions()
+        .unwrap()
+        .is_empty());
+}
+
+// ============================================================================
+// AdaptiveWindows Tests
+// ============================================================================
+
+fn day(d: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, d, 0, 0, 0).unwrap()
+}
+
+#[test]
+fn test_adaptive_windows_generate_by_step() {
+    let mut windows = AdaptiveWindows::new(day(1), day(10), Duration::days(4), 100);
+
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(1), day(5))));
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(5), day(9))));
+    assert_eq!(
+        windows.next_window(),
+        Some(DateWindow::new(day(9), day(10)))
+    );
+    assert_eq!(windows.next_window(), None);
+}
+
+#[test]
+fn test_adaptive_windows_split_queues_halves() {
+    let mut windows = AdaptiveWindows::new(day(1), day(9), Duration::days(4), 100);
+
+    let first = windows.next_window().unwrap();
+    assert!(windows.split(&first));
+    assert_eq!(windows.step(), Duration::days(2));
+
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(1), day(3))));
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(3), day(5))));
+    // Later windows use the reduced step
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(5), day(7))));
+}
+
+#[test]
+fn test_adaptive_windows_split_stops_at_min_step() {
+    let mut windows = AdaptiveWindows::new(day(1), day(9), Duration::days(2), 100)
+        .with_step_bounds("1d", "30d")
+        .unwrap();
+
+    let first = windows.next_window().unwrap();
+    assert!(windows.split(&first));
+    let half = windows.next_window().unwrap();
+    assert_eq!(half.duration(), Duration::days(1));
+    assert!(!windows.split(&half));
+}
+
+#[test]
+fn test_adaptive_windows_observe_widens_sparse_windows() {
+    let mut windows = AdaptiveWindows::new(day(1), day(31), Duration::days(2), 100)
+        .with_step_bounds("1d", "4d")
+        .unwrap();
+
+    windows.next_window();
+    windows.observe(10);
+    assert_eq!(windows.step(), Duration::days(4));
+    // Capped by max_step
+    windows.observe(10);
+    assert_eq!(windows.step(), Duration::days(4));
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(3), day(7))));
+
+    // Busy windows keep the step
+    windows.observe(50);
+    assert_eq!(windows.step(), Duration::days(4));
+}
+
+#[test]
+fn test_adaptive_windows_is_capped() {
+    let windows = AdaptiveWindows::new(day(1), day(2), Duration::days(1), 100);
+    assert!(windows.is_capped(100));
+    assert!(windows.is_capped(150));
+    assert!(!windows.is_capped(99));
+}
+
+#[test]
+fn test_adaptive_windows_replay_stored_windows() {
+    let stored = vec![
+        DateWindow::new(day(1), day(2)),
+        DateWindow::new(day(2), day(6)),
+    ];
+    let mut windows =
+        AdaptiveWindows::new(day(1), day(10), Duration::days(2), 100).with_windows(stored);
+
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(1), day(2))));
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(2), day(6))));
+    // Continues from the last stored window
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(6), day(8))));
+}
+
+#[test]
+fn test_adaptive_windows_ignore_stored_windows_for_other_start() {
+    let stored = vec![DateWindow::new(day(2), day(6))];
+    let mut windows =
+        AdaptiveWindows::new(day(1), day(10), Duration::days(2), 100).with_windows(stored);
+
+    assert_eq!(windows.next_window(), Some(DateWindow::new(day(1), day(3))));
+}
+
+#[test]
+fn test_adaptive_windows_partition_values() {
+    let windows = AdaptiveWindows::new(day(1), day(10), Duration::days(2), 100)
+        .with_format("%Y%m%d")
+        .with_params("since", "until");
+    let partition = windows.partition(&DateWindow::new(day(1), day(3)));
+
+    assert_eq!(partition.id, "2024-01-01T00:00:00Z_2024-01-03T00:00:00Z");
+    assert_eq!(partition.get_string("since"), Some("20240101"));
+    assert_eq!(partition.get_string("until"), Some("20240103"));
+    assert_eq!(partition.get_string("partition_start"), Some("20240101"));
+    assert_eq!(partition.get_string("partition_end"), Some("20240103"));
+}
+
+#[test]
+fn test_adaptive_windows_from_strings() {
+    let windows = AdaptiveWindows::from_strings("2024-01-01", "2024-01-10", "1w", 100)
+        .unwrap()
+        .with_total_path("meta.total");
+    assert_eq!(windows.step(), Duration::weeks(1));
+    assert_eq!(windows.total_path(), Some("meta.total"));
+
+    assert!(AdaptiveWindows::from_strings("bad", "2024-01-10", "1d", 100).is_err());
+    assert!(AdaptiveWindows::new(day(1), day(2), Duration::days(1), 100)
+        .with_step_bounds("x", "30d")
+        .is_err());
+}
+
 // ============================================================================
 // Duration Parsing Tests
 // ============================================================================
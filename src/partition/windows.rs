@@ -0,0 +1,241 @@
+//! Adaptive date windows
+//!
+//! Slices a date range into windows whose size adapts to the data: windows
+//! that hit an API's result cap are bisected, and windows that return few
+//! records let the following windows grow.
+
+use super::routers::{parse_datetime, parse_duration};
+use super::types::PartitionValue;
+use crate::error::Result;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+
+/// A half-open date window `[start, end)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateWindow {
+    /// Window start
+    pub start: DateTime<Utc>,
+    /// Window end (exclusive)
+    pub end: DateTime<Utc>,
+}
+
+impl DateWindow {
+    /// Create a new window
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { start, end }
+    }
+
+    /// Window length
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Stable identifier (`{start}_{end}` in RFC 3339)
+    pub fn id(&self) -> String {
+        format!(
+            "{}_{}",
+            self.start.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.end.to_rfc3339_opts(SecondsFormat::Secs, true)
+        )
+    }
+}
+
+/// Date-range slicer that splits capped windows and widens sparse ones
+///
+/// Windows start at `step` and stay between `min_step` and `max_step`. A
+/// window whose record count (or reported total) reaches `max_records` is
+/// split in half and re-queued; a window returning fewer than a quarter of
+/// `max_records` doubles the size of the windows that follow.
+#[derive(Debug, Clone)]
+pub struct AdaptiveWindows {
+    /// Range start
+    start: DateTime<Utc>,
+    /// Range end
+    end: DateTime<Utc>,
+    /// Size of the next generated window
+    step: Duration,
+    /// Smallest window size
+    min_step: Duration,
+    /// Largest window size
+    max_step: Duration,
+    /// Result cap of the API
+    max_records: usize,
+    /// JSONPath to the total result count in responses
+    total_path: Option<String>,
+    /// Format string for window bounds
+    format: String,
+    /// Parameter name for start
+    start_param: String,
+    /// Parameter name for end
+    end_param: String,
+    /// Windows queued before the next generated one (splits, resumed slices)
+    pending: VecDeque<DateWindow>,
+    /// Start of the next generated window
+    next_start: DateTime<Utc>,
+}
+
+impl AdaptiveWindows {
+    /// Create a new adaptive window slicer
+    pub fn new(
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+        max_records: usize,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            step,
+            min_step: Duration::days(1).min(step),
+            max_step: Duration::days(30).max(step),
+            max_records,
+            total_path: None,
+            format: "%Y-%m-%d".to_string(),
+            start_param: "start_date".to_string(),
+            end_param: "end_date".to_string(),
+            pending: VecDeque::new(),
+            next_start: start,
+        }
+    }
+
+    /// Create from string values
+    pub fn from_strings(start: &str, end: &str, step: &str, max_records: usize) -> Result<Self> {
+        let start_dt = parse_datetime(start)?;
+        let end_dt = if matches!(end.trim(), "now" | "{{ now }}" | "{{ now() }}") {
+            Utc::now()
+        } else {
+            parse_datetime(end)?
+        };
+        Ok(Self::new(
+            start_dt,
+            end_dt,
+            parse_duration(step)?,
+            max_records,
+        ))
+    }
+
+    /// Set the smallest and largest window sizes (e.g., "1h", "90d")
+    pub fn with_step_bounds(mut self, min_step: &str, max_step: &str) -> Result<Self> {
+        self.min_step = parse_duration(min_step)?;
+        self.max_step = parse_duration(max_step)?.max(self.min_step);
+        self.step = self.step.clamp(self.min_step, self.max_step);
+        Ok(self)
+    }
+
+    /// Set the path to the total result count in responses
+    #[must_use]
+    pub fn with_total_path(mut self, path: impl Into<String>) -> Self {
+        self.total_path = Some(path.into());
+        self
+    }
+
+    /// Set the format string for window bounds
+    #[must_use]
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = format.into();
+        self
+    }
+
+    /// Set the parameter names for window bounds
+    #[must_use]
+    pub fn with_params(
+        mut self,
+        start_param: impl Into<String>,
+        end_param: impl Into<String>,
+    ) -> Self {
+        self.start_param = start_param.into();
+        self.end_param = end_param.into();
+        self
+    }
+
+    /// Replay windows chosen by an earlier run
+    ///
+    /// Ignored when the stored windows do not begin at the configured start
+    /// (the range changed). Windows past the stored ones are generated as
+    /// usual, so a moving end (e.g., `now`) extends the range.
+    #[must_use]
+    pub fn with_windows(mut self, windows: Vec<DateWindow>) -> Self {
+        if windows.first().is_none_or(|w| w.start != self.start) {
+            return self;
+        }
+        let mut next_start = self.start;
+        for window in windows {
+            if window.start != next_start || window.end <= window.start {
+                break;
+            }
+            next_start = window.end;
+            self.pending.push_back(window);
+        }
+        self.next_start = next_start;
+        self
+    }
+
+    /// Path to the total result count, if configured
+    pub fn total_path(&self) -> Option<&str> {
+        self.total_path.as_deref()
+    }
+
+    /// Size of the next generated window
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Next window to sync
+    pub fn next_window(&mut self) -> Option<DateWindow> {
+        if let Some(window) = self.pending.pop_front() {
+            return Some(window);
+        }
+        if self.next_start >= self.end {
+            return None;
+        }
+        let end = (self.next_start + self.step).min(self.end);
+        let window = DateWindow::new(self.next_start, end);
+        self.next_start = end;
+        Some(window)
+    }
+
+    /// Whether a record count or reported total reaches the result cap
+    pub fn is_capped(&self, count: usize) -> bool {
+        self.max_records > 0 && count >= self.max_records
+    }
+
+    /// Split a capped window in half and queue both halves
+    ///
+    /// Returns `false` when the window is already at the minimum size.
+    pub fn split(&mut self, window: &DateWindow) -> bool {
+        let half = window.duration() / 2;
+        if half < self.min_step {
+            return false;
+        }
+        let mid = window.start + half;
+        self.pending.push_front(DateWindow::new(mid, window.end));
+        self.pending.push_front(DateWindow::new(window.start, mid));
+        self.step = half;
+        true
+    }
+
+    /// Record the size of a synced window, widening later windows if sparse
+    pub fn observe(&mut self, count: usize) {
+        if self.max_records > 0 && count < self.max_records / 4 {
+            self.step = (self.step * 2).min(self.max_step);
+        }
+    }
+
+    /// Partition values for a window
+    pub fn partition(&self, window: &DateWindow) -> PartitionValue {
+        let start = window.start.format(&self.format).to_string();
+        let end = window.end.format(&self.format).to_string();
+
+        let mut values = HashMap::new();
+        values.insert(self.start_param.clone(), Value::String(start.clone()));
+        values.insert(self.end_param.clone(), Value::String(end.clone()));
+        values.insert("partition_start".to_string(), Value::String(start));
+        values.insert("partition_end".to_string(), Value::String(end));
+
+        PartitionValue {
+            id: window.id(),
+            values,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Secret references module
+//!
+//! Supports: Environment variables, Files, Vault (KV v2), AWS Secrets Manager
+//!
+//! # Overview
+//!
+//! Configs and connector YAML can reference secrets instead of embedding them:
+//!
+//! - `{{ secret.env.STRIPE_KEY }}` - read from an environment variable
+//! - `{{ secret.file./run/secrets/stripe }}` - read from a file (trimmed)
+//! - `{{ secret.vault.stripe#api_key }}` - read a field from a Vault KV v2 secret
+//! - `{{ secret.aws.prod/stripe#api_key }}` - read from a Secrets Manager-style endpoint
+//!
+//! The `SecretResolver` resolves references in config values before a sync starts.
+//! `env` and `file` references are also resolved directly by `template::render`.
+//!
+//! Resolved values are registered with the process-wide redactor (see [`redact`]),
+//! which scrubs them from logs, errors and tracing output.
+
+mod providers;
+pub mod redact;
+mod resolver;
+
+pub use providers::{
+    AwsSecretsManagerProvider, EnvSecretProvider, FileSecretProvider, VaultSecretProvider,
+};
+pub use redact::Redactor;
+pub use resolver::{
+    find_references, is_reference, require_references, resolve_builtin, SecretProvider,
+    SecretReference, SecretResolver,
+};
+
+#[cfg(test)]
+mod tests;
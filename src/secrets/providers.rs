@@ -0,0 +1,295 @@
+//! Secret provider implementations
+//!
+//! Each provider resolves keys from one secret source.
+
+use super::resolver::SecretProvider;
+use crate::auth::extract_jsonpath;
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::Value;
+
+// ============================================================================
+// Environment Provider
+// ============================================================================
+
+/// Reads secrets from environment variables (`secret.env.NAME`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSecretProvider;
+
+impl EnvSecretProvider {
+    /// Read an environment variable
+    pub fn read(name: &str) -> Result<String> {
+        std::env::var(name)
+            .map_err(|_| Error::secret(format!("Environment variable '{name}' is not set")))
+    }
+}
+
+#[async_trait]
+impl SecretProvider for EnvSecretProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn fetch(&self, key: &str) -> Result<String> {
+        Self::read(key)
+    }
+}
+
+// ============================================================================
+// File Provider
+// ============================================================================
+
+/// Reads secrets from files (`secret.file./run/secrets/name`)
+///
+/// Trailing whitespace and newlines are trimmed, matching how Docker and
+/// Kubernetes secret mounts are usually written.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSecretProvider;
+
+impl FileSecretProvider {
+    /// Read a secret file
+    pub fn read(path: &str) -> Result<String> {
+        std::fs::read_to_string(path)
+            .map(|s| s.trim_end().to_string())
+            .map_err(|e| Error::secret(format!("Failed to read secret file '{path}': {e}")))
+    }
+}
+
+#[async_trait]
+impl SecretProvider for FileSecretProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn fetch(&self, key: &str) -> Result<String> {
+        Self::read(key)
+    }
+}
+
+// ============================================================================
+// Vault Provider
+// ============================================================================
+
+/// Reads secrets from a HashiCorp Vault KV v2 engine over HTTP
+///
+/// Keys use the form `path#field` (e.g., `stripe#api_key`). Without a field,
+/// the secret must contain exactly one value.
+#[derive(Debug, Clone)]
+pub struct VaultSecretProvider {
+    /// Vault address (e.g., "https://vault.internal:8200")
+    address: String,
+    /// Vault token
+    token: String,
+    /// KV v2 mount point
+    mount: String,
+    /// Optional Vault Enterprise namespace
+    namespace: Option<String>,
+    /// HTTP client
+    http_client: Client,
+}
+
+impl VaultSecretProvider {
+    /// Create a new Vault provider
+    pub fn new(address: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            token: token.into(),
+            mount: "secret".to_string(),
+            namespace: None,
+            http_client: Client::new(),
+        }
+    }
+
+    /// Create from `VAULT_ADDR`, `VAULT_TOKEN`, `VAULT_NAMESPACE` and
+    /// `VAULT_KV_MOUNT`; returns `None` unless address and token are set
+    pub fn from_env() -> Option<Self> {
+        let address = std::env::var("VAULT_ADDR").ok()?;
+        let token = std::env::var("VAULT_TOKEN").ok()?;
+        let mut provider = Self::new(address, token);
+        if let Ok(namespace) = std::env::var("VAULT_NAMESPACE") {
+            provider.namespace = Some(namespace);
+        }
+        if let Ok(mount) = std::env::var("VAULT_KV_MOUNT") {
+            provider.mount = mount;
+        }
+        Some(provider)
+    }
+
+    /// Set the KV v2 mount point
+    #[must_use]
+    pub fn with_mount(mut self, mount: impl Into<String>) -> Self {
+        self.mount = mount.into();
+        self
+    }
+
+    /// Set the namespace
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultSecretProvider {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn fetch(&self, key: &str) -> Result<String> {
+        let (path, field) = split_field(key);
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            self.address.trim_end_matches('/'),
+            self.mount.trim_matches('/'),
+            path.trim_start_matches('/')
+        );
+
+        let mut req = self
+            .http_client
+            .get(&url)
+            .header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            req = req.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = req.send().await.map_err(Error::Http)?;
+        if !response.status().is_success() {
+            return Err(Error::secret(format!(
+                "Vault returned status {} for '{path}'",
+                response.status().as_u16()
+            )));
+        }
+
+        let body: Value = response.json().await.map_err(Error::Http)?;
+        let data = body
+            .pointer("/data/data")
+            .ok_or_else(|| Error::secret(format!("Vault response for '{path}' has no data")))?;
+        select_field(data, field, path)
+    }
+}
+
+// ============================================================================
+// AWS Secrets Manager Provider
+// ============================================================================
+
+/// Reads secrets from an AWS Secrets Manager-style `GetSecretValue` endpoint
+///
+/// Keys use the form `secret-id#field`; the field is looked up in the JSON
+/// `SecretString`. Requests are not SigV4-signed, so the endpoint is expected
+/// to be a signing proxy, sidecar or local emulator that accepts the
+/// `secretsmanager.GetSecretValue` JSON protocol.
+#[derive(Debug, Clone)]
+pub struct AwsSecretsManagerProvider {
+    /// Endpoint URL
+    endpoint: String,
+    /// Optional bearer token for the endpoint
+    token: Option<String>,
+    /// HTTP client
+    http_client: Client,
+}
+
+impl AwsSecretsManagerProvider {
+    /// Create a new provider for an endpoint
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            token: None,
+            http_client: Client::new(),
+        }
+    }
+
+    /// Create from `AWS_ENDPOINT_URL_SECRETS_MANAGER` (and optional
+    /// `AWS_SECRETS_MANAGER_TOKEN`); returns `None` if no endpoint is set
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("AWS_ENDPOINT_URL_SECRETS_MANAGER").ok()?;
+        let mut provider = Self::new(endpoint);
+        provider.token = std::env::var("AWS_SECRETS_MANAGER_TOKEN").ok();
+        Some(provider)
+    }
+
+    /// Set a bearer token for the endpoint
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+}
+
+#[async_trait]
+impl SecretProvider for AwsSecretsManagerProvider {
+    fn name(&self) -> &'static str {
+        "aws"
+    }
+
+    async fn fetch(&self, key: &str) -> Result<String> {
+        let (secret_id, field) = split_field(key);
+
+        let mut req = self
+            .http_client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/x-amz-json-1.1")
+            .header("X-Amz-Target", "secretsmanager.GetSecretValue")
+            .body(serde_json::json!({ "SecretId": secret_id }).to_string());
+        if let Some(token) = &self.token {
+            req = req.bearer_auth(token);
+        }
+
+        let response = req.send().await.map_err(Error::Http)?;
+        if !response.status().is_success() {
+            return Err(Error::secret(format!(
+                "Secrets Manager returned status {} for '{secret_id}'",
+                response.status().as_u16()
+            )));
+        }
+
+        let body: Value = response.json().await.map_err(Error::Http)?;
+        let secret_string = body
+            .get("SecretString")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::secret(format!("Secret '{secret_id}' has no SecretString")))?;
+
+        if field.is_none() {
+            return Ok(secret_string.to_string());
+        }
+
+        let data: Value = serde_json::from_str(secret_string).map_err(|e| {
+            Error::secret(format!("Secret '{secret_id}' is not a JSON object: {e}"))
+        })?;
+        select_field(&data, field, secret_id)
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Split a key like "path#field" into path and optional field
+fn split_field(key: &str) -> (&str, Option<&str>) {
+    match key.split_once('#') {
+        Some((path, field)) => (path, Some(field)),
+        None => (key, None),
+    }
+}
+
+/// Select a field from a secret's JSON data
+fn select_field(data: &Value, field: Option<&str>, path: &str) -> Result<String> {
+    match field {
+        Some(field) => extract_jsonpath(data, field)
+            .ok_or_else(|| Error::secret(format!("Field '{field}' not found in secret '{path}'"))),
+        None => match data {
+            Value::Object(map) if map.len() == 1 => match map.values().next() {
+                Some(Value::String(s)) => Ok(s.clone()),
+                Some(v @ (Value::Number(_) | Value::Bool(_))) => Ok(v.to_string()),
+                _ => Err(Error::secret(format!(
+                    "Secret '{path}' value is not a scalar"
+                ))),
+            },
+            Value::String(s) => Ok(s.clone()),
+            _ => Err(Error::secret(format!(
+                "Secret '{path}' has multiple fields; use '{path}#<field>'"
+            ))),
+        },
+    }
+}
@@ -0,0 +1,457 @@
+//! Secret redaction
+//!
+//! Scrubs known secret values, sensitive query parameters and sensitive
+//! headers from text before it leaves the process (log messages, error
+//! strings, tracing output).
+//!
+//! A process-wide redactor is populated from resolved secret references,
+//! config fields marked `secret` and auth definitions; the free functions in
+//! this module operate on it. Inside [`scope`] (e.g., one server request) they
+//! operate on a redactor of that task instead, which starts from the
+//! process-wide secrets and is dropped when the task completes.
+
+use crate::auth::{AuthConfig, Location};
+use crate::loader::AuthDefinition;
+use crate::template::{has_templates, render_optional, TemplateContext};
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::io::Write;
+use std::sync::{LazyLock, RwLock};
+
+/// Replacement text for redacted values
+pub const REDACTED: &str = "***";
+
+/// Values shorter than this are not redacted (too likely to match ordinary text)
+const MIN_SECRET_LEN: usize = 4;
+
+/// Header names whose values are always redacted
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+    "x-access-token",
+    "x-csrf-token",
+    "x-xsrf-token",
+    "x-vault-token",
+];
+
+/// Query parameter / JSON key names whose values are always redacted
+const SENSITIVE_PARAMS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "api-key",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "secret",
+    "password",
+    "passwd",
+    "signature",
+];
+
+/// Query string parameters: `?name=value` / `&name=value`
+static QUERY_PARAM_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"([?&;])([A-Za-z0-9_.\-\[\]]+)=([^&#\s"'<>]*)"#).unwrap());
+
+/// Header-style pairs: `Authorization: Bearer abc`, `"x-api-key": "abc"`
+static HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    let names = SENSITIVE_HEADERS
+        .iter()
+        .map(|h| regex::escape(h))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!(
+        r#"(?i)(["']?\b(?:{names})["']?\s*[:=]\s*["']?)(?:(?:bearer|basic|token)\s+)?[^\s"',;&]+"#
+    ))
+    .unwrap()
+});
+
+/// JSON string fields: `"access_token": "abc"`
+static JSON_FIELD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([A-Za-z0-9_\-]+)"(\s*:\s*)"((?:[^"\\]|\\.)*)""#).unwrap());
+
+/// Process-wide redactor
+static GLOBAL: LazyLock<RwLock<Redactor>> = LazyLock::new(|| RwLock::new(Redactor::new()));
+
+tokio::task_local! {
+    /// Redactor of the current [`scope`]
+    static SCOPED: RwLock<Redactor>;
+}
+
+/// Redacts secret values from text and JSON
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Known secret values (longest first)
+    values: Vec<String>,
+    /// Additional sensitive parameter names (lowercase)
+    params: Vec<String>,
+}
+
+impl Redactor {
+    /// Create an empty redactor (sensitive headers and parameters are still scrubbed)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a known secret value
+    ///
+    /// The URL-encoded form is added too, so values in query strings match.
+    pub fn add_secret(&mut self, value: &str) {
+        let value = value.trim();
+        if value.len() < MIN_SECRET_LEN {
+            return;
+        }
+        let encoded: String = url::form_urlencoded::byte_serialize(value.as_bytes()).collect();
+        for v in [value.to_string(), encoded] {
+            if !self.values.contains(&v) {
+                self.values.push(v);
+            }
+        }
+        // Longest first so overlapping secrets are fully replaced
+        self.values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+    }
+
+    /// Add a sensitive parameter name (e.g., an API key query parameter)
+    pub fn add_param(&mut self, name: &str) {
+        let name = name.to_lowercase();
+        if !self.params.contains(&name) {
+            self.params.push(name);
+        }
+    }
+
+    /// Add the values of secret fields from a config object
+    ///
+    /// Field names may use dot notation for nested values.
+    pub fn add_config<S: AsRef<str>>(&mut self, config: &Value, secret_fields: &[S]) {
+        for field in secret_fields {
+            let value = field
+                .as_ref()
+                .split('.')
+                .try_fold(config, |current, part| current.get(part));
+            match value {
+                Some(Value::String(s)) => self.add_secret(s),
+                Some(Value::Number(n)) => self.add_secret(&n.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Add the secrets and query parameter names of an auth config
+    pub fn add_auth(&mut self, auth: &AuthConfig) {
+        match auth {
+            AuthConfig::None => {}
+            AuthConfig::ApiKey {
+                location,
+                query_param,
+                value,
+                ..
+            } => {
+                self.add_secret(value);
+                if matches!(location, Location::Query) {
+                    self.add_param(query_param.as_deref().unwrap_or("api_key"));
+                }
+            }
+            AuthConfig::Basic { password, .. } => self.add_secret(password),
+            AuthConfig::Bearer { token } => self.add_secret(token),
+            AuthConfig::Oauth2ClientCredentials { client_secret, .. } => {
+                self.add_secret(client_secret);
+            }
+            AuthConfig::Oauth2Refresh {
+                client_secret,
+                refresh_token,
+                ..
+            } => {
+                self.add_secret(client_secret);
+                self.add_secret(refresh_token);
+            }
+            AuthConfig::Session { login_body, .. }
+            | AuthConfig::CookieSession { login_body, .. } => {
+                for (key, value) in login_body {
+                    if is_sensitive_param(key) || key.to_lowercase().contains("pass") {
+                        self.add_secret(value);
+                    }
+                }
+            }
+            AuthConfig::Jwt { private_key, .. } => self.add_secret(private_key),
+            AuthConfig::CustomHeaders { headers } => {
+                for (key, value) in headers {
+                    if is_sensitive_header(key) {
+                        self.add_secret(value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add the rendered secrets and query parameter names of a connector auth definition
+    pub fn add_auth_definition(&mut self, auth: &AuthDefinition, context: &TemplateContext) {
+        let mut add = |template: &str| {
+            let rendered = render_optional(template, context);
+            if !has_templates(&rendered) {
+                self.add_secret(&rendered);
+            }
+        };
+        match auth {
+            AuthDefinition::ApiKey { value, .. } => add(value),
+            AuthDefinition::Bearer { token } => add(token),
+            AuthDefinition::Basic { password, .. } => add(password),
+            AuthDefinition::OAuth2ClientCredentials { client_secret, .. } => add(client_secret),
+            AuthDefinition::OAuth2RefreshToken {
+                client_secret,
+                refresh_token,
+                ..
+            } => {
+                add(client_secret);
+                add(refresh_token);
+            }
+            AuthDefinition::SessionToken { .. }
+            | AuthDefinition::CookieSession { .. }
+            | AuthDefinition::None => {}
+        }
+        if let AuthDefinition::ApiKey { key, location, .. } = auth {
+            if location == "query" {
+                self.add_param(key);
+            }
+        }
+    }
+
+    /// Check if nothing but the built-in patterns would be redacted
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.params.is_empty()
+    }
+
+    /// Redact secrets from a string
+    pub fn redact(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for value in &self.values {
+            if result.contains(value.as_str()) {
+                result = result.replace(value.as_str(), REDACTED);
+            }
+        }
+
+        let result = QUERY_PARAM_REGEX.replace_all(&result, |cap: &Captures<'_>| {
+            if self.is_sensitive(&cap[2]) && !cap[3].is_empty() && &cap[3] != REDACTED {
+                format!("{}{}={REDACTED}", &cap[1], &cap[2])
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        let result = HEADER_REGEX.replace_all(&result, |cap: &Captures<'_>| {
+            format!("{}{REDACTED}", &cap[1])
+        });
+
+        let result = JSON_FIELD_REGEX.replace_all(&result, |cap: &Captures<'_>| {
+            if self.is_sensitive(&cap[1]) && !cap[3].is_empty() {
+                format!("\"{}\"{}\"{REDACTED}\"", &cap[1], &cap[2])
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        result.into_owned()
+    }
+
+    /// Redact secrets from all string values (recursively)
+    pub fn redact_value(&self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.redact(s)),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), self.redact_value(v)))
+                    .collect(),
+            ),
+            Value::Array(arr) => Value::Array(arr.iter().map(|v| self.redact_value(v)).collect()),
+            _ => value.clone(),
+        }
+    }
+
+    /// Redact header values (sensitive header names and known secret values)
+    pub fn redact_headers<S: BuildHasher>(
+        &self,
+        headers: &HashMap<String, String, S>,
+    ) -> HashMap<String, String> {
+        headers
+            .iter()
+            .map(|(k, v)| {
+                let v = if is_sensitive_header(k) {
+                    REDACTED.to_string()
+                } else {
+                    self.redact(v)
+                };
+                (k.clone(), v)
+            })
+            .collect()
+    }
+
+    /// Check if a parameter/key name is sensitive
+    fn is_sensitive(&self, name: &str) -> bool {
+        is_sensitive_param(name) || self.params.contains(&name.to_lowercase())
+    }
+}
+
+/// Check if a header name carries credentials
+pub fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS.contains(&name.to_lowercase().as_str())
+}
+
+/// Check if a query parameter or JSON key name carries credentials
+pub fn is_sensitive_param(name: &str) -> bool {
+    SENSITIVE_PARAMS.contains(&name.to_lowercase().as_str())
+}
+
+// ============================================================================
+// Process-wide Redactor
+// ============================================================================
+
+/// Run a future with its own redactor
+///
+/// Secrets registered inside the future are redacted only inside it, so
+/// concurrent requests do not share (or accumulate) each other's secrets.
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    let redactor = GLOBAL.read().map(|r| r.clone()).unwrap_or_default();
+    SCOPED.scope(RwLock::new(redactor), future).await
+}
+
+/// Apply `f` to the redactor of the current scope, or the process-wide one
+fn with_redactor<R>(f: impl Fn(&RwLock<Redactor>) -> R) -> R {
+    SCOPED.try_with(&f).unwrap_or_else(|_| f(&GLOBAL))
+}
+
+/// Register a secret value with the current redactor
+pub fn register_secret(value: &str) {
+    with_redactor(|lock| {
+        if let Ok(mut redactor) = lock.write() {
+            redactor.add_secret(value);
+        }
+    });
+}
+
+/// Register a sensitive parameter name with the current redactor
+pub fn register_param(name: &str) {
+    with_redactor(|lock| {
+        if let Ok(mut redactor) = lock.write() {
+            redactor.add_param(name);
+        }
+    });
+}
+
+/// Register config secret fields with the current redactor
+pub fn register_config<S: AsRef<str>>(config: &Value, secret_fields: &[S]) {
+    with_redactor(|lock| {
+        if let Ok(mut redactor) = lock.write() {
+            redactor.add_config(config, secret_fields);
+        }
+    });
+}
+
+/// Register an auth config with the current redactor
+pub fn register_auth(auth: &AuthConfig) {
+    with_redactor(|lock| {
+        if let Ok(mut redactor) = lock.write() {
+            redactor.add_auth(auth);
+        }
+    });
+}
+
+/// Register a connector auth definition with the current redactor
+pub fn register_auth_definition(auth: &AuthDefinition, context: &TemplateContext) {
+    with_redactor(|lock| {
+        if let Ok(mut redactor) = lock.write() {
+            redactor.add_auth_definition(auth, context);
+        }
+    });
+}
+
+/// Redact a string with the current redactor
+pub fn redact_str(text: &str) -> String {
+    with_redactor(|lock| match lock.read() {
+        Ok(redactor) => redactor.redact(text),
+        Err(_) => text.to_string(),
+    })
+}
+
+/// Redact a JSON value with the current redactor
+pub fn redact_value(value: &Value) -> Value {
+    with_redactor(|lock| match lock.read() {
+        Ok(redactor) => redactor.redact_value(value),
+        Err(_) => value.clone(),
+    })
+}
+
+/// Redact header values with the current redactor
+pub fn redact_headers<S: BuildHasher>(
+    headers: &HashMap<String, String, S>,
+) -> HashMap<String, String> {
+    with_redactor(|lock| match lock.read() {
+        Ok(redactor) => redactor.redact_headers(headers),
+        Err(_) => headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Tracing Writer
+// ============================================================================
+
+/// `MakeWriter` for `tracing_subscriber::fmt` that redacts every event
+///
+/// ```no_run
+/// use solidafy_cdk::secrets::redact::RedactingMakeWriter;
+///
+/// tracing_subscriber::fmt()
+///     .with_writer(RedactingMakeWriter::new(std::io::stderr))
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    /// Wrap a writer factory
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M> tracing_subscriber::fmt::MakeWriter<'a> for RedactingMakeWriter<M>
+where
+    M: tracing_subscriber::fmt::MakeWriter<'a>,
+{
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+        }
+    }
+}
+
+/// Writer that redacts each write before forwarding it
+#[derive(Debug)]
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.inner.write_all(redact_str(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
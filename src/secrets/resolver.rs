@@ -0,0 +1,295 @@
+//! Secret resolver implementation
+//!
+//! Parses `{{ secret.<provider>.<key> }}` references and resolves them
+//! through registered providers, caching each value for the lifetime of the
+//! resolver or for a configured TTL.
+
+use super::providers::{
+    AwsSecretsManagerProvider, EnvSecretProvider, FileSecretProvider, VaultSecretProvider,
+};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Regex for matching secret references: {{ secret.provider.key }}
+static SECRET_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{\s*secret\.([a-zA-Z_][a-zA-Z0-9_]*)\.([^\s{}]+)\s*\}\}").unwrap()
+});
+
+/// A parsed secret reference
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretReference {
+    /// Provider name (e.g., "env", "file", "vault", "aws")
+    pub provider: String,
+    /// Provider-specific key (variable name, file path, secret path)
+    pub key: String,
+}
+
+impl SecretReference {
+    /// Create a new secret reference
+    pub fn new(provider: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            key: key.into(),
+        }
+    }
+
+    /// Parse a template variable path like `secret.env.STRIPE_KEY`
+    pub fn parse(path: &str) -> Option<Self> {
+        let rest = path.strip_prefix("secret.")?;
+        let (provider, key) = rest.split_once('.')?;
+        if provider.is_empty() || key.is_empty() {
+            return None;
+        }
+        Some(Self::new(provider, key))
+    }
+
+    /// Template variable path for this reference (without braces)
+    pub fn path(&self) -> String {
+        format!("secret.{}.{}", self.provider, self.key)
+    }
+}
+
+impl std::fmt::Display for SecretReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{{ {} }}}}", self.path())
+    }
+}
+
+/// A source of secret values
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+    /// Provider name used in references (`secret.<name>.<key>`)
+    fn name(&self) -> &str;
+
+    /// Fetch the secret value for a key
+    async fn fetch(&self, key: &str) -> Result<String>;
+}
+
+/// Resolves secret references through registered providers
+pub struct SecretResolver {
+    /// Providers by name
+    providers: HashMap<String, Arc<dyn SecretProvider>>,
+    /// Resolved values by reference path, with the time they were fetched
+    cache: RwLock<HashMap<String, (String, Instant)>>,
+    /// How long a resolved value is reused (forever when unset)
+    ttl: Option<Duration>,
+}
+
+impl SecretResolver {
+    /// Create a resolver with no providers
+    pub fn new() -> Self {
+        Self {
+            providers: HashMap::new(),
+            cache: RwLock::new(HashMap::new()),
+            ttl: None,
+        }
+    }
+
+    /// Create a resolver with the `env` and `file` providers, plus `vault`
+    /// and `aws` when their environment variables are set
+    pub fn from_env() -> Self {
+        Self::remote_from_env()
+            .with_provider(Arc::new(EnvSecretProvider))
+            .with_provider(Arc::new(FileSecretProvider))
+    }
+
+    /// Create a resolver with only the remote providers (`vault` and `aws`,
+    /// when their environment variables are set)
+    ///
+    /// Used for config sent by HTTP clients, which must not read the
+    /// server's environment or filesystem.
+    pub fn remote_from_env() -> Self {
+        let mut resolver = Self::new();
+        if let Some(vault) = VaultSecretProvider::from_env() {
+            resolver.register(Arc::new(vault));
+        }
+        if let Some(aws) = AwsSecretsManagerProvider::from_env() {
+            resolver.register(Arc::new(aws));
+        }
+        resolver
+    }
+
+    /// Re-fetch cached values older than `ttl` (e.g., to pick up rotated secrets)
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Add a provider
+    #[must_use]
+    pub fn with_provider(mut self, provider: Arc<dyn SecretProvider>) -> Self {
+        self.register(provider);
+        self
+    }
+
+    /// Register a provider, replacing any provider with the same name
+    pub fn register(&mut self, provider: Arc<dyn SecretProvider>) {
+        self.providers.insert(provider.name().to_string(), provider);
+    }
+
+    /// Check if a provider is registered
+    pub fn has_provider(&self, name: &str) -> bool {
+        self.providers.contains_key(name)
+    }
+
+    /// Resolve a single reference
+    pub async fn resolve(&self, reference: &SecretReference) -> Result<String> {
+        let path = reference.path();
+        if let Some((value, fetched_at)) = self.cache.read().await.get(&path) {
+            if self.ttl.is_none_or(|ttl| fetched_at.elapsed() < ttl) {
+                // Cached values may be used in a different redaction scope
+                super::redact::register_secret(value);
+                return Ok(value.clone());
+            }
+        }
+
+        let provider = self.providers.get(&reference.provider).ok_or_else(|| {
+            Error::secret(format!(
+                "Unknown secret provider '{}' in {reference}",
+                reference.provider
+            ))
+        })?;
+
+        let value = provider
+            .fetch(&reference.key)
+            .await
+            .map_err(|e| Error::secret(format!("Failed to resolve {reference}: {e}")))?;
+
+        super::redact::register_secret(&value);
+        self.cache
+            .write()
+            .await
+            .insert(path, (value.clone(), Instant::now()));
+        Ok(value)
+    }
+
+    /// Replace every secret reference in a string
+    pub async fn resolve_str(&self, s: &str) -> Result<String> {
+        let resolved = self.prefetch(s).await?;
+        let result = SECRET_REGEX.replace_all(s, |cap: &regex::Captures<'_>| {
+            let path = SecretReference::new(&cap[1], &cap[2]).path();
+            resolved.get(&path).cloned().unwrap_or_default()
+        });
+        Ok(result.into_owned())
+    }
+
+    /// Replace secret references in all string values (recursively)
+    pub async fn resolve_value(&self, value: &Value) -> Result<Value> {
+        match value {
+            Value::String(s) if SECRET_REGEX.is_match(s) => {
+                Ok(Value::String(self.resolve_str(s).await?))
+            }
+            Value::Object(map) => {
+                let mut new_map = serde_json::Map::new();
+                for (k, v) in map {
+                    new_map.insert(k.clone(), Box::pin(self.resolve_value(v)).await?);
+                }
+                Ok(Value::Object(new_map))
+            }
+            Value::Array(arr) => {
+                let mut new_arr = Vec::with_capacity(arr.len());
+                for v in arr {
+                    new_arr.push(Box::pin(self.resolve_value(v)).await?);
+                }
+                Ok(Value::Array(new_arr))
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+
+    /// Resolve every reference found in a text (e.g., a serialized connector
+    /// definition) and return the values keyed by reference path, ready for
+    /// `TemplateContext::set_secrets`
+    pub async fn prefetch(&self, text: &str) -> Result<HashMap<String, String>> {
+        let mut resolved = HashMap::new();
+        for reference in find_references(text) {
+            let value = self.resolve(&reference).await?;
+            resolved.insert(reference.path(), value);
+        }
+        Ok(resolved)
+    }
+}
+
+impl Default for SecretResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SecretResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self.providers.keys().collect();
+        names.sort();
+        f.debug_struct("SecretResolver")
+            .field("providers", &names)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Find all secret references in a string (deduplicated, in order)
+pub fn find_references(s: &str) -> Vec<SecretReference> {
+    let mut refs: Vec<SecretReference> = Vec::new();
+    for cap in SECRET_REGEX.captures_iter(s) {
+        let reference = SecretReference::new(&cap[1], &cap[2]);
+        if !refs.contains(&reference) {
+            refs.push(reference);
+        }
+    }
+    refs
+}
+
+/// Check if a string is exactly one secret reference
+pub fn is_reference(s: &str) -> bool {
+    SECRET_REGEX
+        .find(s.trim())
+        .is_some_and(|m| m.as_str() == s.trim())
+}
+
+/// Resolve `env` and `file` references synchronously
+///
+/// Used by template rendering, which cannot await remote providers.
+/// Returns `None` for other providers or missing values.
+pub fn resolve_builtin(reference: &SecretReference) -> Option<String> {
+    match reference.provider.as_str() {
+        "env" => EnvSecretProvider::read(&reference.key).ok(),
+        "file" => FileSecretProvider::read(&reference.key).ok(),
+        _ => None,
+    }
+}
+
+/// Strict mode check: every secret field present in the config must be a
+/// secret reference rather than a raw value
+///
+/// Field names may use dot notation for nested values (e.g., "credentials.client_secret").
+pub fn require_references<S: AsRef<str>>(config: &Value, secret_fields: &[S]) -> Result<()> {
+    for field in secret_fields {
+        let field = field.as_ref();
+        let Some(current) = field
+            .split('.')
+            .try_fold(config, |current, part| current.get(part))
+        else {
+            continue;
+        };
+
+        let is_ref = match current {
+            Value::String(s) => s.is_empty() || is_reference(s),
+            Value::Null => true,
+            _ => false,
+        };
+        if !is_ref {
+            return Err(Error::InvalidConfigValue {
+                field: field.to_string(),
+                message: "secret fields must use a {{ secret.<provider>.<key> }} reference in strict mode"
+                    .to_string(),
+            });
+        }
+    }
+    Ok(())
+}
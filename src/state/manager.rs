@@ -2,8 +2,9 @@
 //!
 //! Provides file-based state persistence with atomic writes.
 
-use super::types::State;
+use super::types::{DateSlice, State, StreamState};
 use crate::error::{Error, Result};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -81,6 +82,16 @@ impl StateManager {
         })
     }
 
+    /// Another handle to the same state (updates through either are shared)
+    #[must_use]
+    pub fn share(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            state: Arc::clone(&self.state),
+            auto_save: self.auto_save,
+        }
+    }
+
     /// Save state to a specific file path
     pub async fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
         let state = self.state.read().await;
@@ -226,6 +237,130 @@ impl StateManager {
         Ok(())
     }
 
+    /// Clear completion flags for a stream's resumable partitions, keeping their cursors
+    pub async fn clear_completed_partitions(&self, stream: &str, incremental: bool) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            if let Some(stream_state) = state.streams.get_mut(stream) {
+                stream_state.clear_completed_partitions(incremental);
+            }
+        }
+
+        if self.auto_save {
+            self.save().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the adaptive date windows recorded for a stream
+    pub async fn get_slices(&self, stream: &str) -> Vec<DateSlice> {
+        let state = self.state.read().await;
+        state
+            .get_stream(stream)
+            .map(|s| s.slices.clone())
+            .unwrap_or_default()
+    }
+
+    /// Record the adaptive date windows for a stream
+    pub async fn set_slices(&self, stream: &str, slices: Vec<DateSlice>) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            state.get_stream_mut(stream).slices = slices;
+        }
+
+        if self.auto_save {
+            self.save().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the last streamed event ID of a stream or one of its partitions
+    pub async fn get_event_id(&self, stream: &str, partition_id: Option<&str>) -> Option<String> {
+        let state = self.state.read().await;
+        let stream_state = state.get_stream(stream)?;
+        match partition_id {
+            Some(id) => stream_state.get_partition(id)?.event_id.clone(),
+            None => stream_state.event_id.clone(),
+        }
+    }
+
+    /// Record the last streamed event ID of a stream or one of its partitions
+    pub async fn set_event_id(
+        &self,
+        stream: &str,
+        partition_id: Option<&str>,
+        event_id: String,
+    ) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            let stream_state = state.get_stream_mut(stream);
+            match partition_id {
+                Some(id) => stream_state.get_partition_mut(id).event_id = Some(event_id),
+                None => stream_state.event_id = Some(event_id),
+            }
+        }
+
+        if self.auto_save {
+            self.save().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Record a partition failure with its last error
+    pub async fn mark_partition_failed(
+        &self,
+        stream: &str,
+        partition_id: &str,
+        error: &str,
+    ) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            state
+                .get_stream_mut(stream)
+                .mark_partition_failed(partition_id, error);
+        }
+
+        if self.auto_save {
+            self.save().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if a partition failed in an earlier sync
+    pub async fn is_partition_failed(&self, stream: &str, partition_id: &str) -> bool {
+        let state = self.state.read().await;
+        state
+            .get_stream(stream)
+            .is_some_and(|s| s.is_partition_failed(partition_id))
+    }
+
+    /// Failed partitions of a stream with their last error
+    pub async fn failed_partitions(&self, stream: &str) -> Vec<(String, String)> {
+        let state = self.state.read().await;
+        state
+            .get_stream(stream)
+            .map(StreamState::failed_partitions)
+            .unwrap_or_default()
+    }
+
+    /// Cursors of a stream's partitions by partition ID
+    pub async fn partition_cursors(&self, stream: &str) -> HashMap<String, String> {
+        let state = self.state.read().await;
+        state
+            .get_stream(stream)
+            .map(|s| {
+                s.partitions
+                    .iter()
+                    .filter_map(|(id, p)| Some((id.clone(), p.cursor.clone()?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Get partition cursor
     pub async fn get_partition_cursor(&self, stream: &str, partition_id: &str) -> Option<String> {
         let state = self.state.read().await;
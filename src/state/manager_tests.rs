@@ -135,6 +135,135 @@ async fn test_partition_cursor() {
     );
 }
 
+#[tokio::test]
+async fn test_partition_cursors() {
+    let manager = StateManager::in_memory();
+    assert!(manager.partition_cursors("stream").await.is_empty());
+
+    manager
+        .set_partition_cursor("stream", "p1", "c1".to_string())
+        .await
+        .unwrap();
+    manager
+        .mark_partition_completed("stream", "p2")
+        .await
+        .unwrap();
+
+    // Partitions without a cursor are left out
+    let cursors = manager.partition_cursors("stream").await;
+    assert_eq!(cursors.len(), 1);
+    assert_eq!(cursors.get("p1").map(String::as_str), Some("c1"));
+}
+
+#[tokio::test]
+async fn test_clear_completed_partitions() {
+    let manager = StateManager::in_memory();
+
+    manager
+        .set_partition_cursor("stream", "p1", "c1".to_string())
+        .await
+        .unwrap();
+    manager
+        .mark_partition_completed("stream", "p1")
+        .await
+        .unwrap();
+    manager
+        .mark_partition_completed("other", "p1")
+        .await
+        .unwrap();
+
+    manager
+        .clear_completed_partitions("stream", false)
+        .await
+        .unwrap();
+    manager
+        .clear_completed_partitions("missing", false)
+        .await
+        .unwrap();
+
+    // Completion is cleared but the cursor is kept
+    assert!(!manager.is_partition_completed("stream", "p1").await);
+    assert_eq!(
+        manager.get_partition_cursor("stream", "p1").await,
+        Some("c1".to_string())
+    );
+    // Other streams are untouched
+    assert!(manager.is_partition_completed("other", "p1").await);
+}
+
+#[tokio::test]
+async fn test_partition_failures() {
+    let manager = StateManager::in_memory();
+    assert!(manager.failed_partitions("stream").await.is_empty());
+
+    manager
+        .mark_partition_failed("stream", "p1", "HTTP 503")
+        .await
+        .unwrap();
+    assert!(manager.is_partition_failed("stream", "p1").await);
+    assert!(!manager.is_partition_failed("stream", "p2").await);
+    assert_eq!(
+        manager.failed_partitions("stream").await,
+        vec![("p1".to_string(), "HTTP 503".to_string())]
+    );
+
+    manager
+        .mark_partition_completed("stream", "p1")
+        .await
+        .unwrap();
+    assert!(manager.failed_partitions("stream").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_slices() {
+    let manager = StateManager::in_memory();
+    assert!(manager.get_slices("stream").await.is_empty());
+
+    let slices = vec![DateSlice {
+        start: "2024-01-01T00:00:00Z".to_string(),
+        end: "2024-01-02T00:00:00Z".to_string(),
+        records: 10,
+        completed: true,
+        error: None,
+    }];
+    manager.set_slices("stream", slices.clone()).await.unwrap();
+
+    assert_eq!(manager.get_slices("stream").await, slices);
+    assert!(manager.get_slices("other").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_event_ids() {
+    let manager = StateManager::in_memory();
+    assert!(manager.get_event_id("events", None).await.is_none());
+
+    manager
+        .set_event_id("events", None, "evt_10".to_string())
+        .await
+        .unwrap();
+    manager
+        .set_event_id("events", Some("region-a"), "evt_3".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        manager.get_event_id("events", None).await,
+        Some("evt_10".to_string())
+    );
+    assert_eq!(
+        manager.get_event_id("events", Some("region-a")).await,
+        Some("evt_3".to_string())
+    );
+    assert!(manager
+        .get_event_id("events", Some("region-b"))
+        .await
+        .is_none());
+
+    // Saved in the state file next to the cursor
+    let json = manager.to_json().await.unwrap();
+    assert!(json.contains(r#""event_id":"evt_10""#));
+}
+
 // ============================================================================
 // Persistence Tests
 // ============================================================================
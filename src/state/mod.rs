@@ -14,7 +14,7 @@ mod manager;
 mod types;
 
 pub use manager::StateManager;
-pub use types::{PartitionState, State, StreamState};
+pub use types::{DateSlice, PartitionState, State, StreamState};
 
 #[cfg(test)]
 mod manager_tests;
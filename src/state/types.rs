@@ -50,6 +50,14 @@ pub struct StreamState {
     /// Per-partition state (for partitioned streams)
     #[serde(default)]
     pub partitions: HashMap<String, PartitionState>,
+
+    /// Date windows chosen by adaptive date-range partitioning
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slices: Vec<DateSlice>,
+
+    /// Last event ID of a streaming response (sent as `Last-Event-ID` on resume)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
 }
 
 impl StreamState {
@@ -75,12 +83,76 @@ impl StreamState {
             .is_some_and(|p| p.completed)
     }
 
-    /// Mark a partition as completed
+    /// Mark a partition as completed (clears any earlier failure)
     pub fn mark_partition_completed(&mut self, partition_id: &str) {
-        self.get_partition_mut(partition_id).completed = true;
+        let partition = self.get_partition_mut(partition_id);
+        partition.completed = true;
+        partition.error = None;
+    }
+
+    /// Record a partition failure with its last error
+    pub fn mark_partition_failed(&mut self, partition_id: &str, error: impl Into<String>) {
+        let partition = self.get_partition_mut(partition_id);
+        partition.completed = false;
+        partition.error = Some(error.into());
+    }
+
+    /// Check if a partition failed in an earlier sync
+    pub fn is_partition_failed(&self, partition_id: &str) -> bool {
+        self.partitions
+            .get(partition_id)
+            .is_some_and(|p| p.error.is_some())
+    }
+
+    /// IDs of failed partitions and date windows with their last error, sorted by ID
+    pub fn failed_partitions(&self) -> Vec<(String, String)> {
+        let mut failed: Vec<_> = self
+            .partitions
+            .iter()
+            .filter_map(|(id, p)| Some((id.clone(), p.error.clone()?)))
+            .chain(self.slices.iter().filter_map(|slice| {
+                Some((
+                    format!("{}_{}", slice.start, slice.end),
+                    slice.error.clone()?,
+                ))
+            }))
+            .collect();
+        failed.sort();
+        failed
+    }
+
+    /// Clear completion flags so partitions are re-visited (cursors are kept)
+    ///
+    /// Only partitions that resume from a cursor or event ID are cleared,
+    /// or every partition of an incremental stream; cursorless partitions
+    /// stay completed so their history is not fetched again.
+    pub fn clear_completed_partitions(&mut self, incremental: bool) {
+        for partition in self.partitions.values_mut() {
+            if incremental || partition.cursor.is_some() || partition.event_id.is_some() {
+                partition.completed = false;
+            }
+        }
     }
 }
 
+/// A date window chosen by adaptive date-range partitioning
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateSlice {
+    /// Window start (RFC 3339)
+    pub start: String,
+    /// Window end (RFC 3339, exclusive)
+    pub end: String,
+    /// Records synced from the window
+    #[serde(default)]
+    pub records: usize,
+    /// Whether the window has been synced in the current pass
+    #[serde(default)]
+    pub completed: bool,
+    /// Last error, if the window failed after all retries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// State for a single partition
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PartitionState {
@@ -91,6 +163,14 @@ pub struct PartitionState {
     /// Whether this partition has been fully synced
     #[serde(default)]
     pub completed: bool,
+
+    /// Last error, if the partition failed after all retries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Last event ID of a streaming response within this partition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
 }
 
 impl PartitionState {
@@ -102,8 +182,8 @@ impl PartitionState {
     /// Create a completed partition state
     pub fn completed() -> Self {
         Self {
-            cursor: None,
             completed: true,
+            ..Self::default()
         }
     }
 }
@@ -138,6 +218,79 @@ mod tests {
         assert!(!stream_state.is_partition_completed("p2"));
     }
 
+    #[test]
+    fn test_clear_completed_partitions() {
+        let mut stream_state = StreamState::new();
+        stream_state.mark_partition_completed("p1");
+        stream_state.get_partition_mut("p1").cursor = Some("2024-01-01".to_string());
+
+        stream_state.mark_partition_completed("p2");
+
+        stream_state.clear_completed_partitions(false);
+        assert!(!stream_state.is_partition_completed("p1"));
+        assert_eq!(
+            stream_state.get_partition("p1").unwrap().cursor.as_deref(),
+            Some("2024-01-01")
+        );
+        // Cursorless partitions are cleared only for an incremental stream
+        assert!(stream_state.is_partition_completed("p2"));
+        stream_state.clear_completed_partitions(true);
+        assert!(!stream_state.is_partition_completed("p2"));
+    }
+
+    #[test]
+    fn test_partition_failures() {
+        let mut stream_state = StreamState::new();
+        stream_state.mark_partition_failed("p2", "HTTP 500");
+        stream_state.mark_partition_failed("p1", "timeout");
+        assert!(stream_state.is_partition_failed("p1"));
+        assert!(!stream_state.is_partition_completed("p1"));
+        assert_eq!(
+            stream_state.failed_partitions(),
+            vec![
+                ("p1".to_string(), "timeout".to_string()),
+                ("p2".to_string(), "HTTP 500".to_string()),
+            ]
+        );
+
+        // A later success clears the failure
+        stream_state.mark_partition_completed("p1");
+        assert!(!stream_state.is_partition_failed("p1"));
+        assert_eq!(stream_state.failed_partitions().len(), 1);
+
+        // Healthy partitions omit the error field
+        let json = serde_json::to_value(stream_state.get_partition("p1")).unwrap();
+        assert!(json.get("error").is_none());
+    }
+
+    #[test]
+    fn test_slices_serialization() {
+        // Streams without slices omit the field
+        let json = serde_json::to_value(StreamState::new()).unwrap();
+        assert!(json.get("slices").is_none());
+
+        let mut stream_state = StreamState::new();
+        stream_state.slices.push(DateSlice {
+            start: "2024-01-01T00:00:00Z".to_string(),
+            end: "2024-01-02T00:00:00Z".to_string(),
+            records: 5,
+            completed: false,
+            error: Some("HTTP 500".to_string()),
+        });
+        let json = serde_json::to_string(&stream_state).unwrap();
+        let restored: StreamState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.slices, stream_state.slices);
+
+        // Failed windows are reported with failed partitions
+        assert_eq!(
+            restored.failed_partitions(),
+            vec![(
+                "2024-01-01T00:00:00Z_2024-01-02T00:00:00Z".to_string(),
+                "HTTP 500".to_string()
+            )]
+        );
+    }
+
     #[test]
     fn test_state_serialization() {
         let mut state = State::new();
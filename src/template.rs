@@ -2,19 +2,26 @@
 //!
 //! Handles `{{ variable }}` interpolation in connector configurations.
 //! Supports nested access like `{{ config.api_key }}` and `{{ partition.id }}`.
+//! Secret references like `{{ secret.env.API_KEY }}` are resolved through
+//! the context's prefetched secrets, falling back to the `env`/`file` providers.
 
 use crate::error::{Error, Result};
+use crate::secrets::{resolve_builtin, SecretReference};
 use regex::Regex;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
-/// Regex for matching template variables: {{ variable.path }}
+/// Regex for matching template variables: {{ variable.path }} or {{ secret.provider.key }}
 static TEMPLATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\{\{\s*([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)*)\s*\}\}").unwrap()
+    Regex::new(
+        r"\{\{\s*(secret\.[a-zA-Z_][a-zA-Z0-9_]*\.[^\s{}]+|[a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)*)\s*\}\}",
+    )
+    .unwrap()
 });
 
 /// Context for template interpolation
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct TemplateContext {
     /// Connector configuration values
     pub config: Value,
@@ -24,6 +31,22 @@ pub struct TemplateContext {
     pub state: Value,
     /// Additional context variables
     pub vars: Value,
+    /// Resolved secret values by reference path (e.g., "secret.vault.stripe#key")
+    pub secrets: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for TemplateContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut secret_paths: Vec<_> = self.secrets.keys().collect();
+        secret_paths.sort();
+        f.debug_struct("TemplateContext")
+            .field("config", &self.config)
+            .field("partition", &self.partition)
+            .field("state", &self.state)
+            .field("vars", &self.vars)
+            .field("secrets", &secret_paths)
+            .finish()
+    }
 }
 
 impl TemplateContext {
@@ -64,6 +87,33 @@ impl TemplateContext {
         self
     }
 
+    /// Set resolved secrets (see `SecretResolver::prefetch`)
+    pub fn set_secrets(&mut self, secrets: HashMap<String, String>) -> &mut Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Get a secret by reference path (e.g., "secret.env.API_KEY")
+    ///
+    /// Prefetched secrets take precedence; `env` and `file` references are
+    /// otherwise read directly.
+    pub fn get_secret(&self, path: &str) -> Option<String> {
+        if let Some(value) = self.secrets.get(path) {
+            return Some(value.clone());
+        }
+        SecretReference::parse(path).and_then(|r| resolve_builtin(&r))
+    }
+
+    /// Resolve a template variable path to its substitution string
+    fn lookup(&self, path: &str) -> Option<String> {
+        if path.starts_with("secret.") {
+            if let Some(secret) = self.get_secret(path) {
+                return Some(secret);
+            }
+        }
+        self.get(path).map(value_to_string)
+    }
+
     /// Get a value by path (e.g., "config.api_key")
     pub fn get(&self, path: &str) -> Option<&Value> {
         let parts: Vec<&str> = path.split('.').collect();
@@ -120,9 +170,8 @@ pub fn render(template: &str, ctx: &TemplateContext) -> Result<String> {
         let full_match = cap.get(0).unwrap().as_str();
         let var_path = cap.get(1).unwrap().as_str();
 
-        match ctx.get(var_path) {
-            Some(value) => {
-                let replacement = value_to_string(value);
+        match ctx.lookup(var_path) {
+            Some(replacement) => {
                 result = result.replace(full_match, &replacement);
             }
             None => {
@@ -146,8 +195,7 @@ pub fn render_optional(template: &str, ctx: &TemplateContext) -> String {
         let full_match = cap.get(0).unwrap().as_str();
         let var_path = cap.get(1).unwrap().as_str();
 
-        if let Some(value) = ctx.get(var_path) {
-            let replacement = value_to_string(value);
+        if let Some(replacement) = ctx.lookup(var_path) {
             result = result.replace(full_match, &replacement);
         }
         // Leave undefined variables as-is
@@ -349,4 +397,31 @@ mod tests {
             "test {{ config.missing }}"
         );
     }
+
+    #[test]
+    fn test_prefetched_secret() {
+        let mut ctx = TemplateContext::new();
+        let mut secrets = HashMap::new();
+        secrets.insert(
+            "secret.vault.stripe#api_key".to_string(),
+            "sk_live".to_string(),
+        );
+        ctx.set_secrets(secrets);
+
+        let result = render("Bearer {{ secret.vault.stripe#api_key }}", &ctx).unwrap();
+        assert_eq!(result, "Bearer sk_live");
+        assert!(!format!("{ctx:?}").contains("sk_live"));
+    }
+
+    #[test]
+    fn test_env_secret_fallback() {
+        std::env::set_var("SOLIDAFY_TEMPLATE_TEST_SECRET", "from_env");
+        let ctx = TemplateContext::new();
+
+        let result = render("{{ secret.env.SOLIDAFY_TEMPLATE_TEST_SECRET }}", &ctx).unwrap();
+        assert_eq!(result, "from_env");
+
+        // Unresolvable secrets are reported as undefined
+        assert!(render("{{ secret.vault.missing#key }}", &ctx).is_err());
+    }
 }
@@ -0,0 +1,26 @@
+//! Record transformation module
+//!
+//! Supports: Add field, Remove fields, Rename field, Flatten, Cast, Hash, Filter
+//!
+//! # Overview
+//!
+//! Streams can declare an ordered list of transformations that run on each
+//! page of decoded records before they are emitted:
+//!
+//! - Add a field from a template (`{{ partition.account_id }}`, `{{ config.region }}`)
+//! - Remove or rename fields (dot paths reach nested fields)
+//! - Flatten a nested object into prefixed top-level fields
+//! - Cast a field to a string, integer, number or boolean
+//! - Replace a field with its SHA-256 hash (e.g., to drop PII but keep joins)
+//! - Keep only records matching a predicate
+//!
+//! Pagination, incremental cursors and parent partitions read records as
+//! decoded, so transformations only shape the emitted output.
+
+mod pipeline;
+
+pub(crate) use pipeline::remove_field;
+pub use pipeline::{CastType, TransformPipeline, Transformation};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,330 @@
+//! Transformation pipeline
+//!
+//! Applies a stream's transformations, in order, to each decoded record.
+
+use crate::error::Result;
+use crate::pagination::set_body_field;
+use crate::partition::{extract_json_path, CompareOp};
+use crate::template::{self, TemplateContext};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Target type of a cast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastType {
+    /// String (objects and arrays become JSON text)
+    String,
+    /// 64-bit integer
+    Integer,
+    /// Floating point number
+    Number,
+    /// Boolean (`true`/`yes`/`1` and `false`/`no`/`0`)
+    Boolean,
+}
+
+impl CastType {
+    /// Parse a type name ("string", "integer", "number" or "boolean")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "string" => Some(Self::String),
+            "integer" => Some(Self::Integer),
+            "number" => Some(Self::Number),
+            "boolean" => Some(Self::Boolean),
+            _ => None,
+        }
+    }
+
+    /// Cast a value; values that cannot be cast are kept as-is
+    #[allow(clippy::cast_possible_truncation)]
+    fn cast(self, value: Value) -> Value {
+        let cast = match (self, &value) {
+            (_, Value::Null) | (Self::String, Value::String(_)) => None,
+            (Self::String, Value::Number(n)) => Some(Value::String(n.to_string())),
+            (Self::String, Value::Bool(b)) => Some(Value::String(b.to_string())),
+            (Self::String, _) => Some(Value::String(value.to_string())),
+            (Self::Integer, Value::Number(n)) => n
+                .as_i64()
+                .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64))
+                .map(Value::from),
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::from),
+            (Self::Number, Value::Number(n)) => n.as_f64().map(Value::from),
+            (Self::Number, Value::String(s)) => s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            (Self::Boolean, Value::String(s)) => match s.trim().to_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            (Self::Boolean, Value::Number(n)) => match n.as_i64() {
+                Some(1) => Some(Value::Bool(true)),
+                Some(0) => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        };
+        cast.unwrap_or(value)
+    }
+}
+
+/// A record transformation
+///
+/// Field paths are dot paths (`billing.address.city`). String values in
+/// `AddField`, `Hash` salts and `Filter` values are templates, rendered
+/// against the stream context (`config`, `partition`, `state`) once per page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transformation {
+    /// Set a field to a value (a lone `{{ var }}` keeps the variable's type)
+    AddField {
+        /// Field to set
+        path: String,
+        /// Value or template
+        value: Value,
+    },
+    /// Remove fields
+    RemoveFields {
+        /// Fields to remove
+        paths: Vec<String>,
+    },
+    /// Move a field to a new path
+    RenameField {
+        /// Current field
+        from: String,
+        /// New field
+        to: String,
+    },
+    /// Replace a nested object with prefixed top-level fields
+    ///
+    /// `{"billing": {"address": {"city": "Oslo"}}}` flattened at `billing`
+    /// becomes `{"billing_address_city": "Oslo"}`.
+    Flatten {
+        /// Object to flatten
+        path: String,
+        /// Separator between key segments
+        separator: String,
+        /// Key prefix (defaults to the path joined with the separator; empty for none)
+        prefix: Option<String>,
+    },
+    /// Cast a field to another type
+    Cast {
+        /// Field to cast
+        path: String,
+        /// Target type
+        to: CastType,
+    },
+    /// Replace a field with the hex SHA-256 hash of its value
+    Hash {
+        /// Field to hash
+        path: String,
+        /// Salt prepended to the value before hashing
+        salt: Option<String>,
+    },
+    /// Keep only records whose field compares true (missing fields compare as null)
+    Filter {
+        /// Field to compare
+        path: String,
+        /// Comparison operator
+        op: CompareOp,
+        /// Value compared against
+        value: Value,
+    },
+}
+
+impl Transformation {
+    /// Render the templates of a transformation against a context
+    fn render(&self, context: &TemplateContext) -> Result<Self> {
+        Ok(match self {
+            Self::AddField { path, value } => Self::AddField {
+                path: path.clone(),
+                value: render_typed(value, context)?,
+            },
+            Self::Hash { path, salt } => Self::Hash {
+                path: path.clone(),
+                salt: salt
+                    .as_deref()
+                    .map(|salt| template::render(salt, context))
+                    .transpose()?,
+            },
+            Self::Filter { path, op, value } => Self::Filter {
+                path: path.clone(),
+                op: *op,
+                value: render_typed(value, context)?,
+            },
+            other => other.clone(),
+        })
+    }
+
+    /// Apply to a record; `None` drops the record
+    fn apply(&self, mut record: Value) -> Option<Value> {
+        if !record.is_object() {
+            return Some(record);
+        }
+
+        match self {
+            Self::AddField { path, value } => set_body_field(&mut record, path, value.clone()),
+            Self::RemoveFields { paths } => {
+                for path in paths {
+                    remove_field(&mut record, path);
+                }
+            }
+            Self::RenameField { from, to } => {
+                if let Some(value) = remove_field(&mut record, from) {
+                    set_body_field(&mut record, to, value);
+                }
+            }
+            Self::Flatten {
+                path,
+                separator,
+                prefix,
+            } => {
+                match remove_field(&mut record, path) {
+                    Some(Value::Object(object)) => {
+                        let prefix = prefix
+                            .clone()
+                            .unwrap_or_else(|| path.replace('.', separator));
+                        if let Value::Object(fields) = &mut record {
+                            flatten_into(fields, &prefix, separator, object);
+                        }
+                    }
+                    // Not an object: put the field back
+                    Some(value) => set_body_field(&mut record, path, value),
+                    None => {}
+                }
+            }
+            Self::Cast { path, to } => {
+                if let Some(value) = extract_json_path(&record, path) {
+                    set_body_field(&mut record, path, to.cast(value));
+                }
+            }
+            Self::Hash { path, salt } => match extract_json_path(&record, path) {
+                Some(Value::Null) | None => {}
+                Some(value) => {
+                    set_body_field(&mut record, path, hash_value(&value, salt.as_deref()));
+                }
+            },
+            Self::Filter { path, op, value } => {
+                let field = extract_json_path(&record, path).unwrap_or(Value::Null);
+                if !op.compare(&field, value) {
+                    return None;
+                }
+            }
+        }
+        Some(record)
+    }
+}
+
+/// Ordered list of record transformations for a stream
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformPipeline {
+    /// Transformations, applied in order
+    steps: Vec<Transformation>,
+}
+
+impl TransformPipeline {
+    /// Create a pipeline from transformations
+    pub fn new(steps: Vec<Transformation>) -> Self {
+        Self { steps }
+    }
+
+    /// Whether the pipeline has no transformations
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Apply the pipeline to a page of records
+    ///
+    /// Templates are rendered once against the context, then every record
+    /// runs through the transformations; filtered records are dropped.
+    pub fn apply(&self, records: &[Value], context: &TemplateContext) -> Result<Vec<Value>> {
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| step.render(context))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(records
+            .iter()
+            .filter_map(|record| {
+                steps
+                    .iter()
+                    .try_fold(record.clone(), |record, step| step.apply(record))
+            })
+            .collect())
+    }
+}
+
+/// Render a template value, keeping the type of a lone `{{ var }}` reference
+fn render_typed(value: &Value, context: &TemplateContext) -> Result<Value> {
+    if let Value::String(s) = value {
+        if let [var] = template::extract_variables(s).as_slice() {
+            let is_lone = s
+                .trim()
+                .trim_start_matches("{{")
+                .trim_end_matches("}}")
+                .trim()
+                == var;
+            if let (true, Some(typed)) = (is_lone, context.get(var)) {
+                return Ok(typed.clone());
+            }
+        }
+    }
+    template::render_value(value, context)
+}
+
+/// Remove a field by dot path, returning its value
+pub(crate) fn remove_field(record: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent
+                .split('.')
+                .try_fold(record, |current, part| current.get_mut(part))?,
+            key,
+        ),
+        None => (record, path),
+    };
+    parent.as_object_mut()?.remove(key)
+}
+
+/// Insert an object's leaves into `fields` under prefixed keys
+fn flatten_into(
+    fields: &mut Map<String, Value>,
+    prefix: &str,
+    separator: &str,
+    object: Map<String, Value>,
+) {
+    for (key, value) in object {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}{separator}{key}")
+        };
+        match value {
+            Value::Object(nested) => flatten_into(fields, &key, separator, nested),
+            value => {
+                fields.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Hash a value (strings as-is, other values as JSON) with an optional salt
+fn hash_value(value: &Value, salt: Option<&str>) -> Value {
+    let mut hasher = Sha256::new();
+    if let Some(salt) = salt {
+        hasher.update(salt.as_bytes());
+    }
+    match value {
+        Value::String(s) => hasher.update(s.as_bytes()),
+        other => hasher.update(other.to_string().as_bytes()),
+    }
+    let digest = hasher.finalize();
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    Value::String(hex)
+}
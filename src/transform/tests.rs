@@ -0,0 +1,246 @@
+//! Tests for transform module
+
+use super::*;
+use crate::partition::CompareOp;
+use crate::template::TemplateContext;
+use serde_json::json;
+
+fn apply(steps: Vec<Transformation>, records: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    TransformPipeline::new(steps)
+        .apply(records, &TemplateContext::new())
+        .unwrap()
+}
+
+// ============================================================================
+// Field Transformations
+// ============================================================================
+
+#[test]
+fn test_add_field_from_partition_and_config() {
+    let mut context = TemplateContext::with_config(json!({"region": "eu"}));
+    context.set_partition(json!({"account_id": 42}));
+    let pipeline = TransformPipeline::new(vec![
+        Transformation::AddField {
+            path: "account_id".to_string(),
+            value: json!("{{ partition.account_id }}"),
+        },
+        Transformation::AddField {
+            path: "meta.source".to_string(),
+            value: json!("api-{{ config.region }}"),
+        },
+    ]);
+
+    let records = pipeline.apply(&[json!({"id": 1})], &context).unwrap();
+    // A lone variable keeps its type
+    assert_eq!(
+        records[0],
+        json!({"id": 1, "account_id": 42, "meta": {"source": "api-eu"}})
+    );
+}
+
+#[test]
+fn test_add_field_undefined_variable() {
+    let pipeline = TransformPipeline::new(vec![Transformation::AddField {
+        path: "account_id".to_string(),
+        value: json!("{{ partition.account_id }}"),
+    }]);
+    assert!(pipeline
+        .apply(&[json!({"id": 1})], &TemplateContext::new())
+        .is_err());
+}
+
+#[test]
+fn test_remove_and_rename_fields() {
+    let records = apply(
+        vec![
+            Transformation::RemoveFields {
+                paths: vec!["email".to_string(), "profile.phone".to_string()],
+            },
+            Transformation::RenameField {
+                from: "createdAt".to_string(),
+                to: "created_at".to_string(),
+            },
+            Transformation::RenameField {
+                from: "missing".to_string(),
+                to: "other".to_string(),
+            },
+        ],
+        &[json!({
+            "id": 1,
+            "email": "a@example.com",
+            "createdAt": "2024-01-01",
+            "profile": {"phone": "555", "name": "Alice"}
+        })],
+    );
+
+    assert_eq!(
+        records[0],
+        json!({"id": 1, "created_at": "2024-01-01", "profile": {"name": "Alice"}})
+    );
+}
+
+#[test]
+fn test_flatten() {
+    let record = json!({
+        "id": 1,
+        "billing": {"address": {"city": "Oslo", "zip": "0150"}, "plan": "pro"},
+        "tags": ["a"]
+    });
+
+    let records = apply(
+        vec![Transformation::Flatten {
+            path: "billing".to_string(),
+            separator: "_".to_string(),
+            prefix: None,
+        }],
+        &[record.clone()],
+    );
+    assert_eq!(
+        records[0],
+        json!({
+            "id": 1,
+            "billing_address_city": "Oslo",
+            "billing_address_zip": "0150",
+            "billing_plan": "pro",
+            "tags": ["a"]
+        })
+    );
+
+    let records = apply(
+        vec![Transformation::Flatten {
+            path: "billing.address".to_string(),
+            separator: ".".to_string(),
+            prefix: Some(String::new()),
+        }],
+        &[record],
+    );
+    assert_eq!(records[0]["city"], "Oslo");
+    assert_eq!(records[0]["billing"], json!({"plan": "pro"}));
+}
+
+#[test]
+fn test_flatten_non_object_is_kept() {
+    let records = apply(
+        vec![Transformation::Flatten {
+            path: "tags".to_string(),
+            separator: "_".to_string(),
+            prefix: None,
+        }],
+        &[json!({"id": 1, "tags": ["a", "b"]})],
+    );
+    assert_eq!(records[0], json!({"id": 1, "tags": ["a", "b"]}));
+}
+
+#[test]
+fn test_cast() {
+    let cast = |to: CastType, value: serde_json::Value| {
+        apply(
+            vec![Transformation::Cast {
+                path: "v".to_string(),
+                to,
+            }],
+            &[json!({ "v": value })],
+        )[0]["v"]
+            .clone()
+    };
+
+    assert_eq!(cast(CastType::Integer, json!("42")), json!(42));
+    assert_eq!(cast(CastType::Integer, json!(3.0)), json!(3));
+    assert_eq!(cast(CastType::Number, json!("12.5")), json!(12.5));
+    assert_eq!(cast(CastType::Boolean, json!("yes")), json!(true));
+    assert_eq!(cast(CastType::Boolean, json!(0)), json!(false));
+    assert_eq!(cast(CastType::String, json!(7)), json!("7"));
+    assert_eq!(cast(CastType::String, json!({"a": 1})), json!(r#"{"a":1}"#));
+    // Values that cannot be cast are kept
+    assert_eq!(cast(CastType::Integer, json!("n/a")), json!("n/a"));
+    assert_eq!(cast(CastType::Number, json!(null)), json!(null));
+}
+
+#[test]
+fn test_cast_type_parse() {
+    assert_eq!(CastType::parse("Integer"), Some(CastType::Integer));
+    assert_eq!(CastType::parse("number"), Some(CastType::Number));
+    assert_eq!(CastType::parse("date"), None);
+}
+
+#[test]
+fn test_hash() {
+    let records = apply(
+        vec![Transformation::Hash {
+            path: "email".to_string(),
+            salt: None,
+        }],
+        &[json!({"email": "abc"}), json!({"email": null})],
+    );
+    assert_eq!(
+        records[0]["email"],
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(records[1]["email"], json!(null));
+
+    let context = TemplateContext::with_config(json!({"salt": "s3cr3t"}));
+    let salted = TransformPipeline::new(vec![Transformation::Hash {
+        path: "email".to_string(),
+        salt: Some("{{ config.salt }}".to_string()),
+    }])
+    .apply(&[json!({"email": "abc"})], &context)
+    .unwrap();
+    assert_ne!(salted[0]["email"], records[0]["email"]);
+}
+
+// ============================================================================
+// Filter
+// ============================================================================
+
+#[test]
+fn test_filter() {
+    let records = apply(
+        vec![
+            Transformation::Filter {
+                path: "status".to_string(),
+                op: CompareOp::Ne,
+                value: json!("deleted"),
+            },
+            Transformation::Filter {
+                path: "amount".to_string(),
+                op: CompareOp::Gte,
+                value: json!(10),
+            },
+        ],
+        &[
+            json!({"id": 1, "status": "open", "amount": 25}),
+            json!({"id": 2, "status": "deleted", "amount": 50}),
+            json!({"id": 3, "status": "open", "amount": "5"}),
+            json!({"id": 4, "status": "open"}),
+        ],
+    );
+
+    let ids: Vec<_> = records.iter().map(|r| r["id"].clone()).collect();
+    assert_eq!(ids, vec![json!(1)]);
+}
+
+#[test]
+fn test_pipeline_order_and_non_objects() {
+    let records = apply(
+        vec![
+            Transformation::RenameField {
+                from: "state".to_string(),
+                to: "status".to_string(),
+            },
+            Transformation::Filter {
+                path: "status".to_string(),
+                op: CompareOp::Eq,
+                value: json!("open"),
+            },
+        ],
+        &[
+            json!({"state": "open"}),
+            json!({"state": "closed"}),
+            json!("raw"),
+        ],
+    );
+
+    // Non-object records pass through untouched
+    assert_eq!(records, vec![json!({"status": "open"}), json!("raw")]);
+    assert!(TransformPipeline::default().is_empty());
+}